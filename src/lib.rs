@@ -64,9 +64,6 @@ pub type AccountId = <AccountSignature as Verify>::Signer;
 /// The type used by accounts to prove their ID.
 pub type AccountSignature = AnySignature;
 
-/// Alias for ContentId, used in various places.
-pub type ContentId = primitives::H256;
-
 /// A hash of some data used by the chain.
 pub type Hash = primitives::H256;
 
@@ -117,7 +114,7 @@ pub const VERSION: RuntimeVersion = RuntimeVersion {
     spec_name: create_runtime_str!("subsocial-node"),
     impl_name: create_runtime_str!("subsocial-node"),
     authoring_version: 5,
-    spec_version: 2,
+    spec_version: 7,
     impl_version: 0,
     apis: RUNTIME_API_VERSIONS,
 };
@@ -234,6 +231,7 @@ impl blogs::Trait for Runtime {
     type PostId = u64;
     type CommentId = u64;
     type ReactionId = u64;
+    type MessageId = u64;
 }
 
 impl migration::Trait for Runtime {
@@ -395,4 +393,26 @@ impl_runtime_apis! {
             Consensus::authorities()
         }
     }
+
+    impl social::api::BlogsApi<Block, AccountId, BlockNumber, u64, u64, u64> for Runtime {
+        fn trending_tags(limit: u32) -> Vec<(Vec<u8>, u32)> {
+            Blogs::trending_tags(limit)
+        }
+
+        fn blog_post_ids(blog_id: u64) -> Vec<u64> {
+            Blogs::blog_post_ids(blog_id)
+        }
+
+        fn post_comment_ids(post_id: u64) -> Vec<u64> {
+            Blogs::post_comment_ids(post_id)
+        }
+
+        fn is_post_visible_to(post_id: u64, account: AccountId) -> bool {
+            Blogs::post_by_id(post_id).map_or(false, |post| Blogs::is_post_visible_to(&post, &account))
+        }
+
+        fn active_attestations(account: AccountId) -> Vec<(AccountId, blogs::AttestationClaim, Option<BlockNumber>)> {
+            Blogs::active_attestation_claims(account)
+        }
+    }
 }
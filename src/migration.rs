@@ -1,4 +1,5 @@
 use crate::VERSION;
+use crate::social::blogs;
 use runtime_io::print;
 use srml_support::{decl_event, decl_module, decl_storage, StorageValue};
 use system;
@@ -8,19 +9,25 @@ use system;
 // the runtime doesn't need to maintain any logic for old migrations. All knowledge about state of the chain and runtime
 // prior to the new runtime taking over is implicit in the migration code implementation. If assumptions are incorrect
 // behaviour is undefined.
-const MIGRATION_FOR_SPEC_VERSION: u32 = 2;
+const MIGRATION_FOR_SPEC_VERSION: u32 = 7;
+
+// The last runtime deployed before this release. The storage layout it left is described in `social::legacy`.
+const DEPLOYED_SPEC_VERSION: u32 = 2;
 
 impl<T: Trait> Module<T> {
-    fn runtime_initialization() {
+    fn runtime_initialization(previous_spec_version: Option<u32>) {
         if VERSION.spec_version != MIGRATION_FOR_SPEC_VERSION {
             return;
         }
 
         print("running runtime initializers");
 
-        // ...
-        // add initialization of other modules introduced in this runtime
-        // ...
+        // Blogs, posts, comments and profiles are rewritten from the deployed layout in chunks
+        // over the following blocks: content became a `Content` enum, edit histories and counters moved
+        // to separate maps, posts got tags and visibility. A chain that already runs a newer layout is left as is.
+        if previous_spec_version.map_or(false, |spec_version| spec_version <= DEPLOYED_SPEC_VERSION) {
+            <blogs::Module<T>>::start_legacy_migration();
+        }
 
         Self::deposit_event(RawEvent::Migrated(
             <system::Module<T>>::block_number(),
@@ -29,7 +36,7 @@ impl<T: Trait> Module<T> {
     }
 }
 
-pub trait Trait: system::Trait {
+pub trait Trait: system::Trait + blogs::Trait {
     type Event: From<Event<Self>> + Into<<Self as system::Trait>::Event>;
 }
 
@@ -52,12 +59,13 @@ decl_module! {
         fn deposit_event<T>() = default;
 
         fn on_initialize(_now: T::BlockNumber) {
-            if Self::spec_version().map_or(true, |spec_version| VERSION.spec_version > spec_version) {
+            let previous_spec_version = Self::spec_version();
+            if previous_spec_version.map_or(true, |spec_version| VERSION.spec_version > spec_version) {
                 // mark store version with current version of the runtime
                 <SpecVersion<T>>::put(VERSION.spec_version);
 
                 // run migrations and store initializers
-                Self::runtime_initialization();
+                Self::runtime_initialization(previous_spec_version);
             }
         }
     }
@@ -0,0 +1,31 @@
+//! Runtime API of the Blogs module for queries that can not be answered by reading storage directly.
+
+use rstd::prelude::*;
+use parity_codec::Codec;
+use substrate_client::decl_runtime_apis;
+use super::blogs::AttestationClaim;
+
+decl_runtime_apis! {
+  pub trait BlogsApi<AccountId, BlockNumber, BlogId, PostId, CommentId> where
+    AccountId: Codec,
+    BlockNumber: Codec,
+    BlogId: Codec,
+    PostId: Codec,
+    CommentId: Codec,
+  {
+    /// Up to `limit` tags that were used the most within the trending window, along with their usage.
+    fn trending_tags(limit: u32) -> Vec<(Vec<u8>, u32)>;
+
+    /// Posts listed in the blog, with the pinned ones first.
+    fn blog_post_ids(blog_id: BlogId) -> Vec<PostId>;
+
+    /// Comments on the post, with the pinned ones first.
+    fn post_comment_ids(post_id: PostId) -> Vec<CommentId>;
+
+    /// Whether the account can see the post. Drafts are seen only by their author and blog writers.
+    fn is_post_visible_to(post_id: PostId, account: AccountId) -> bool;
+
+    /// Verifier, claim and expiration block of each attestation of the account that is neither revoked nor expired.
+    fn active_attestations(account: AccountId) -> Vec<(AccountId, AttestationClaim, Option<BlockNumber>)>;
+  }
+}
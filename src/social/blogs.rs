@@ -1,15 +1,21 @@
 use super::defaults::*;
 use super::messages::*;
+use super::content::Content;
+use crate::currency::{GovernanceCurrency, BalanceOf};
 
 use rstd::prelude::*;
 use parity_codec::Codec;
 use parity_codec_derive::{Encode, Decode};
 use srml_support::{StorageMap, StorageValue, decl_module, decl_storage, decl_event, ensure, Parameter};
-use runtime_primitives::traits::{SimpleArithmetic, As, Member, MaybeDebug, MaybeSerializeDebug};
-use system::{self, ensure_signed};
+use srml_support::traits::LockIdentifier;
+use runtime_primitives::traits::{SimpleArithmetic, As, Member, MaybeDebug, MaybeSerializeDebug, Zero, CheckedAdd};
+use system::{self, ensure_signed, ensure_root};
 use {timestamp};
 
-pub trait Trait: system::Trait + timestamp::Trait + MaybeDebug {
+pub const USERNAME_LOCK_ID: LockIdentifier = *b"username";
+pub const VOTING_STAKE_LOCK_ID: LockIdentifier = *b"votestak";
+
+pub trait Trait: system::Trait + timestamp::Trait + GovernanceCurrency + MaybeDebug {
 
   type Event: From<Event<Self>> + Into<<Self as system::Trait>::Event>;
 
@@ -24,6 +30,9 @@ pub trait Trait: system::Trait + timestamp::Trait + MaybeDebug {
 
   type ReactionId: Parameter + Member + SimpleArithmetic + Codec + Default + Copy
     + As<usize> + As<u64> + MaybeSerializeDebug + PartialEq;
+
+  type MessageId: Parameter + Member + SimpleArithmetic + Codec + Default + Copy
+    + As<usize> + As<u64> + MaybeSerializeDebug + PartialEq;
 }
 
 #[cfg_attr(feature = "std", derive(Debug))]
@@ -46,13 +55,15 @@ pub struct Blog<T: Trait> {
   // Can be updated by the owner:
   pub writers: Vec<T::AccountId>,
   pub slug: Vec<u8>,
-  pub ipfs_hash: Vec<u8>,
+  pub content: Content,
+}
 
+/// Counters and score change often, so they are stored apart from the blog itself.
+#[cfg_attr(feature = "std", derive(Debug))]
+#[derive(Clone, Default, Encode, Decode, PartialEq)]
+pub struct BlogCounters {
   pub posts_count: u16,
   pub followers_count: u32,
-
-  pub edit_history: Vec<BlogHistoryRecord<T>>,
-
   pub score: i32,
 }
 
@@ -61,7 +72,7 @@ pub struct Blog<T: Trait> {
 pub struct BlogUpdate<T: Trait> {
   pub writers: Option<Vec<T::AccountId>>,
   pub slug: Option<Vec<u8>>,
-  pub ipfs_hash: Option<Vec<u8>>,
+  pub content: Option<Content>,
 }
 
 #[cfg_attr(feature = "std", derive(Debug))]
@@ -82,15 +93,34 @@ pub struct Post<T: Trait> {
 
   // Next fields can be updated by the owner only:
 
-  pub ipfs_hash: Vec<u8>,
+  pub content: Content,
+  pub tags: Vec<Vec<u8>>,
+  pub visibility: PostVisibility,
+}
 
+/// Drafts are seen only by the author and blog writers, unlisted posts are reachable only by id.
+/// Only public posts are listed in blogs and can be commented, reacted, shared or scored.
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize, Debug))]
+#[derive(Clone, Copy, Encode, Decode, PartialEq, Eq)]
+pub enum PostVisibility {
+    Draft,
+    Unlisted,
+    Public,
+}
+
+impl Default for PostVisibility {
+    fn default() -> Self {
+        PostVisibility::Public
+    }
+}
+
+#[cfg_attr(feature = "std", derive(Debug))]
+#[derive(Clone, Default, Encode, Decode, PartialEq)]
+pub struct PostCounters {
   pub comments_count: u16,
   pub upvotes_count: u16,
   pub downvotes_count: u16,
   pub shares_count: u16,
-
-  pub edit_history: Vec<PostHistoryRecord<T>>,
-
   pub score: i32,
 }
 
@@ -98,7 +128,8 @@ pub struct Post<T: Trait> {
 #[derive(Clone, Encode, Decode, PartialEq)]
 pub struct PostUpdate<T: Trait> {
   pub blog_id: Option<T::BlogId>,
-  pub ipfs_hash: Option<Vec<u8>>,
+  pub content: Option<Content>,
+  pub tags: Option<Vec<Vec<u8>>>,
 }
 
 #[cfg_attr(feature = "std", derive(Debug))]
@@ -109,11 +140,23 @@ pub struct PostHistoryRecord<T: Trait> {
 }
 
 #[cfg_attr(feature = "std", derive(Serialize, Deserialize, Debug))]
-#[derive(Clone, Copy, Encode, Decode, PartialEq, Eq)]
+#[derive(Clone, Encode, Decode, PartialEq, Eq)]
 pub enum PostExtension<T: Trait> {
     RegularPost,
     SharedPost(T::PostId),
     SharedComment(T::CommentId),
+    Poll(Poll<T>),
+}
+
+/// The question of a poll is the content of its post.
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize, Debug))]
+#[derive(Clone, Encode, Decode, PartialEq, Eq)]
+pub struct Poll<T: Trait> {
+  pub options: Vec<Vec<u8>>,
+  // Votes are accepted up to and including this block:
+  pub ends_at: T::BlockNumber,
+  pub multiple_choice: bool,
+  pub weighted_by_reputation: bool,
 }
 
 impl <T: Trait> Default for PostExtension<T> {
@@ -132,22 +175,23 @@ pub struct Comment<T: Trait> {
   pub updated: Option<Change<T>>,
 
   // Can be updated by the owner:
-  pub ipfs_hash: Vec<u8>,
+  pub content: Content,
+}
 
+#[cfg_attr(feature = "std", derive(Debug))]
+#[derive(Clone, Default, Encode, Decode, PartialEq)]
+pub struct CommentCounters {
   pub upvotes_count: u16,
   pub downvotes_count: u16,
   pub shares_count: u16,
   pub direct_replies_count: u16,
-
-  pub edit_history: Vec<CommentHistoryRecord<T>>,
-
   pub score: i32,
 }
 
 #[cfg_attr(feature = "std", derive(Debug))]
 #[derive(Clone, Encode, Decode, PartialEq)]
 pub struct CommentUpdate {
-  pub ipfs_hash: Vec<u8>,
+  pub content: Content,
 }
 
 #[cfg_attr(feature = "std", derive(Debug))]
@@ -157,6 +201,107 @@ pub struct CommentHistoryRecord<T: Trait> {
   pub old_data: CommentUpdate,
 }
 
+/// An account mentioned in a post or comment, either directly or by its profile username.
+#[cfg_attr(feature = "std", derive(Debug))]
+#[derive(Clone, Encode, Decode, PartialEq)]
+pub enum Mention<T: Trait> {
+  Account(T::AccountId),
+  Username(Vec<u8>),
+}
+
+/// A direct message. Only a hash of the encrypted content is stored on chain.
+#[cfg_attr(feature = "std", derive(Debug))]
+#[derive(Clone, Encode, Decode, PartialEq)]
+pub struct Message<T: Trait> {
+  pub id: T::MessageId,
+  pub created: Change<T>,
+  pub recipient: T::AccountId,
+  // Position of this message in its conversation:
+  pub index: u32,
+  pub content: Content,
+}
+
+#[cfg_attr(feature = "std", derive(Debug))]
+#[derive(Clone, Encode, Decode, PartialEq)]
+pub enum Bookmark<T: Trait> {
+  Post(T::PostId),
+  Comment(T::CommentId),
+}
+
+/// A named list of bookmarks that other accounts can see.
+#[cfg_attr(feature = "std", derive(Debug))]
+#[derive(Clone, Encode, Decode, PartialEq)]
+pub struct Collection<T: Trait> {
+  pub created: Change<T>,
+  pub updated: Option<Change<T>>,
+  pub name: Vec<u8>,
+  pub items: Vec<Bookmark<T>>,
+}
+
+#[cfg_attr(feature = "std", derive(Debug))]
+#[derive(Clone, Encode, Decode, PartialEq)]
+pub struct Notification<T: Trait> {
+  // The account that caused this notification:
+  pub created: Change<T>,
+  pub kind: NotificationKind<T>,
+}
+
+#[cfg_attr(feature = "std", derive(Debug))]
+#[derive(Clone, Encode, Decode, PartialEq)]
+pub enum NotificationKind<T: Trait> {
+  AccountFollowed,
+  PostCommented(T::PostId, T::CommentId),
+  // The parent comment and the reply:
+  CommentReplied(T::CommentId, T::CommentId),
+  PostReacted(T::PostId, T::ReactionId),
+  CommentReacted(T::CommentId, T::ReactionId),
+  // The original post or comment and the post that shares it:
+  PostShared(T::PostId, T::PostId),
+  CommentShared(T::CommentId, T::PostId),
+  CoAuthorInvited(T::PostId),
+  PostTipped(T::PostId),
+}
+
+/// Rewards of an ended epoch that are being paid out, a chunk of authors per block.
+#[cfg_attr(feature = "std", derive(Debug))]
+#[derive(Clone, Encode, Decode, PartialEq)]
+pub struct RewardDistribution<T: Trait> {
+  pub epoch: u32,
+  pub amount: BalanceOf<T>,
+  pub paid: BalanceOf<T>,
+  // Index of the next author in `EpochScoredAccount`:
+  pub next_index: u32,
+}
+
+/// What the migration from the spec version 2 layout is going to do next, item by item.
+/// Accounts are found by the legacy items that refer to them and queued in `LegacyAccount`.
+#[cfg_attr(feature = "std", derive(Debug))]
+#[derive(Clone, Encode, Decode, PartialEq)]
+pub enum LegacyMigrationStep<T: Trait> {
+  Blogs(T::BlogId),
+  LegacySlugKeys(T::BlogId),
+  SlugKeys(T::BlogId),
+  Posts(T::PostId),
+  Comments(T::CommentId),
+  Reactions(T::ReactionId),
+  PostScores(T::PostId),
+  Profiles(u32),
+  LegacyUsernameKeys(u32),
+  UsernameKeys(u32),
+}
+
+/// The legacy layout is migrated in chunks. Only the items that existed at the upgrade are migrated,
+/// as the ones created later are stored in the current layout.
+#[cfg_attr(feature = "std", derive(Debug))]
+#[derive(Clone, Encode, Decode, PartialEq)]
+pub struct LegacyMigration<T: Trait> {
+  pub step: LegacyMigrationStep<T>,
+  pub next_blog_id: T::BlogId,
+  pub next_post_id: T::PostId,
+  pub next_comment_id: T::CommentId,
+  pub next_reaction_id: T::ReactionId,
+}
+
 #[cfg_attr(feature = "std", derive(Serialize, Deserialize, Debug))]
 #[derive(Clone, Copy, Encode, Decode, PartialEq, Eq)]
 pub enum ReactionKind {
@@ -196,16 +341,14 @@ pub struct Profile<T: Trait> {
   pub updated: Option<Change<T>>,
 
   pub username: Vec<u8>,
-  pub ipfs_hash: Vec<u8>,
-  
-  pub edit_history: Vec<ProfileHistoryRecord<T>>,
+  pub content: Content,
 }
 
 #[cfg_attr(feature = "std", derive(Debug))]
 #[derive(Clone, Encode, Decode, PartialEq)]
 pub struct ProfileUpdate {
   pub username: Option<Vec<u8>>,
-  pub ipfs_hash: Option<Vec<u8>>,
+  pub content: Option<Content>,
 }
 
 #[cfg_attr(feature = "std", derive(Debug))]
@@ -215,6 +358,24 @@ pub struct ProfileHistoryRecord<T: Trait> {
   pub old_data: ProfileUpdate,
 }
 
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize, Debug))]
+#[derive(Clone, Encode, Decode, PartialEq, Eq)]
+pub enum AttestationClaim {
+  Identity,
+  Organization(Vec<u8>),
+  Domain(Vec<u8>),
+}
+
+#[cfg_attr(feature = "std", derive(Debug))]
+#[derive(Clone, Encode, Decode, PartialEq)]
+pub struct Attestation<T: Trait> {
+  pub verifier: T::AccountId,
+  pub claim: AttestationClaim,
+  pub created: Change<T>,
+  pub expires_at: Option<T::BlockNumber>,
+  pub revoked: Option<Change<T>>,
+}
+
 #[cfg_attr(feature = "std", derive(Serialize, Deserialize, Debug))]
 #[derive(Clone, Copy, Encode, Decode, PartialEq, Eq)]
 pub enum ScoringAction {
@@ -235,13 +396,42 @@ impl Default for ScoringAction {
   }
 }
 
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize, Debug))]
+#[derive(Clone, Copy, Encode, Decode, PartialEq, Eq)]
+pub enum PaidAction {
+  CreateBlog,
+  UpdateBlog,
+  FollowBlog,
+  FollowAccount,
+  CreatePost,
+  UpdatePost,
+  CreateComment,
+  UpdateComment,
+  React,
+  CreateProfile,
+  UpdateProfile,
+  SendMessage,
+}
+
 decl_storage! {
   trait Store for Module<T: Trait> as Blogs {
 
     pub SlugMinLen get(slug_min_len): u32 = DEFAULT_SLUG_MIN_LEN;
     pub SlugMaxLen get(slug_max_len): u32 = DEFAULT_SLUG_MAX_LEN;
 
-    pub IpfsHashLen get(ipfs_hash_len): u32 = DEFAULT_IPFS_HASH_LEN;
+    pub MaxBookmarksPerAccount get(max_bookmarks_per_account): u32 = DEFAULT_MAX_BOOKMARKS_PER_ACCOUNT;
+    pub MaxCollectionsPerAccount get(max_collections_per_account): u32 = DEFAULT_MAX_COLLECTIONS_PER_ACCOUNT;
+    pub MaxItemsPerCollection get(max_items_per_collection): u32 = DEFAULT_MAX_ITEMS_PER_COLLECTION;
+    pub CollectionNameMaxLen get(collection_name_max_len): u32 = DEFAULT_COLLECTION_NAME_MAX_LEN;
+
+    pub TagMaxLen get(tag_max_len): u32 = DEFAULT_TAG_MAX_LEN;
+    pub MaxTagsPerPost get(max_tags_per_post): u32 = DEFAULT_MAX_TAGS_PER_POST;
+
+    // How many shares can follow each other, e.g. a quote of a quote of a post:
+    pub MaxQuoteDepth get(max_quote_depth): u32 = DEFAULT_MAX_QUOTE_DEPTH;
+
+    pub PollMaxOptions get(poll_max_options): u32 = DEFAULT_POLL_MAX_OPTIONS;
+    pub PollOptionMaxLen get(poll_option_max_len): u32 = DEFAULT_POLL_OPTION_MAX_LEN;
 
     pub UsernameMinLen get(username_min_len): u32 = DEFAULT_USERNAME_MIN_LEN;
     pub UsernameMaxLen get(username_max_len): u32 = DEFAULT_USERNAME_MAX_LEN;
@@ -260,13 +450,38 @@ decl_storage! {
     pub FollowBlogActionWeight get (follow_blog_action_weight): i16 = DEFAULT_FOLLOW_BLOG_ACTION_WEIGHT;
     pub FollowAccountActionWeight get (follow_account_action_weight): i16 = DEFAULT_FOLLOW_ACCOUNT_ACTION_WEIGHT;
 
+    pub BaseFeeByAction get(base_fee_by_action): map PaidAction => BalanceOf<T>;
+    pub FeePerByte get(fee_per_byte): BalanceOf<T>;
+    pub FeeDiscountReputation get(fee_discount_reputation): u32 = DEFAULT_FEE_DISCOUNT_REPUTATION;
+    pub FeeDiscountPercent get(fee_discount_percent): u8 = DEFAULT_FEE_DISCOUNT_PERCENT;
+    pub FeeExemptReputation get(fee_exempt_reputation): u32 = DEFAULT_FEE_EXEMPT_REPUTATION;
+
     pub BlogById get(blog_by_id): map T::BlogId => Option<Blog<T>>;
     pub PostById get(post_by_id): map T::PostId => Option<Post<T>>;
     pub CommentById get(comment_by_id): map T::CommentId => Option<Comment<T>>;
+
+    pub BlogCountersById get(blog_counters): map T::BlogId => BlogCounters;
+    pub PostCountersById get(post_counters): map T::PostId => PostCounters;
+    pub CommentCountersById get(comment_counters): map T::CommentId => CommentCounters;
     pub ReactionById get(reaction_by_id): map T::ReactionId => Option<Reaction<T>>;
     pub SocialAccountById get(social_account_by_id): map T::AccountId => Option<SocialAccount<T>>;
 
     pub BlogIdsByOwner get(blog_ids_by_owner): map T::AccountId => Vec<T::BlogId>;
+    // Blogs other than its own one where a post is listed as well:
+    pub CrossPostBlogIds get(cross_post_blog_ids): map T::PostId => Vec<T::BlogId>;
+    pub MaxCrossPostsPerPost get(max_cross_posts_per_post): u32 = DEFAULT_MAX_CROSS_POSTS_PER_POST;
+    // The part of a post's score that was earned while it was listed in a blog:
+    pub PostScoreInBlog get(post_score_in_blog): map (T::BlogId, T::PostId) => i32;
+    pub MaxCoAuthorsPerPost get(max_co_authors_per_post): u32 = DEFAULT_MAX_CO_AUTHORS_PER_POST;
+    // Co-authors along with their percentage shares. The post creator keeps the rest:
+    pub CoAuthorInvites get(co_author_invites): map T::PostId => Vec<(T::AccountId, u8)>;
+    pub CoAuthors get(co_authors): map T::PostId => Vec<(T::AccountId, u8)>;
+    pub MaxPinnedPostsPerBlog get(max_pinned_posts_per_blog): u32 = DEFAULT_MAX_PINNED_POSTS_PER_BLOG;
+    pub MaxPinnedCommentsPerPost get(max_pinned_comments_per_post): u32 = DEFAULT_MAX_PINNED_COMMENTS_PER_POST;
+    // In the order they were pinned:
+    pub PinnedPostIds get(pinned_post_ids): map T::BlogId => Vec<T::PostId>;
+    pub PinnedCommentIds get(pinned_comment_ids): map T::PostId => Vec<T::CommentId>;
+
     pub PostIdsByBlogId get(post_ids_by_blog_id): map T::BlogId => Vec<T::PostId>;
     pub CommentIdsByPostId get(comment_ids_by_post_id): map T::PostId => Vec<T::CommentId>;
 
@@ -276,8 +491,15 @@ decl_storage! {
     pub CommentReactionIdByAccount get(comment_reaction_id_by_account): map (T::AccountId, T::CommentId) => T::ReactionId;
 
     pub BlogIdBySlug get(blog_id_by_slug): map Vec<u8> => Option<T::BlogId>;
+    pub ReservedSlugs get(is_slug_reserved): map Vec<u8> => bool;
+    pub SlugRedirectPeriod get(slug_redirect_period): T::BlockNumber = T::BlockNumber::sa(DEFAULT_SLUG_REDIRECT_PERIOD);
+    // Old slugs of renamed blogs keep resolving to their blog until this block:
+    pub SlugRedirectExpiresAt get(slug_redirect_expires_at): map Vec<u8> => Option<T::BlockNumber>;
 
     pub BlogsFollowedByAccount get(blogs_followed_by_account): map T::AccountId => Vec<T::BlogId>;
+    // Bookmarks that are not in any collection:
+    pub BookmarksByAccount get(bookmarks_by_account): map T::AccountId => Vec<Bookmark<T>>;
+    pub CollectionsByAccount get(collections_by_account): map T::AccountId => Vec<Collection<T>>;
     pub BlogFollowers get(blog_followers): map T::BlogId => Vec<T::AccountId>;
     pub BlogFollowedByAccount get(blog_followed_by_account): map (T::AccountId, T::BlogId) => bool;
 
@@ -285,12 +507,46 @@ decl_storage! {
     pub AccountsFollowedByAccount get(accounts_followed_by_account): map T::AccountId => Vec<T::AccountId>;
     pub AccountFollowers get(account_followers): map T::AccountId => Vec<T::AccountId>;
 
+    // Keyed by (blocker, blocked account):
+    pub AccountBlockedByAccount get(account_blocked_by_account): map (T::AccountId, T::AccountId) => bool;
+    pub AccountsBlockedByAccount get(accounts_blocked_by_account): map T::AccountId => Vec<T::AccountId>;
+
+    pub MessageById get(message_by_id): map T::MessageId => Option<Message<T>>;
+    // Conversations are keyed by an ordered pair of accounts. Deleted messages leave gaps in indexes.
+    pub ConversationMessageId get(conversation_message_id): map ((T::AccountId, T::AccountId), u32) => Option<T::MessageId>;
+    pub ConversationMessagesCount get(conversation_messages_count): map (T::AccountId, T::AccountId) => u32;
+    // Keyed by (reader, the other account). Messages before this index are read:
+    pub ConversationReadUpTo get(conversation_read_up_to): map (T::AccountId, T::AccountId) => u32;
+    pub UnreadMessagesCount get(unread_messages_count): map (T::AccountId, T::AccountId) => u32;
+
+    pub MaxEditHistoryLen get(max_edit_history_len): u32 = DEFAULT_MAX_EDIT_HISTORY_LEN;
+    // Revisions are indexed from the oldest one. Only the last `MaxEditHistoryLen` revisions are kept.
+    pub BlogHistory get(blog_history_record): map (T::BlogId, u32) => Option<BlogHistoryRecord<T>>;
+    pub BlogRevisionsCount get(blog_revisions_count): map T::BlogId => u32;
+    pub PostHistory get(post_history_record): map (T::PostId, u32) => Option<PostHistoryRecord<T>>;
+    pub PostRevisionsCount get(post_revisions_count): map T::PostId => u32;
+    pub CommentHistory get(comment_history_record): map (T::CommentId, u32) => Option<CommentHistoryRecord<T>>;
+    pub CommentRevisionsCount get(comment_revisions_count): map T::CommentId => u32;
+    pub ProfileHistory get(profile_history_record): map (T::AccountId, u32) => Option<ProfileHistoryRecord<T>>;
+    pub ProfileRevisionsCount get(profile_revisions_count): map T::AccountId => u32;
+
+    pub MaxNotificationsPerAccount get(max_notifications_per_account): u32 = DEFAULT_MAX_NOTIFICATIONS_PER_ACCOUNT;
+    // Notifications are indexed from the oldest one. Only the last `MaxNotificationsPerAccount` ones are kept.
+    pub NotificationByAccount get(notification_by_account): map (T::AccountId, u32) => Option<Notification<T>>;
+    pub NotificationsCount get(notifications_count): map T::AccountId => u32;
+    // Notifications before this index are read:
+    pub NotificationsReadUpTo get(notifications_read_up_to): map T::AccountId => u32;
+    pub UnreadNotificationsCount get(unread_notifications_count): map T::AccountId => u32;
+
     pub NextBlogId get(next_blog_id): T::BlogId = T::BlogId::sa(1);
     pub NextPostId get(next_post_id): T::PostId = T::PostId::sa(1);
     pub NextCommentId get(next_comment_id): T::CommentId = T::CommentId::sa(1);
     pub NextReactionId get(next_reaction_id): T::ReactionId = T::ReactionId::sa(1);
+    pub NextMessageId get(next_message_id): T::MessageId = T::MessageId::sa(1);
 
     pub AccountReputationDiffByAccount get(account_reputation_diff_by_account): map (T::AccountId, T::AccountId, ScoringAction) => Option<i16>; // TODO shorten name (?refactor)
+    // The part of a post's score each of its authors got from a scorer, keyed by (scorer, post, action, author):
+    pub PostReputationDiffByAccount get(post_reputation_diff_by_account): map (T::AccountId, T::PostId, ScoringAction, T::AccountId) => Option<i16>;
     pub PostScoreByAccount get(post_score_by_account): map (T::AccountId, T::PostId, ScoringAction) => Option<i16>;
     pub CommentScoreByAccount get(comment_score_by_account): map (T::AccountId, T::CommentId, ScoringAction) => Option<i16>;
 
@@ -301,6 +557,86 @@ decl_storage! {
     pub SharedPostIdsByOriginalCommentId get(shared_post_ids_by_original_comment_id): map T::CommentId => Vec<T::PostId>;
 
     pub AccountByProfileUsername get(account_by_profile_username): map Vec<u8> => Option<T::AccountId>;
+    pub ReservedUsernames get(is_username_reserved): map Vec<u8> => bool;
+    pub UsernameReleaseCooldown get(username_release_cooldown): T::BlockNumber = T::BlockNumber::sa(DEFAULT_USERNAME_RELEASE_COOLDOWN);
+    // A released username can be claimed only by its previous owner until the block:
+    pub ReleasedUsername get(released_username): map Vec<u8> => Option<(T::AccountId, T::BlockNumber)>;
+    pub UsernameDepositBase get(username_deposit_base): BalanceOf<T>;
+    pub UsernameRegistrationPeriod get(username_registration_period): T::BlockNumber = T::BlockNumber::sa(DEFAULT_USERNAME_REGISTRATION_PERIOD);
+    pub UsernameExpiresAt get(username_expires_at): map Vec<u8> => Option<T::BlockNumber>;
+
+    pub MaxMentions get(max_mentions): u32 = DEFAULT_MAX_MENTIONS;
+    pub MaxMentionsPerAccount get(max_mentions_per_account): u32 = DEFAULT_MAX_MENTIONS_PER_ACCOUNT;
+    // Posts and comments (along with their posts) where an account was mentioned, indexed from the oldest one.
+    // Only the last `MaxMentionsPerAccount` ones are kept:
+    pub MentionByAccount get(mention_by_account): map (T::AccountId, u32) => Option<(T::PostId, Option<T::CommentId>)>;
+    pub MentionsCount get(mentions_count): map T::AccountId => u32;
+    // An account is mentioned only once per post or comment while the mention is kept:
+    pub IsMentionedIn get(is_mentioned_in): map (T::AccountId, T::PostId, Option<T::CommentId>) => bool;
+
+    // Votes (or reputation weights, if a poll is weighted) per option:
+    pub PollTallies get(poll_tallies): map T::PostId => Vec<u64>;
+    // Indexes of the options chosen by an account:
+    pub PollVoteByAccount get(poll_vote_by_account): map (T::PostId, T::AccountId) => Option<Vec<u16>>;
+    pub PollsEndingAt get(polls_ending_at): map T::BlockNumber => Vec<T::PostId>;
+
+    pub PostIdsByTag get(post_ids_by_tag): map Vec<u8> => Vec<T::PostId>;
+    // Trending tags are ranked by their usage within the last `TagUsageBucketsCount` buckets of blocks:
+    pub TagUsageBucketLen get(tag_usage_bucket_len): T::BlockNumber = T::BlockNumber::sa(DEFAULT_TAG_USAGE_BUCKET_LEN);
+    pub TagUsageBucketsCount get(tag_usage_buckets_count): u32 = DEFAULT_TAG_USAGE_BUCKETS_COUNT;
+    // How many times a tag was added to posts per bucket (block number divided by bucket length):
+    pub TagUsage get(tag_usage): map Vec<u8> => Vec<(u64, u32)>;
+    // Tags used within the trending window. Others are pruned in chunks from the start of every bucket:
+    pub RecentTag get(recent_tag): map u32 => Option<Vec<u8>>;
+    pub RecentTagsCount get(recent_tags_count): u32;
+    pub RecentTagIndex get(recent_tag_index): map Vec<u8> => Option<u32>;
+    pub TagPruningChunkLen get(tag_pruning_chunk_len): u32 = DEFAULT_TAG_PRUNING_CHUNK_LEN;
+    // Recent tags below this index are yet to be checked by the pruning in progress:
+    pub TagPruningCursor get(tag_pruning_cursor): Option<u32>;
+
+    // At the end of every epoch the pool is paid out to authors by the score they have gained within it:
+    pub RewardPool get(reward_pool): BalanceOf<T>;
+    pub RewardEpochLength get(reward_epoch_length): T::BlockNumber = T::BlockNumber::sa(DEFAULT_REWARD_EPOCH_LENGTH);
+    pub RewardDistributionChunkLen get(reward_distribution_chunk_len): u32 = DEFAULT_REWARD_DISTRIBUTION_CHUNK_LEN;
+    pub RewardEpoch get(reward_epoch): u32;
+    pub RewardEpochStartedAt get(reward_epoch_started_at): T::BlockNumber;
+    pub EpochScoreByAccount get(epoch_score_by_account): map (u32, T::AccountId) => i32;
+    // Authors who have gained or lost score within an epoch, in order:
+    pub EpochScoredAccount get(epoch_scored_account): map (u32, u32) => Option<T::AccountId>;
+    pub EpochScoredAccountsCount get(epoch_scored_accounts_count): map u32 => u32;
+    // Only positive scores are rewarded:
+    pub EpochPositiveScore get(epoch_positive_score): map u32 => u64;
+    pub PendingRewardDistribution get(pending_reward_distribution): Option<RewardDistribution<T>>;
+    // Part of the score a post gains that goes to its earlier upvoters:
+    pub CurationRewardPercent get(curation_reward_percent): u8 = DEFAULT_CURATION_REWARD_PERCENT;
+    // Only this many first upvoters of a post are curated:
+    pub MaxCuratorsPerPost get(max_curators_per_post): u32 = DEFAULT_MAX_CURATORS_PER_POST;
+    // Current curated upvoters of a post in the order they voted, along with their reputation at that time:
+    pub PostUpvoters get(post_upvoters): map T::PostId => Vec<(T::AccountId, u32)>;
+    // Curation a scoring action has paid to each upvoter, keyed by (scorer, post, action):
+    pub PostCurationByAccount get(post_curation_by_account): map (T::AccountId, T::PostId, ScoringAction) => Vec<(T::AccountId, i32)>;
+
+    // Share of the locked voting stake in the weight of scoring actions. The rest is the share of reputation:
+    pub StakeWeightPercent get(stake_weight_percent): u8 = DEFAULT_STAKE_WEIGHT_PERCENT;
+    // Stake counts like reputation, a point per this amount:
+    pub StakePerPoint get(stake_per_point): BalanceOf<T>;
+    pub StakeUnbondingPeriod get(stake_unbonding_period): T::BlockNumber = T::BlockNumber::sa(DEFAULT_STAKE_UNBONDING_PERIOD);
+    pub VotingStake get(voting_stake): map T::AccountId => BalanceOf<T>;
+    // Stake that does not count anymore, but is still locked until the given block:
+    pub UnbondingStake get(unbonding_stake): map T::AccountId => Option<(BalanceOf<T>, T::BlockNumber)>;
+
+    pub IsVerifier get(is_verifier): map T::AccountId => bool;
+    pub AttestationsByAccount get(attestations_by_account): map T::AccountId => Vec<Attestation<T>>;
+
+    // The block the layout of spec version 2 was upgraded at, if this chain has ever run it:
+    pub LegacyLayoutUpgradedAt get(legacy_layout_upgraded_at): Option<T::BlockNumber>;
+    pub PendingLegacyMigration get(pending_legacy_migration): Option<LegacyMigration<T>>;
+    pub LegacyMigrationChunkLen get(legacy_migration_chunk_len): u32 = DEFAULT_LEGACY_MIGRATION_CHUNK_LEN;
+    // Accounts found in the legacy layout, in the order their profiles are migrated:
+    pub LegacyAccount get(legacy_account): map u32 => Option<T::AccountId>;
+    pub LegacyAccountsCount get(legacy_accounts_count): u32;
+    pub IsLegacyAccountFound get(is_legacy_account_found): map T::AccountId => bool;
+    pub IsProfileMigrated get(is_profile_migrated): map T::AccountId => bool;
   }
 }
 
@@ -310,7 +646,8 @@ decl_event! {
     <T as Trait>::BlogId,
     <T as Trait>::PostId,
     <T as Trait>::CommentId,
-    <T as Trait>::ReactionId
+    <T as Trait>::ReactionId,
+    <T as Trait>::MessageId
   {
     BlogCreated(AccountId, BlogId),
     BlogUpdated(AccountId, BlogId),
@@ -326,14 +663,42 @@ decl_event! {
 
     PostCreated(AccountId, PostId),
     PostUpdated(AccountId, PostId),
+    PostPublished(AccountId, PostId),
     PostDeleted(AccountId, PostId),
     PostShared(AccountId, PostId),
 
+    PostPinned(AccountId, BlogId, PostId),
+    PostUnpinned(AccountId, BlogId, PostId),
+    CommentPinned(AccountId, PostId, CommentId),
+    CommentUnpinned(AccountId, PostId, CommentId),
+
+    PostCrossPosted(AccountId, PostId, BlogId),
+    CrossPostRemoved(AccountId, PostId, BlogId),
+    CoAuthorInvited(AccountId, PostId, AccountId),
+    CoAuthorAccepted(AccountId, PostId),
+    CoAuthorInviteRemoved(AccountId, PostId, AccountId),
+    PostTipped(AccountId, PostId),
+
+    RewardPoolFunded(AccountId),
+    RewardEpochEnded(u32),
+    CreatorRewarded(AccountId, u32),
+    RewardsDistributed(u32),
+
+    VotingStakeLocked(AccountId),
+    VotingStakeUnbonded(AccountId),
+    UnbondedStakeWithdrawn(AccountId),
+
+    PollVoted(AccountId, PostId),
+    PollClosed(PostId),
+
     CommentCreated(AccountId, CommentId),
     CommentUpdated(AccountId, CommentId),
     CommentDeleted(AccountId, CommentId),
     CommentShared(AccountId, CommentId),
 
+    // Author, mentioned account, post and the comment if mentioned in a comment:
+    AccountMentioned(AccountId, AccountId, PostId, Option<CommentId>),
+
     PostReactionCreated(AccountId, PostId, ReactionId),
     PostReactionUpdated(AccountId, PostId, ReactionId),
     PostReactionDeleted(AccountId, PostId, ReactionId),
@@ -344,6 +709,33 @@ decl_event! {
 
     ProfileCreated(AccountId),
     ProfileUpdated(AccountId),
+    UsernameRenewed(AccountId),
+    UsernameReclaimed(AccountId, AccountId),
+
+    NotificationsRead(AccountId, u32),
+
+    AccountBlocked(AccountId, AccountId),
+    AccountUnblocked(AccountId, AccountId),
+
+    PostBookmarked(AccountId, PostId),
+    PostUnbookmarked(AccountId, PostId),
+    CommentBookmarked(AccountId, CommentId),
+    CommentUnbookmarked(AccountId, CommentId),
+
+    CollectionCreated(AccountId, Vec<u8>),
+    // Old and new names:
+    CollectionRenamed(AccountId, Vec<u8>, Vec<u8>),
+    CollectionUpdated(AccountId, Vec<u8>),
+    CollectionDeleted(AccountId, Vec<u8>),
+
+    MessageSent(AccountId, AccountId, MessageId),
+    MessageDeleted(AccountId, MessageId),
+    ConversationRead(AccountId, AccountId),
+
+    VerifierAdded(AccountId),
+    VerifierRemoved(AccountId),
+    AccountAttested(AccountId, AccountId),
+    AttestationRevoked(AccountId, AccountId),
   }
 }
 
@@ -353,69 +745,79 @@ decl_module! {
     pub fn deposit_event<T>() = default;
 
     fn on_initialize(_now: T::BlockNumber) {
-      // Stub
+      Self::migrate_legacy_chunk();
     }
 
-    fn on_finalize(_now: T::BlockNumber) {
-      // Stub
+    fn on_finalize(now: T::BlockNumber) {
+      for post_id in <PollsEndingAt<T>>::take(now) {
+        Self::deposit_event(RawEvent::PollClosed(post_id));
+      }
+
+      if (now % Self::tag_usage_bucket_len()).is_zero() {
+        <TagPruningCursor<T>>::put(Self::recent_tags_count());
+      }
+      Self::prune_recent_tags_chunk();
+
+      // A new epoch ends only once the previous one is paid out:
+      if Self::pending_reward_distribution().is_some() {
+        Self::distribute_rewards_chunk();
+      } else if now >= Self::reward_epoch_started_at() + Self::reward_epoch_length() {
+        Self::end_reward_epoch(now);
+      }
     }
 
     // TODO use BlogUpdate to pass data
-    pub fn create_blog(origin, slug: Vec<u8>, ipfs_hash: Vec<u8>) {
+    pub fn create_blog(origin, slug: Vec<u8>, content: Content) {
       let owner = ensure_signed(origin)?;
 
-      ensure!(slug.len() >= Self::slug_min_len() as usize, MSG_BLOG_SLUG_IS_TOO_SHORT);
-      ensure!(slug.len() <= Self::slug_max_len() as usize, MSG_BLOG_SLUG_IS_TOO_LONG);
-      ensure!(!<BlogIdBySlug<T>>::exists(slug.clone()), MSG_BLOG_SLUG_IS_NOT_UNIQUE);
-      Self::is_ipfs_hash_valid(ipfs_hash.clone())?;
-
       let blog_id = Self::next_blog_id();
-      let ref mut new_blog: Blog<T> = Blog {
+      let slug = Self::normalize_slug(slug, blog_id)?;
+      Self::is_content_valid(&content, Some(Self::blog_max_len()))?;
+      let payload_len = slug.len() + content.size();
+
+      let ref new_blog: Blog<T> = Blog {
         id: blog_id,
         created: Self::new_change(owner.clone()),
         updated: None,
         writers: vec![],
         slug: slug.clone(),
-        ipfs_hash,
-        posts_count: 0,
-        followers_count: 0,
-        edit_history: vec![],
-        score: 0
+        content,
       };
 
       // Blog creator automatically follows their blog:
-      Self::add_blog_follower_and_insert_blog(owner.clone(), new_blog, true)?;
+      Self::add_blog_follower_and_insert_blog(owner.clone(), new_blog, true, PaidAction::CreateBlog, payload_len)?;
 
       <BlogIdsByOwner<T>>::mutate(owner.clone(), |ids| ids.push(blog_id));
-      <BlogIdBySlug<T>>::insert(slug, blog_id);
+      Self::claim_slug(slug, blog_id);
       <NextBlogId<T>>::mutate(|n| { *n += T::BlogId::sa(1); });
     }
 
     pub fn follow_blog(origin, blog_id: T::BlogId) {
       let follower = ensure_signed(origin)?;
 
-      let ref mut blog = Self::blog_by_id(blog_id).ok_or(MSG_BLOG_NOT_FOUND)?;
+      let ref blog = Self::blog_by_id(blog_id).ok_or(MSG_BLOG_NOT_FOUND)?;
       ensure!(!Self::blog_followed_by_account((follower.clone(), blog_id)), MSG_ACCOUNT_IS_FOLLOWING_BLOG);
 
-      Self::add_blog_follower_and_insert_blog(follower.clone(), blog, false)?;
+      Self::add_blog_follower_and_insert_blog(follower.clone(), blog, false, PaidAction::FollowBlog, 0)?;
     }
 
     pub fn unfollow_blog(origin, blog_id: T::BlogId) {
       let follower = ensure_signed(origin)?;
 
-      let ref mut blog = Self::blog_by_id(blog_id).ok_or(MSG_BLOG_NOT_FOUND)?;
+      let blog = Self::blog_by_id(blog_id).ok_or(MSG_BLOG_NOT_FOUND)?;
       ensure!(Self::blog_followed_by_account((follower.clone(), blog_id)), MSG_ACCOUNT_IS_NOT_FOLLOWING_BLOG);
 
       let mut social_account = Self::social_account_by_id(follower.clone()).ok_or(MSG_SOCIAL_ACCOUNT_NOT_FOUND)?;
       social_account.following_blogs_count = social_account.following_blogs_count
         .checked_sub(1)
         .ok_or(MSG_UNDERFLOW_UNFOLLOWING_BLOG)?;
-      blog.followers_count = blog.followers_count.checked_sub(1).ok_or(MSG_UNDERFLOW_UNFOLLOWING_BLOG)?;
+      let mut blog_counters = Self::blog_counters(blog_id);
+      blog_counters.followers_count = blog_counters.followers_count.checked_sub(1).ok_or(MSG_UNDERFLOW_UNFOLLOWING_BLOG)?;
 
       if blog.created.account != follower {
         let author = blog.created.account.clone();
         if let Some(score_diff) = Self::account_reputation_diff_by_account((follower.clone(), author.clone(), ScoringAction::FollowBlog)) {
-          blog.score = blog.score.checked_sub(score_diff as i32).ok_or(MSG_OUT_OF_BOUNDS_UPDATING_BLOG_SCORE)?;
+          blog_counters.score = blog_counters.score.checked_sub(score_diff as i32).ok_or(MSG_OUT_OF_BOUNDS_UPDATING_BLOG_SCORE)?;
           Self::change_social_account_reputation(author.clone(), follower.clone(), score_diff * -1, ScoringAction::FollowBlog)?;
         }
       }
@@ -424,7 +826,7 @@ decl_module! {
       <BlogFollowers<T>>::mutate(blog_id, |account_ids| Self::vec_remove_on(account_ids, follower.clone()));
       <BlogFollowedByAccount<T>>::remove((follower.clone(), blog_id));
       <SocialAccountById<T>>::insert(follower.clone(), social_account);
-      <BlogById<T>>::insert(blog_id, blog);
+      <BlogCountersById<T>>::insert(blog_id, blog_counters);
 
       Self::deposit_event(RawEvent::BlogUnfollowed(follower.clone(), blog_id));
     }
@@ -443,17 +845,20 @@ decl_module! {
       followed_account.followers_count = followed_account.followers_count
         .checked_add(1).ok_or(MSG_OVERFLOW_FOLLOWING_ACCOUNT)?;
 
-      Self::change_social_account_reputation(account.clone(), follower.clone(),
-        Self::get_score_diff(follower_account.reputation.clone(), ScoringAction::FollowAccount),
-        ScoringAction::FollowAccount
-      )?;
+      let score_diff = Self::get_account_score_diff(&follower, follower_account.reputation, ScoringAction::FollowAccount);
+      let (reputation, score_diff) = Self::reputation_after(followed_account.reputation, score_diff)?;
+      // Storage changes of a failed call are not reverted, so the fee is charged after all checks:
+      Self::charge_fee(&follower, PaidAction::FollowAccount, 0)?;
 
       <SocialAccountById<T>>::insert(follower.clone(), follower_account);
       <SocialAccountById<T>>::insert(account.clone(), followed_account);
+      // Written after the followed account, so that its new reputation is not overwritten:
+      Self::record_reputation_change(account.clone(), follower.clone(), reputation, score_diff, ScoringAction::FollowAccount);
       <AccountsFollowedByAccount<T>>::mutate(follower.clone(), |ids| ids.push(account.clone()));
       <AccountFollowers<T>>::mutate(account.clone(), |ids| ids.push(follower.clone()));
       <AccountFollowedByAccount<T>>::insert((follower.clone(), account.clone()), true);
 
+      Self::notify(account.clone(), follower.clone(), NotificationKind::AccountFollowed);
       Self::deposit_event(RawEvent::AccountFollowed(follower, account));
     }
 
@@ -475,13 +880,11 @@ decl_module! {
       let reputation_diff = Self::account_reputation_diff_by_account(
         (follower.clone(), account.clone(), ScoringAction::FollowAccount)
       ).ok_or(MSG_REPUTATION_DIFF_NOT_FOUND)?;
-      Self::change_social_account_reputation(account.clone(), follower.clone(),
-        reputation_diff,
-        ScoringAction::FollowAccount
-      )?;
+      let (reputation, reputation_diff) = Self::reputation_after(followed_account.reputation, reputation_diff * -1)?;
 
       <SocialAccountById<T>>::insert(follower.clone(), follower_account);
       <SocialAccountById<T>>::insert(account.clone(), followed_account);
+      Self::record_reputation_change(account.clone(), follower.clone(), reputation, reputation_diff, ScoringAction::FollowAccount);
       <AccountsFollowedByAccount<T>>::mutate(follower.clone(), |account_ids| Self::vec_remove_on(account_ids, account.clone()));
       <AccountFollowers<T>>::mutate(account.clone(), |account_ids| Self::vec_remove_on(account_ids, follower.clone()));
       <AccountFollowedByAccount<T>>::remove((follower.clone(), account.clone()));
@@ -489,28 +892,268 @@ decl_module! {
       Self::deposit_event(RawEvent::AccountUnfollowed(follower, account));
     }
 
+    /// Blocked accounts can not message, mention or notify the blocker.
+    pub fn block_account(origin, account: T::AccountId) {
+      let blocker = ensure_signed(origin)?;
+
+      ensure!(blocker != account, MSG_ACCOUNT_CANNOT_BLOCK_ITSELF);
+      ensure!(!Self::account_blocked_by_account((blocker.clone(), account.clone())), MSG_ACCOUNT_IS_ALREADY_BLOCKED);
+
+      <AccountBlockedByAccount<T>>::insert((blocker.clone(), account.clone()), true);
+      <AccountsBlockedByAccount<T>>::mutate(blocker.clone(), |ids| ids.push(account.clone()));
+
+      Self::deposit_event(RawEvent::AccountBlocked(blocker, account));
+    }
+
+    pub fn unblock_account(origin, account: T::AccountId) {
+      let blocker = ensure_signed(origin)?;
+
+      ensure!(Self::account_blocked_by_account((blocker.clone(), account.clone())), MSG_ACCOUNT_IS_NOT_BLOCKED);
+
+      <AccountBlockedByAccount<T>>::remove((blocker.clone(), account.clone()));
+      <AccountsBlockedByAccount<T>>::mutate(blocker.clone(), |ids| Self::vec_remove_on(ids, account.clone()));
+
+      Self::deposit_event(RawEvent::AccountUnblocked(blocker, account));
+    }
+
+    pub fn bookmark(origin, item: Bookmark<T>) {
+      let owner = ensure_signed(origin)?;
+
+      Self::ensure_bookmark_exists(&owner, &item)?;
+      let mut bookmarks = Self::bookmarks_by_account(owner.clone());
+      ensure!(!bookmarks.contains(&item), MSG_ITEM_IS_ALREADY_BOOKMARKED);
+      ensure!(bookmarks.len() < Self::max_bookmarks_per_account() as usize, MSG_TOO_MANY_BOOKMARKS);
+
+      bookmarks.push(item.clone());
+      <BookmarksByAccount<T>>::insert(owner.clone(), bookmarks);
+
+      Self::deposit_event(match item {
+        Bookmark::Post(post_id) => RawEvent::PostBookmarked(owner, post_id),
+        Bookmark::Comment(comment_id) => RawEvent::CommentBookmarked(owner, comment_id),
+      });
+    }
+
+    pub fn unbookmark(origin, item: Bookmark<T>) {
+      let owner = ensure_signed(origin)?;
+
+      let mut bookmarks = Self::bookmarks_by_account(owner.clone());
+      ensure!(bookmarks.contains(&item), MSG_ITEM_IS_NOT_BOOKMARKED);
+
+      bookmarks.retain(|bookmark| *bookmark != item);
+      <BookmarksByAccount<T>>::insert(owner.clone(), bookmarks);
+
+      Self::deposit_event(match item {
+        Bookmark::Post(post_id) => RawEvent::PostUnbookmarked(owner, post_id),
+        Bookmark::Comment(comment_id) => RawEvent::CommentUnbookmarked(owner, comment_id),
+      });
+    }
+
+    pub fn create_collection(origin, name: Vec<u8>) {
+      let owner = ensure_signed(origin)?;
+
+      let mut collections = Self::collections_by_account(owner.clone());
+      Self::ensure_collection_name_is_valid(&collections, &name)?;
+      ensure!(collections.len() < Self::max_collections_per_account() as usize, MSG_TOO_MANY_COLLECTIONS);
+
+      collections.push(Collection {
+        created: Self::new_change(owner.clone()),
+        updated: None,
+        name: name.clone(),
+        items: vec![],
+      });
+      <CollectionsByAccount<T>>::insert(owner.clone(), collections);
+
+      Self::deposit_event(RawEvent::CollectionCreated(owner, name));
+    }
+
+    pub fn rename_collection(origin, name: Vec<u8>, new_name: Vec<u8>) {
+      let owner = ensure_signed(origin)?;
+
+      let mut collections = Self::collections_by_account(owner.clone());
+      let index = Self::collection_index(&collections, &name)?;
+      Self::ensure_collection_name_is_valid(&collections, &new_name)?;
+
+      collections[index].name = new_name.clone();
+      collections[index].updated = Some(Self::new_change(owner.clone()));
+      <CollectionsByAccount<T>>::insert(owner.clone(), collections);
+
+      Self::deposit_event(RawEvent::CollectionRenamed(owner, name, new_name));
+    }
+
+    pub fn delete_collection(origin, name: Vec<u8>) {
+      let owner = ensure_signed(origin)?;
+
+      let mut collections = Self::collections_by_account(owner.clone());
+      let index = Self::collection_index(&collections, &name)?;
+
+      collections.remove(index);
+      <CollectionsByAccount<T>>::insert(owner.clone(), collections);
+
+      Self::deposit_event(RawEvent::CollectionDeleted(owner, name));
+    }
+
+    pub fn add_to_collection(origin, name: Vec<u8>, item: Bookmark<T>) {
+      let owner = ensure_signed(origin)?;
+
+      Self::ensure_bookmark_exists(&owner, &item)?;
+      let mut collections = Self::collections_by_account(owner.clone());
+      let index = Self::collection_index(&collections, &name)?;
+      {
+        let collection = &mut collections[index];
+        ensure!(!collection.items.contains(&item), MSG_ITEM_IS_ALREADY_IN_COLLECTION);
+        ensure!(collection.items.len() < Self::max_items_per_collection() as usize, MSG_TOO_MANY_ITEMS_IN_COLLECTION);
+
+        collection.items.push(item);
+        collection.updated = Some(Self::new_change(owner.clone()));
+      }
+      <CollectionsByAccount<T>>::insert(owner.clone(), collections);
+
+      Self::deposit_event(RawEvent::CollectionUpdated(owner, name));
+    }
+
+    pub fn remove_from_collection(origin, name: Vec<u8>, item: Bookmark<T>) {
+      let owner = ensure_signed(origin)?;
+
+      let mut collections = Self::collections_by_account(owner.clone());
+      let index = Self::collection_index(&collections, &name)?;
+      {
+        let collection = &mut collections[index];
+        ensure!(collection.items.contains(&item), MSG_ITEM_IS_NOT_IN_COLLECTION);
+
+        collection.items.retain(|bookmark| *bookmark != item);
+        collection.updated = Some(Self::new_change(owner.clone()));
+      }
+      <CollectionsByAccount<T>>::insert(owner.clone(), collections);
+
+      Self::deposit_event(RawEvent::CollectionUpdated(owner, name));
+    }
+
+    pub fn send_message(origin, to: T::AccountId, encrypted_content_hash: Content) {
+      let sender = ensure_signed(origin)?;
+
+      ensure!(sender != to, MSG_ACCOUNT_CANNOT_MESSAGE_ITSELF);
+      ensure!(!Self::account_blocked_by_account((to.clone(), sender.clone())), MSG_SENDER_IS_BLOCKED_BY_RECIPIENT);
+      // Raw content is not allowed, as it would be stored unencrypted:
+      Self::is_content_valid(&encrypted_content_hash, None)?;
+      Self::charge_fee(&sender, PaidAction::SendMessage, encrypted_content_hash.size())?;
+
+      let conversation = Self::conversation_key(sender.clone(), to.clone());
+      let index = Self::conversation_messages_count(conversation.clone());
+      let message_id = Self::next_message_id();
+      let message = Message {
+        id: message_id,
+        created: Self::new_change(sender.clone()),
+        recipient: to.clone(),
+        index,
+        content: encrypted_content_hash,
+      };
+
+      <MessageById<T>>::insert(message_id, message);
+      <ConversationMessageId<T>>::insert((conversation.clone(), index), message_id);
+      <ConversationMessagesCount<T>>::insert(conversation, index + 1);
+      <UnreadMessagesCount<T>>::mutate((to.clone(), sender.clone()), |count| *count = count.saturating_add(1));
+      <NextMessageId<T>>::mutate(|n| { *n += T::MessageId::sa(1); });
+
+      Self::deposit_event(RawEvent::MessageSent(sender, to, message_id));
+    }
+
+    /// Messages are deleted for both participants of the conversation.
+    pub fn delete_message(origin, message_id: T::MessageId) {
+      let sender = ensure_signed(origin)?;
+
+      let message = Self::message_by_id(message_id).ok_or(MSG_MESSAGE_NOT_FOUND)?;
+      ensure!(sender == message.created.account, MSG_ONLY_MESSAGE_SENDER_CAN_DELETE_MESSAGE);
+
+      let recipient = message.recipient;
+      if message.index >= Self::conversation_read_up_to((recipient.clone(), sender.clone())) {
+        <UnreadMessagesCount<T>>::mutate((recipient.clone(), sender.clone()), |count| *count = count.saturating_sub(1));
+      }
+      <ConversationMessageId<T>>::remove((Self::conversation_key(sender.clone(), recipient), message.index));
+      <MessageById<T>>::remove(message_id);
+
+      Self::deposit_event(RawEvent::MessageDeleted(sender, message_id));
+    }
+
+    /// Marks all messages received from the given account as read.
+    pub fn mark_conversation_read(origin, with: T::AccountId) {
+      let reader = ensure_signed(origin)?;
+
+      ensure!(Self::unread_messages_count((reader.clone(), with.clone())) > 0, MSG_NO_UNREAD_MESSAGES_IN_CONVERSATION);
+
+      let count = Self::conversation_messages_count(Self::conversation_key(reader.clone(), with.clone()));
+      <ConversationReadUpTo<T>>::insert((reader.clone(), with.clone()), count);
+      <UnreadMessagesCount<T>>::remove((reader.clone(), with.clone()));
+
+      Self::deposit_event(RawEvent::ConversationRead(reader, with));
+    }
+
     // TODO use PostUpdate to pass data?
-    pub fn create_post(origin, blog_id: T::BlogId, ipfs_hash: Vec<u8>, extension: PostExtension<T>) {
+    pub fn create_post(
+      origin,
+      blog_id: T::BlogId,
+      content: Content,
+      extension: PostExtension<T>,
+      tags: Vec<Vec<u8>>,
+      mentions: Vec<Mention<T>>,
+      visibility: PostVisibility
+    ) {
       let owner = ensure_signed(origin)?;
 
-      let mut blog = Self::blog_by_id(blog_id).ok_or(MSG_BLOG_NOT_FOUND)?;
-      blog.posts_count = blog.posts_count.checked_add(1).ok_or(MSG_OVERFLOW_ADDING_POST_ON_BLOG)?;
+      Self::ensure_blog_exists(blog_id)?;
+      let is_public = visibility == PostVisibility::Public;
+      let mut blog_counters = Self::blog_counters(blog_id);
+      if is_public {
+        blog_counters.posts_count = blog_counters.posts_count.checked_add(1).ok_or(MSG_OVERFLOW_ADDING_POST_ON_BLOG)?;
+      }
+      let tags = Self::normalize_tags(tags)?;
+      ensure!(mentions.is_empty() || visibility != PostVisibility::Draft, MSG_CANNOT_MENTION_IN_DRAFT);
+      let mentioned_accounts = Self::resolve_mentions(&owner, mentions)?;
 
       let new_post_id = Self::next_post_id();
-
-      // Sharing functions contain check for post/comment existance
-      match extension {
+      let poll_options_size = match extension {
         PostExtension::RegularPost => {
-          Self::is_ipfs_hash_valid(ipfs_hash.clone())?;
+          Self::is_content_valid(&content, Some(Self::post_max_len()))?;
+          0
         },
         PostExtension::SharedPost(post_id) => {
+          ensure!(is_public, MSG_SHARE_SHOULD_BE_PUBLIC);
           let post = Self::post_by_id(post_id).ok_or(MSG_ORIGINAL_POST_NOT_FOUND)?;
-          ensure!(post.extension == PostExtension::RegularPost, MSG_CANNOT_SHARE_SHARED_POST);
-          Self::share_post(owner.clone(), post_id, new_post_id)?;
+          Self::ensure_post_is_public(&post)?;
+          match post.extension {
+            // Shares can be quoted, but not reposted again:
+            PostExtension::SharedPost(_) | PostExtension::SharedComment(_) => {
+              ensure!(!content.is_none(), MSG_CANNOT_SHARE_SHARED_POST);
+            },
+            _ => {},
+          }
+          ensure!(Self::attribution_chain(post_id).len() < Self::max_quote_depth() as usize, MSG_QUOTE_CHAIN_IS_TOO_LONG);
+          Self::ensure_quote_is_valid(&content)?;
+          Self::ensure_post_can_be_shared(&owner, post_id)?;
+          0
         },
         PostExtension::SharedComment(comment_id) => {
-          Self::share_comment(owner.clone(), comment_id, new_post_id)?;
+          ensure!(is_public, MSG_SHARE_SHOULD_BE_PUBLIC);
+          Self::ensure_comment_can_be_shared(&owner, comment_id)?;
+          Self::ensure_quote_is_valid(&content)?;
+          0
         },
+        PostExtension::Poll(ref poll) => {
+          Self::is_content_valid(&content, Some(Self::post_max_len()))?;
+          Self::ensure_poll_is_valid(poll)?;
+          poll.options.iter().map(|option| option.len()).sum()
+        },
+      };
+      // Storage changes of a failed call are not reverted, so the fee is charged after all checks:
+      Self::charge_fee(&owner, PaidAction::CreatePost, content.size() + Self::tags_size(&tags) + poll_options_size)?;
+
+      match extension {
+        PostExtension::SharedPost(post_id) => Self::share_post(owner.clone(), post_id, new_post_id)?,
+        PostExtension::SharedComment(comment_id) => Self::share_comment(owner.clone(), comment_id, new_post_id)?,
+        PostExtension::Poll(ref poll) => {
+          <PollTallies<T>>::insert(new_post_id, vec![0; poll.options.len()]);
+          <PollsEndingAt<T>>::mutate(poll.ends_at, |ids| ids.push(new_post_id));
+        },
+        PostExtension::RegularPost => {},
       }
 
       let new_post: Post<T> = Post {
@@ -519,29 +1162,324 @@ decl_module! {
         created: Self::new_change(owner.clone()),
         updated: None,
         extension,
-        ipfs_hash,
-        comments_count: 0,
-        upvotes_count: 0,
-        downvotes_count: 0,
-        shares_count: 0,
-        edit_history: vec![],
-        score: 0,
+        content,
+        tags: tags.clone(),
+        visibility,
       };
 
       <PostById<T>>::insert(new_post_id, new_post);
-      <PostIdsByBlogId<T>>::mutate(blog_id, |ids| ids.push(new_post_id));
       <NextPostId<T>>::mutate(|n| { *n += T::PostId::sa(1); });
-      <BlogById<T>>::insert(blog_id, blog);
+      // Drafts and unlisted posts get listed once they are published:
+      if is_public {
+        Self::add_post_tags(new_post_id, &tags);
+        <PostIdsByBlogId<T>>::mutate(blog_id, |ids| ids.push(new_post_id));
+        <BlogCountersById<T>>::insert(blog_id, blog_counters);
+      }
 
       Self::deposit_event(RawEvent::PostCreated(owner.clone(), new_post_id));
+      Self::add_mentions(owner, mentioned_accounts, new_post_id, None);
+    }
+
+    /// Lists the post in another blog as well. The author should own or write to that blog.
+    pub fn cross_post(origin, post_id: T::PostId, blog_id: T::BlogId) {
+      let owner = ensure_signed(origin)?;
+
+      let post = Self::post_by_id(post_id).ok_or(MSG_POST_NOT_FOUND)?;
+      ensure!(owner == post.created.account, MSG_ONLY_POST_OWNER_CAN_CROSS_POST);
+      Self::ensure_post_is_public(&post)?;
+
+      let blog = Self::blog_by_id(blog_id).ok_or(MSG_BLOG_NOT_FOUND)?;
+      ensure!(owner == blog.created.account || blog.writers.contains(&owner), MSG_NO_PERMISSION_TO_CROSS_POST);
+
+      let mut cross_post_blog_ids = Self::cross_post_blog_ids(post_id);
+      ensure!(blog_id != post.blog_id && !cross_post_blog_ids.contains(&blog_id), MSG_POST_IS_ALREADY_IN_BLOG);
+      ensure!(cross_post_blog_ids.len() < Self::max_cross_posts_per_post() as usize, MSG_TOO_MANY_CROSS_POSTS);
+
+      let mut blog_counters = Self::blog_counters(blog_id);
+      blog_counters.posts_count = blog_counters.posts_count.checked_add(1).ok_or(MSG_OVERFLOW_ADDING_POST_ON_BLOG)?;
+
+      cross_post_blog_ids.push(blog_id);
+      <CrossPostBlogIds<T>>::insert(post_id, cross_post_blog_ids);
+      <PostIdsByBlogId<T>>::mutate(blog_id, |ids| ids.push(post_id));
+      <BlogCountersById<T>>::insert(blog_id, blog_counters);
+
+      Self::deposit_event(RawEvent::PostCrossPosted(owner, post_id, blog_id));
+    }
+
+    /// Either the post author or the blog owner can remove a cross-post.
+    /// The blog loses the part of the post's score that it has got.
+    pub fn remove_cross_post(origin, post_id: T::PostId, blog_id: T::BlogId) {
+      let remover = ensure_signed(origin)?;
+
+      let post = Self::post_by_id(post_id).ok_or(MSG_POST_NOT_FOUND)?;
+      let blog = Self::blog_by_id(blog_id).ok_or(MSG_BLOG_NOT_FOUND)?;
+      ensure!(remover == post.created.account || remover == blog.created.account, MSG_NO_PERMISSION_TO_REMOVE_CROSS_POST);
+
+      let mut cross_post_blog_ids = Self::cross_post_blog_ids(post_id);
+      ensure!(cross_post_blog_ids.contains(&blog_id), MSG_POST_IS_NOT_CROSS_POSTED_TO_BLOG);
+
+      let mut blog_counters = Self::blog_counters(blog_id);
+      blog_counters.posts_count = blog_counters.posts_count.checked_sub(1).ok_or(MSG_UNDERFLOW_REMOVING_POST_FROM_BLOG)?;
+      blog_counters.score = blog_counters.score.checked_sub(Self::post_score_in_blog((blog_id, post_id)))
+        .ok_or(MSG_OUT_OF_BOUNDS_REVERTING_BLOG_SCORE)?;
+
+      Self::vec_remove_on(&mut cross_post_blog_ids, blog_id);
+      <CrossPostBlogIds<T>>::insert(post_id, cross_post_blog_ids);
+      <PostIdsByBlogId<T>>::mutate(blog_id, |ids| Self::vec_remove_on(ids, post_id));
+      <PostScoreInBlog<T>>::remove((blog_id, post_id));
+      <PinnedPostIds<T>>::mutate(blog_id, |ids| ids.retain(|id| *id != post_id));
+      <BlogCountersById<T>>::insert(blog_id, blog_counters);
+
+      Self::deposit_event(RawEvent::CrossPostRemoved(remover, post_id, blog_id));
+    }
+
+    /// Invites a co-author who gets `share_percent` of the post's reputation and tips once accepted.
+    /// The creator should keep a share, so the shares of all co-authors stay below 100 percent.
+    pub fn invite_co_author(origin, post_id: T::PostId, co_author: T::AccountId, share_percent: u8) {
+      let owner = ensure_signed(origin)?;
+
+      let post = Self::post_by_id(post_id).ok_or(MSG_POST_NOT_FOUND)?;
+      ensure!(owner == post.created.account, MSG_ONLY_POST_OWNER_CAN_INVITE_CO_AUTHOR);
+      ensure!(co_author != owner, MSG_POST_OWNER_CANNOT_BE_CO_AUTHOR);
+      ensure!(share_percent > 0, MSG_CO_AUTHOR_SHARE_IS_ZERO);
+      ensure!(!Self::account_blocked_by_account((co_author.clone(), owner.clone())), MSG_POST_OWNER_IS_BLOCKED_BY_CO_AUTHOR);
+
+      let mut invites = Self::co_author_invites(post_id);
+      let co_authors = Self::co_authors(post_id);
+      ensure!(invites.iter().chain(co_authors.iter()).all(|(account, _)| *account != co_author), MSG_ACCOUNT_IS_ALREADY_CO_AUTHOR);
+      ensure!(invites.len() + co_authors.len() < Self::max_co_authors_per_post() as usize, MSG_TOO_MANY_CO_AUTHORS);
+      let shares: u32 = invites.iter().chain(co_authors.iter()).map(|(_, share)| *share as u32).sum();
+      ensure!(shares + (share_percent as u32) < 100, MSG_CO_AUTHOR_SHARES_ARE_TOO_BIG);
+
+      invites.push((co_author.clone(), share_percent));
+      <CoAuthorInvites<T>>::insert(post_id, invites);
+
+      Self::notify(co_author.clone(), owner.clone(), NotificationKind::CoAuthorInvited(post_id));
+      Self::deposit_event(RawEvent::CoAuthorInvited(owner, post_id, co_author));
+    }
+
+    /// Co-authorship can not be revoked once accepted, as the earned reputation is already split.
+    pub fn accept_co_authorship(origin, post_id: T::PostId) {
+      let co_author = ensure_signed(origin)?;
+
+      let mut invites = Self::co_author_invites(post_id);
+      let index = invites.iter().position(|(account, _)| *account == co_author).ok_or(MSG_CO_AUTHOR_INVITE_NOT_FOUND)?;
+
+      let invite = invites.remove(index);
+      <CoAuthorInvites<T>>::insert(post_id, invites);
+      <CoAuthors<T>>::mutate(post_id, |co_authors| co_authors.push(invite));
+
+      Self::deposit_event(RawEvent::CoAuthorAccepted(co_author, post_id));
+    }
+
+    /// Either the post creator cancels an invite or the invited account declines it.
+    pub fn remove_co_author_invite(origin, post_id: T::PostId, co_author: T::AccountId) {
+      let remover = ensure_signed(origin)?;
+
+      let post = Self::post_by_id(post_id).ok_or(MSG_POST_NOT_FOUND)?;
+      ensure!(remover == post.created.account || remover == co_author, MSG_NO_PERMISSION_TO_REMOVE_CO_AUTHOR_INVITE);
+
+      let mut invites = Self::co_author_invites(post_id);
+      let index = invites.iter().position(|(account, _)| *account == co_author).ok_or(MSG_CO_AUTHOR_INVITE_NOT_FOUND)?;
+
+      invites.remove(index);
+      <CoAuthorInvites<T>>::insert(post_id, invites);
+
+      Self::deposit_event(RawEvent::CoAuthorInviteRemoved(remover, post_id, co_author));
+    }
+
+    /// Locks more balance to weigh the scoring actions of the account.
+    pub fn lock_voting_stake(origin, amount: BalanceOf<T>) {
+      let staker = ensure_signed(origin)?;
+      ensure!(!amount.is_zero(), MSG_VOTING_STAKE_IS_ZERO);
+
+      let stake = Self::voting_stake(staker.clone()).checked_add(&amount).ok_or(MSG_OUT_OF_BOUNDS_LOCKING_VOTING_STAKE)?;
+      let locked = stake.checked_add(&Self::unbonding_amount(&staker)).ok_or(MSG_OUT_OF_BOUNDS_LOCKING_VOTING_STAKE)?;
+      ensure!(T::Currency::free_balance(&staker) >= locked, MSG_NOT_ENOUGH_BALANCE_FOR_VOTING_STAKE);
+
+      <VotingStake<T>>::insert(staker.clone(), stake);
+      Self::update_voting_stake_lock(&staker)?;
+
+      Self::deposit_event(RawEvent::VotingStakeLocked(staker));
+    }
+
+    /// The unbonded amount stops counting at once, but stays locked for the unbonding period.
+    /// There is one unbonding amount per account, so unbonding more restarts the period for all of it,
+    /// including the part unbonded earlier.
+    pub fn unbond_voting_stake(origin, amount: BalanceOf<T>) {
+      let staker = ensure_signed(origin)?;
+      ensure!(!amount.is_zero(), MSG_VOTING_STAKE_IS_ZERO);
+
+      let stake = Self::voting_stake(staker.clone());
+      ensure!(amount <= stake, MSG_NOT_ENOUGH_VOTING_STAKE_TO_UNBOND);
+      let unbonding = Self::unbonding_amount(&staker).checked_add(&amount).ok_or(MSG_OUT_OF_BOUNDS_UNBONDING_VOTING_STAKE)?;
+
+      let unlocks_at = <system::Module<T>>::block_number() + Self::stake_unbonding_period();
+      <VotingStake<T>>::insert(staker.clone(), stake - amount);
+      <UnbondingStake<T>>::insert(staker.clone(), (unbonding, unlocks_at));
+
+      Self::deposit_event(RawEvent::VotingStakeUnbonded(staker));
+    }
+
+    pub fn withdraw_unbonded_stake(origin) {
+      let staker = ensure_signed(origin)?;
+
+      let (_, unlocks_at) = Self::unbonding_stake(staker.clone()).ok_or(MSG_NO_UNBONDING_STAKE)?;
+      ensure!(<system::Module<T>>::block_number() >= unlocks_at, MSG_STAKE_IS_STILL_UNBONDING);
+
+      <UnbondingStake<T>>::remove(staker.clone());
+      Self::update_voting_stake_lock(&staker)?;
+
+      Self::deposit_event(RawEvent::UnbondedStakeWithdrawn(staker));
+    }
+
+    pub fn deposit_to_reward_pool(origin, amount: BalanceOf<T>) {
+      let depositor = ensure_signed(origin)?;
+      ensure!(!amount.is_zero(), MSG_REWARD_DEPOSIT_IS_ZERO);
+
+      Self::withdraw_transfer(&depositor, amount)?;
+      Self::add_to_reward_pool(amount);
+
+      Self::deposit_event(RawEvent::RewardPoolFunded(depositor));
+    }
+
+    /// Transfers a tip to the authors of a public post, split by their shares.
+    pub fn tip_post(origin, post_id: T::PostId, amount: BalanceOf<T>) {
+      let tipper = ensure_signed(origin)?;
+
+      let post = Self::post_by_id(post_id).ok_or(MSG_POST_NOT_FOUND)?;
+      Self::ensure_post_is_public(&post)?;
+      ensure!(!amount.is_zero(), MSG_TIP_IS_ZERO);
+
+      Self::pay_post_authors(&post, &tipper, amount)?;
+
+      Self::notify(post.created.account.clone(), tipper.clone(), NotificationKind::PostTipped(post_id));
+      Self::deposit_event(RawEvent::PostTipped(tipper, post_id));
+    }
+
+    pub fn pin_post(origin, blog_id: T::BlogId, post_id: T::PostId) {
+      let owner = ensure_signed(origin)?;
+
+      let blog = Self::blog_by_id(blog_id).ok_or(MSG_BLOG_NOT_FOUND)?;
+      ensure!(owner == blog.created.account, MSG_ONLY_BLOG_OWNER_CAN_PIN_POST);
+      ensure!(Self::post_ids_by_blog_id(blog_id).contains(&post_id), MSG_POST_IS_NOT_IN_BLOG);
+
+      let mut pinned_post_ids = Self::pinned_post_ids(blog_id);
+      ensure!(!pinned_post_ids.contains(&post_id), MSG_POST_IS_ALREADY_PINNED);
+      ensure!(pinned_post_ids.len() < Self::max_pinned_posts_per_blog() as usize, MSG_TOO_MANY_PINNED_POSTS);
+
+      pinned_post_ids.push(post_id);
+      <PinnedPostIds<T>>::insert(blog_id, pinned_post_ids);
+
+      Self::deposit_event(RawEvent::PostPinned(owner, blog_id, post_id));
+    }
+
+    pub fn unpin_post(origin, blog_id: T::BlogId, post_id: T::PostId) {
+      let owner = ensure_signed(origin)?;
+
+      let blog = Self::blog_by_id(blog_id).ok_or(MSG_BLOG_NOT_FOUND)?;
+      ensure!(owner == blog.created.account, MSG_ONLY_BLOG_OWNER_CAN_PIN_POST);
+
+      let mut pinned_post_ids = Self::pinned_post_ids(blog_id);
+      ensure!(pinned_post_ids.contains(&post_id), MSG_POST_IS_NOT_PINNED);
+
+      pinned_post_ids.retain(|id| *id != post_id);
+      <PinnedPostIds<T>>::insert(blog_id, pinned_post_ids);
+
+      Self::deposit_event(RawEvent::PostUnpinned(owner, blog_id, post_id));
+    }
+
+    pub fn pin_comment(origin, post_id: T::PostId, comment_id: T::CommentId) {
+      let owner = ensure_signed(origin)?;
+
+      let post = Self::post_by_id(post_id).ok_or(MSG_POST_NOT_FOUND)?;
+      ensure!(owner == post.created.account, MSG_ONLY_POST_OWNER_CAN_PIN_COMMENT);
+      let comment = Self::comment_by_id(comment_id).ok_or(MSG_COMMENT_NOT_FOUND)?;
+      ensure!(comment.post_id == post_id, MSG_COMMENT_IS_NOT_ON_POST);
+
+      let mut pinned_comment_ids = Self::pinned_comment_ids(post_id);
+      ensure!(!pinned_comment_ids.contains(&comment_id), MSG_COMMENT_IS_ALREADY_PINNED);
+      ensure!(pinned_comment_ids.len() < Self::max_pinned_comments_per_post() as usize, MSG_TOO_MANY_PINNED_COMMENTS);
+
+      pinned_comment_ids.push(comment_id);
+      <PinnedCommentIds<T>>::insert(post_id, pinned_comment_ids);
+
+      Self::deposit_event(RawEvent::CommentPinned(owner, post_id, comment_id));
+    }
+
+    pub fn unpin_comment(origin, post_id: T::PostId, comment_id: T::CommentId) {
+      let owner = ensure_signed(origin)?;
+
+      let post = Self::post_by_id(post_id).ok_or(MSG_POST_NOT_FOUND)?;
+      ensure!(owner == post.created.account, MSG_ONLY_POST_OWNER_CAN_PIN_COMMENT);
+
+      let mut pinned_comment_ids = Self::pinned_comment_ids(post_id);
+      ensure!(pinned_comment_ids.contains(&comment_id), MSG_COMMENT_IS_NOT_PINNED);
+
+      pinned_comment_ids.retain(|id| *id != comment_id);
+      <PinnedCommentIds<T>>::insert(post_id, pinned_comment_ids);
+
+      Self::deposit_event(RawEvent::CommentUnpinned(owner, post_id, comment_id));
+    }
+
+    /// Single-choice polls accept exactly one option. Votes can not be changed.
+    pub fn vote_in_poll(origin, post_id: T::PostId, options: Vec<u16>) {
+      let voter = ensure_signed(origin)?;
+
+      let post = Self::post_by_id(post_id).ok_or(MSG_POST_NOT_FOUND)?;
+      Self::ensure_post_is_public(&post)?;
+      let poll = match post.extension {
+        PostExtension::Poll(poll) => poll,
+        _ => return Err(MSG_POST_IS_NOT_POLL),
+      };
+      ensure!(<system::Module<T>>::block_number() <= poll.ends_at, MSG_POLL_IS_CLOSED);
+      ensure!(!<PollVoteByAccount<T>>::exists((post_id, voter.clone())), MSG_ACCOUNT_ALREADY_VOTED_IN_POLL);
+
+      ensure!(!options.is_empty(), MSG_NO_POLL_OPTIONS_CHOSEN);
+      ensure!(poll.multiple_choice || options.len() == 1, MSG_POLL_IS_SINGLE_CHOICE);
+      for (i, option) in options.iter().enumerate() {
+        ensure!((*option as usize) < poll.options.len(), MSG_POLL_OPTION_NOT_FOUND);
+        ensure!(!options[..i].contains(option), MSG_POLL_OPTION_IS_CHOSEN_TWICE);
+      }
+
+      let weight = if poll.weighted_by_reputation {
+        Self::get_or_new_social_account(voter.clone()).reputation as u64
+      } else {
+        1
+      };
+      let mut tallies = Self::poll_tallies(post_id);
+      for option in options.iter() {
+        tallies[*option as usize] = tallies[*option as usize].saturating_add(weight);
+      }
+
+      <PollTallies<T>>::insert(post_id, tallies);
+      <PollVoteByAccount<T>>::insert((post_id, voter.clone()), options);
+
+      Self::deposit_event(RawEvent::PollVoted(voter, post_id));
     }
 
     // TODO use CommentUpdate to pass data?
-    pub fn create_comment(origin, post_id: T::PostId, parent_id: Option<T::CommentId>, ipfs_hash: Vec<u8>) {
+    pub fn create_comment(origin, post_id: T::PostId, parent_id: Option<T::CommentId>, content: Content, mentions: Vec<Mention<T>>) {
       let owner = ensure_signed(origin)?;
 
-      let ref mut post = Self::post_by_id(post_id).ok_or(MSG_POST_NOT_FOUND)?;
-      Self::is_ipfs_hash_valid(ipfs_hash.clone())?;
+      let ref post = Self::post_by_id(post_id).ok_or(MSG_POST_NOT_FOUND)?;
+      Self::ensure_post_is_public(post)?;
+      Self::is_content_valid(&content, Some(Self::comment_max_len()))?;
+      let mentioned_accounts = Self::resolve_mentions(&owner, mentions)?;
+
+      // Check the parent before any score is changed, so that a failed call leaves no trace:
+      let mut parent_counters = None;
+      if let Some(id) = parent_id {
+        ensure!(<CommentById<T>>::exists(id), MSG_UNKNOWN_PARENT_COMMENT);
+        let mut counters = Self::comment_counters(id);
+        counters.direct_replies_count = counters.direct_replies_count.checked_add(1).ok_or(MSG_OVERFLOW_REPLYING_ON_COMMENT)?;
+        parent_counters = Some(counters);
+      }
+      let mut post_counters = Self::post_counters(post_id);
+      post_counters.comments_count = post_counters.comments_count.checked_add(1).ok_or(MSG_OVERFLOW_ADDING_COMMENT_ON_POST)?;
+      Self::ensure_post_score_can_change(&owner, post, ScoringAction::CreateComment)?;
+      // Storage changes of a failed call are not reverted, so the fee is charged after all checks:
+      Self::charge_fee(&owner, PaidAction::CreateComment, content.size())?;
 
       let comment_id = Self::next_comment_id();
       let new_comment: Comment<T> = Comment {
@@ -550,31 +1488,31 @@ decl_module! {
         post_id,
         created: Self::new_change(owner.clone()),
         updated: None,
-        ipfs_hash,
-        upvotes_count: 0,
-        downvotes_count: 0,
-        shares_count: 0,
-        direct_replies_count: 0,
-        edit_history: vec![],
-        score: 0,
+        content,
       };
 
-      post.comments_count = post.comments_count.checked_add(1).ok_or(MSG_OVERFLOW_ADDING_COMMENT_ON_POST)?;
-
+      <PostCountersById<T>>::insert(post_id, post_counters);
       Self::change_post_score(owner.clone(), post, ScoringAction::CreateComment)?;
 
-      if let Some(id) = parent_id {
-        let mut parent_comment = Self::comment_by_id(id).ok_or(MSG_UNKNOWN_PARENT_COMMENT)?;
-        parent_comment.direct_replies_count = parent_comment.direct_replies_count.checked_add(1).ok_or(MSG_OVERFLOW_REPLYING_ON_COMMENT)?;
-        <CommentById<T>>::insert(id, parent_comment);
+      if let (Some(parent_id), Some(parent_counters)) = (parent_id, parent_counters) {
+        <CommentCountersById<T>>::insert(parent_id, parent_counters);
       }
 
       <CommentById<T>>::insert(comment_id, new_comment);
       <CommentIdsByPostId<T>>::mutate(post_id, |ids| ids.push(comment_id));
       <NextCommentId<T>>::mutate(|n| { *n += T::CommentId::sa(1); });
-      <PostById<T>>::insert(post_id, post);
+
+      // The post author is notified about a reply to their own comment only once:
+      let parent_author = parent_id.and_then(|id| Self::comment_by_id(id)).map(|parent| parent.created.account);
+      if let (Some(parent_id), Some(parent_author)) = (parent_id, parent_author.clone()) {
+        Self::notify(parent_author, owner.clone(), NotificationKind::CommentReplied(parent_id, comment_id));
+      }
+      if parent_author.as_ref() != Some(&post.created.account) {
+        Self::notify(post.created.account.clone(), owner.clone(), NotificationKind::PostCommented(post_id, comment_id));
+      }
 
       Self::deposit_event(RawEvent::CommentCreated(owner.clone(), comment_id));
+      Self::add_mentions(owner, mentioned_accounts, post_id, Some(comment_id));
     }
 
     pub fn create_post_reaction(origin, post_id: T::PostId, kind: ReactionKind) {
@@ -585,31 +1523,36 @@ decl_module! {
         MSG_ACCOUNT_ALREADY_REACTED_TO_POST
       );
 
-      let ref mut post = Self::post_by_id(post_id).ok_or(MSG_POST_NOT_FOUND)?;
-      let reaction_id = Self::new_reaction(owner.clone(), kind.clone());
+      let ref post = Self::post_by_id(post_id).ok_or(MSG_POST_NOT_FOUND)?;
+      Self::ensure_post_is_public(post)?;
       let action: ScoringAction;
+      let mut post_counters = Self::post_counters(post_id);
 
       match kind {
         ReactionKind::Upvote => {
-          post.upvotes_count = post.upvotes_count.checked_add(1).ok_or(MSG_OVERFLOW_UPVOTING_POST)?;
+          post_counters.upvotes_count = post_counters.upvotes_count.checked_add(1).ok_or(MSG_OVERFLOW_UPVOTING_POST)?;
           action = ScoringAction::UpvotePost;
         },
         ReactionKind::Downvote => {
-          post.downvotes_count = post.downvotes_count.checked_add(1).ok_or(MSG_OVERFLOW_DOWNVOTING_POST)?;
+          post_counters.downvotes_count = post_counters.downvotes_count.checked_add(1).ok_or(MSG_OVERFLOW_DOWNVOTING_POST)?;
           action = ScoringAction::DownvotePost;
         },
       }
+      Self::ensure_post_score_can_change(&owner, post, action)?;
+      // Storage changes of a failed call are not reverted, so the fee is charged after all checks:
+      Self::charge_fee(&owner, PaidAction::React, 0)?;
+
+      let reaction_id = Self::new_reaction(owner.clone(), kind.clone());
+      <PostCountersById<T>>::insert(post_id, post_counters);
 
       if post.created.account != owner {
         Self::change_post_score(owner.clone(), post, action)?;
       }
-      else {
-        <PostById<T>>::insert(post_id, post);
-      }
 
       <ReactionIdsByPostId<T>>::mutate(post_id, |ids| ids.push(reaction_id));
       <PostReactionIdByAccount<T>>::insert((owner.clone(), post_id), reaction_id);
 
+      Self::notify(post.created.account.clone(), owner.clone(), NotificationKind::PostReacted(post_id, reaction_id));
       Self::deposit_event(RawEvent::PostReactionCreated(owner.clone(), post_id, reaction_id));
     }
 
@@ -621,51 +1564,56 @@ decl_module! {
         MSG_ACCOUNT_ALREADY_REACTED_TO_COMMENT
       );
 
-      let ref mut comment = Self::comment_by_id(comment_id).ok_or(MSG_COMMENT_NOT_FOUND)?;
-      let reaction_id = Self::new_reaction(owner.clone(), kind.clone());
+      let ref comment = Self::comment_by_id(comment_id).ok_or(MSG_COMMENT_NOT_FOUND)?;
       let action: ScoringAction;
+      let mut comment_counters = Self::comment_counters(comment_id);
 
       match kind {
         ReactionKind::Upvote => {
-          comment.upvotes_count = comment.upvotes_count.checked_add(1).ok_or(MSG_OVERFLOW_UPVOTING_COMMENT)?;
+          comment_counters.upvotes_count = comment_counters.upvotes_count.checked_add(1).ok_or(MSG_OVERFLOW_UPVOTING_COMMENT)?;
           action = ScoringAction::UpvoteComment;
         },
         ReactionKind::Downvote => {
-          comment.downvotes_count = comment.downvotes_count.checked_add(1).ok_or(MSG_OVERFLOW_DOWNVOTING_COMMENT)?;
+          comment_counters.downvotes_count = comment_counters.downvotes_count.checked_add(1).ok_or(MSG_OVERFLOW_DOWNVOTING_COMMENT)?;
           action = ScoringAction::DownvoteComment;
         },
       }
+      Self::ensure_comment_score_can_change(&owner, comment, action)?;
+      // Storage changes of a failed call are not reverted, so the fee is charged after all checks:
+      Self::charge_fee(&owner, PaidAction::React, 0)?;
+
+      let reaction_id = Self::new_reaction(owner.clone(), kind.clone());
+      <CommentCountersById<T>>::insert(comment_id, comment_counters);
+
       if comment.created.account != owner {
         Self::change_comment_score(owner.clone(), comment, action)?;
       }
-      else {
-        <CommentById<T>>::insert(comment_id, comment);
-      }
 
       <ReactionIdsByCommentId<T>>::mutate(comment_id, |ids| ids.push(reaction_id));
       <CommentReactionIdByAccount<T>>::insert((owner.clone(), comment_id), reaction_id);
 
+      Self::notify(comment.created.account.clone(), owner.clone(), NotificationKind::CommentReacted(comment_id, reaction_id));
       Self::deposit_event(RawEvent::CommentReactionCreated(owner.clone(), comment_id, reaction_id));
     }
 
-    pub fn create_profile(origin, username: Vec<u8>, ipfs_hash: Vec<u8>) {
+    pub fn create_profile(origin, username: Vec<u8>, content: Content) {
       let owner = ensure_signed(origin)?;
 
       let mut social_account = Self::get_or_new_social_account(owner.clone());
       ensure!(social_account.profile.is_none(), MSG_PROFILE_ALREADY_EXISTS);
-      Self::is_username_valid(username.clone())?;
-      Self::is_ipfs_hash_valid(ipfs_hash.clone())?;
+      Self::is_username_valid(username.clone(), owner.clone())?;
+      Self::is_content_valid(&content, None)?;
+      Self::charge_fee(&owner, PaidAction::CreateProfile, username.len() + content.size())?;
 
       social_account.profile = Some(
         Profile {
           created: Self::new_change(owner.clone()),
           updated: None,
           username: username.clone(),
-          ipfs_hash,
-          edit_history: vec![]
+          content,
         }
       );
-      <AccountByProfileUsername<T>>::insert(username.clone(), owner.clone());
+      Self::claim_username(username, owner.clone());
       <SocialAccountById<T>>::insert(owner.clone(), social_account.clone());
 
       Self::deposit_event(RawEvent::ProfileCreated(owner.clone()));
@@ -676,32 +1624,51 @@ decl_module! {
 
       let has_updates =
         update.username.is_some() ||
-        update.ipfs_hash.is_some();
+        update.content.is_some();
       
       ensure!(has_updates, MSG_NOTHING_TO_UPDATE_IN_PROFILE);
 
       let mut social_account = Self::social_account_by_id(owner.clone()).ok_or(MSG_SOCIAL_ACCOUNT_NOT_FOUND)?;
       let mut profile = social_account.profile.ok_or(MSG_PROFILE_DOESNT_EXIST)?;
+      let payload_len = update.username.as_ref().map_or(0, |username| username.len())
+        + update.content.as_ref().map_or(0, |content| content.size());
+      if let Some(ref content) = update.content {
+        if *content != profile.content {
+          Self::is_content_valid(content, None)?;
+        }
+      }
+      if let Some(ref username) = update.username {
+        if *username != profile.username {
+          Self::is_username_valid(username.clone(), owner.clone())?;
+        }
+      }
+      // Storage changes of a failed call are not reverted, so the fee is charged after all checks:
+      Self::charge_fee(&owner, PaidAction::UpdateProfile, payload_len)?;
+
       let mut is_update_applied = false;
       let mut new_history_record = ProfileHistoryRecord {
         edited: Self::new_change(owner.clone()),
-        old_data: ProfileUpdate {username: None, ipfs_hash: None}
+        old_data: ProfileUpdate {username: None, content: None}
       };
 
-      if let Some(ipfs_hash) = update.ipfs_hash {
-        if ipfs_hash != profile.ipfs_hash {
-          Self::is_ipfs_hash_valid(ipfs_hash.clone())?;
-          new_history_record.old_data.ipfs_hash = Some(profile.ipfs_hash);
-          profile.ipfs_hash = ipfs_hash;
+      if let Some(content) = update.content {
+        if content != profile.content {
+          new_history_record.old_data.content = Some(profile.content);
+          profile.content = content;
           is_update_applied = true;
         }
       }
 
       if let Some(username) = update.username {
         if username != profile.username {
-          Self::is_username_valid(username.clone())?;
-          <AccountByProfileUsername<T>>::remove(profile.username.clone());
-          <AccountByProfileUsername<T>>::insert(username.clone(), owner.clone());
+          let old_username_key = Self::to_lowercase(profile.username.clone());
+          if old_username_key != Self::to_lowercase(username.clone()) {
+            // A profile has no username after it was reclaimed:
+            if !old_username_key.is_empty() {
+              Self::release_username(old_username_key, owner.clone());
+            }
+            Self::claim_username(username.clone(), owner.clone());
+          }
           new_history_record.old_data.username = Some(profile.username);
           profile.username = username;
           is_update_applied = true;
@@ -710,7 +1677,7 @@ decl_module! {
 
       if is_update_applied {
         profile.updated = Some(Self::new_change(owner.clone()));
-        profile.edit_history.push(new_history_record);
+        Self::add_profile_revision(owner.clone(), new_history_record);
         social_account.profile = Some(profile);
         <SocialAccountById<T>>::insert(owner.clone(), social_account);
 
@@ -718,13 +1685,95 @@ decl_module! {
       }
     }
 
+    pub fn renew_username(origin) {
+      let owner = ensure_signed(origin)?;
+
+      let social_account = Self::social_account_by_id(owner.clone()).ok_or(MSG_SOCIAL_ACCOUNT_NOT_FOUND)?;
+      let profile = social_account.profile.ok_or(MSG_PROFILE_DOESNT_EXIST)?;
+      ensure!(!profile.username.is_empty(), MSG_PROFILE_HAS_NO_USERNAME);
+      Self::ensure_can_lock_username_deposit(&owner, &profile.username)?;
+
+      // Usernames registered before deposits were introduced have no expiry, renewal starts it:
+      let now = <system::Module<T>>::block_number();
+      let username_key = Self::to_lowercase(profile.username.clone());
+      let expires_at = Self::username_expires_at(username_key.clone()).map_or(now, |expires_at| expires_at.max(now))
+        + Self::username_registration_period();
+
+      Self::lock_username_deposit(&owner, &profile.username, expires_at);
+      <UsernameExpiresAt<T>>::insert(username_key, expires_at);
+
+      Self::deposit_event(RawEvent::UsernameRenewed(owner));
+    }
+
+    pub fn reclaim_expired_username(origin, username: Vec<u8>) {
+      let reclaimer = ensure_signed(origin)?;
+
+      let username_key = Self::to_lowercase(username);
+      let expires_at = Self::username_expires_at(username_key.clone()).ok_or(MSG_USERNAME_HAS_NO_EXPIRY)?;
+      ensure!(expires_at <= <system::Module<T>>::block_number(), MSG_USERNAME_HAS_NOT_EXPIRED);
+
+      let owner = Self::account_by_profile_username(username_key.clone()).ok_or(MSG_USERNAME_HAS_NO_EXPIRY)?;
+      let mut social_account = Self::social_account_by_id(owner.clone()).ok_or(MSG_SOCIAL_ACCOUNT_NOT_FOUND)?;
+      let mut profile = social_account.profile.ok_or(MSG_PROFILE_DOESNT_EXIST)?;
+
+      Self::add_profile_revision(owner.clone(), ProfileHistoryRecord {
+        edited: Self::new_change(reclaimer.clone()),
+        old_data: ProfileUpdate {username: Some(profile.username), content: None}
+      });
+      profile.username = vec![];
+      profile.updated = Some(Self::new_change(reclaimer.clone()));
+      social_account.profile = Some(profile);
+      <SocialAccountById<T>>::insert(owner.clone(), social_account);
+
+      // Expired username is free right away, without a release cooldown:
+      Self::unlock_username_deposit(&owner);
+      <UsernameExpiresAt<T>>::remove(username_key.clone());
+      <AccountByProfileUsername<T>>::remove(username_key);
+
+      Self::deposit_event(RawEvent::UsernameReclaimed(reclaimer, owner));
+    }
+
+    pub fn attest_account(origin, account: T::AccountId, claim: AttestationClaim, expires_at: Option<T::BlockNumber>) {
+      let verifier = ensure_signed(origin)?;
+      ensure!(Self::is_verifier(verifier.clone()), MSG_ONLY_VERIFIER_CAN_ATTEST);
+
+      let social_account = Self::social_account_by_id(account.clone()).ok_or(MSG_SOCIAL_ACCOUNT_NOT_FOUND)?;
+      ensure!(social_account.profile.is_some(), MSG_PROFILE_DOESNT_EXIST);
+      if let Some(expires_at) = expires_at {
+        ensure!(expires_at > <system::Module<T>>::block_number(), MSG_ATTESTATION_EXPIRES_IN_PAST);
+      }
+
+      let mut attestations = Self::attestations_by_account(account.clone());
+      ensure!(
+        !attestations.iter().any(|a| a.verifier == verifier && a.claim == claim && Self::is_attestation_active(a)),
+        MSG_ATTESTATION_ALREADY_EXISTS
+      );
+
+      attestations.push(Attestation {
+        verifier: verifier.clone(),
+        claim,
+        created: Self::new_change(verifier.clone()),
+        expires_at,
+        revoked: None,
+      });
+      <AttestationsByAccount<T>>::insert(account.clone(), attestations);
+
+      Self::deposit_event(RawEvent::AccountAttested(verifier, account));
+    }
+
+    /// Revoked attestations are kept for the record.
+    pub fn revoke_attestation(origin, account: T::AccountId, index: u32) {
+      let verifier = ensure_signed(origin)?;
+      Self::revoke_attestation_at(account, index, Some(verifier))?;
+    }
+
     pub fn update_blog(origin, blog_id: T::BlogId, update: BlogUpdate<T>) {
       let owner = ensure_signed(origin)?;
       
       let has_updates = 
         update.writers.is_some() ||
         update.slug.is_some() ||
-        update.ipfs_hash.is_some();
+        update.content.is_some();
 
       ensure!(has_updates, MSG_NOTHING_TO_UPDATE_IN_BLOG);
 
@@ -733,10 +1782,25 @@ decl_module! {
       // TODO ensure: blog writers also should be able to edit this blog:
       ensure!(owner == blog.created.account, MSG_ONLY_BLOG_OWNER_CAN_UPDATE_BLOG);
 
+      let payload_len = update.writers.as_ref().map_or(0, |writers| writers.len() * rstd::mem::size_of::<T::AccountId>())
+        + update.slug.as_ref().map_or(0, |slug| slug.len())
+        + update.content.as_ref().map_or(0, |content| content.size());
+      if let Some(ref content) = update.content {
+        if *content != blog.content {
+          Self::is_content_valid(content, Some(Self::blog_max_len()))?;
+        }
+      }
+      let new_slug = match update.slug {
+        Some(slug) => Some(Self::normalize_slug(slug, blog_id)?),
+        None => None,
+      };
+      // Storage changes of a failed call are not reverted, so the fee is charged after all checks:
+      Self::charge_fee(&owner, PaidAction::UpdateBlog, payload_len)?;
+
       let mut fields_updated = 0;
       let mut new_history_record = BlogHistoryRecord {
         edited: Self::new_change(owner.clone()),
-        old_data: BlogUpdate {writers: None, slug: None, ipfs_hash: None}
+        old_data: BlogUpdate {writers: None, slug: None, content: None}
       };
 
       if let Some(writers) = update.writers {
@@ -749,24 +1813,20 @@ decl_module! {
         }
       }
 
-      if let Some(ipfs_hash) = update.ipfs_hash {
-        if ipfs_hash != blog.ipfs_hash {
-          Self::is_ipfs_hash_valid(ipfs_hash.clone())?;
-          new_history_record.old_data.ipfs_hash = Some(blog.ipfs_hash);
-          blog.ipfs_hash = ipfs_hash;
+      if let Some(content) = update.content {
+        if content != blog.content {
+          new_history_record.old_data.content = Some(blog.content);
+          blog.content = content;
           fields_updated += 1;
         }
       }
 
-      if let Some(slug) = update.slug {
+      if let Some(slug) = new_slug {
         if slug != blog.slug {
-          let slug_len = slug.len();
-          ensure!(slug_len >= Self::slug_min_len() as usize, MSG_BLOG_SLUG_IS_TOO_SHORT);
-          ensure!(slug_len <= Self::slug_max_len() as usize, MSG_BLOG_SLUG_IS_TOO_LONG);
-          ensure!(!<BlogIdBySlug<T>>::exists(slug.clone()), MSG_BLOG_SLUG_IS_NOT_UNIQUE);
-
-          <BlogIdBySlug<T>>::remove(blog.slug.clone());
-          <BlogIdBySlug<T>>::insert(slug.clone(), blog_id);
+          // The old slug is not released right away, but redirects to this blog for a while:
+          let redirect_expires_at = <system::Module<T>>::block_number() + Self::slug_redirect_period();
+          <SlugRedirectExpiresAt<T>>::insert(blog.slug.clone(), redirect_expires_at);
+          Self::claim_slug(slug.clone(), blog_id);
           new_history_record.old_data.slug = Some(blog.slug);
           blog.slug = slug;
           fields_updated += 1;
@@ -776,18 +1836,43 @@ decl_module! {
       // Update this blog only if at least one field should be updated:
       if fields_updated > 0 {
         blog.updated = Some(Self::new_change(owner.clone()));
-        blog.edit_history.push(new_history_record);
+        Self::add_blog_revision(blog_id, new_history_record);
         <BlogById<T>>::insert(blog_id, blog);
         Self::deposit_event(RawEvent::BlogUpdated(owner.clone(), blog_id));
       }
     }
-    
-    pub fn update_post(origin, post_id: T::PostId, update: PostUpdate<T>) {
+
+    /// Makes a draft or unlisted post public and lists it in its blog.
+    pub fn publish_post(origin, post_id: T::PostId) {
+      let owner = ensure_signed(origin)?;
+
+      let mut post = Self::post_by_id(post_id).ok_or(MSG_POST_NOT_FOUND)?;
+      ensure!(owner == post.created.account, MSG_ONLY_POST_OWNER_CAN_PUBLISH_POST);
+      ensure!(post.visibility != PostVisibility::Public, MSG_POST_IS_ALREADY_PUBLIC);
+
+      Self::ensure_blog_exists(post.blog_id)?;
+      let mut blog_counters = Self::blog_counters(post.blog_id);
+      blog_counters.posts_count = blog_counters.posts_count.checked_add(1).ok_or(MSG_OVERFLOW_ADDING_POST_ON_BLOG)?;
+
+      Self::add_post_tags(post_id, &post.tags);
+      <PostIdsByBlogId<T>>::mutate(post.blog_id, |ids| ids.push(post_id));
+      <BlogCountersById<T>>::insert(post.blog_id, blog_counters);
+      post.visibility = PostVisibility::Public;
+      <PostById<T>>::insert(post_id, post);
+
+      Self::deposit_event(RawEvent::PostPublished(owner, post_id));
+    }
+
+    /// Accounts that are mentioned for the first time in this post are notified even if nothing else changes.
+    /// `PostUpdated` is emitted on every successful update, as the fee is charged either way.
+    pub fn update_post(origin, post_id: T::PostId, update: PostUpdate<T>, mentions: Vec<Mention<T>>) {
       let owner = ensure_signed(origin)?;
       
       let has_updates = 
         update.blog_id.is_some() ||
-        update.ipfs_hash.is_some();
+        update.content.is_some() ||
+        update.tags.is_some() ||
+        !mentions.is_empty();
 
       ensure!(has_updates, MSG_NOTHING_TO_UPDATE_IN_POST);
 
@@ -795,75 +1880,145 @@ decl_module! {
 
       // TODO ensure: blog writers also should be able to edit this post:
       ensure!(owner == post.created.account, MSG_ONLY_POST_OWNER_CAN_UPDATE_POST);
+      ensure!(mentions.is_empty() || post.visibility != PostVisibility::Draft, MSG_CANNOT_MENTION_IN_DRAFT);
+      let mentioned_accounts = Self::resolve_mentions(&owner, mentions)?;
+      let is_public = post.visibility == PostVisibility::Public;
+      let new_tags = match update.tags {
+        Some(tags) => Some(Self::normalize_tags(tags)?),
+        None => None,
+      };
+
+      if let Some(ref content) = update.content {
+        if *content != post.content {
+          Self::is_content_valid(content, Some(Self::post_max_len()))?;
+        }
+      }
+      // New counters of the old and the new blog, if a public post moves. An unpublished one is not listed yet:
+      let moved_blog_counters = match update.blog_id {
+        Some(blog_id) if blog_id != post.blog_id => {
+          Self::ensure_blog_exists(blog_id)?;
+          if is_public {
+            ensure!(!Self::cross_post_blog_ids(post_id).contains(&blog_id), MSG_POST_IS_ALREADY_IN_BLOG);
+            Self::ensure_blog_exists(post.blog_id)?;
+            let mut old_blog_counters = Self::blog_counters(post.blog_id);
+            let mut new_blog_counters = Self::blog_counters(blog_id);
+            old_blog_counters.posts_count = old_blog_counters.posts_count.checked_sub(1).ok_or(MSG_UNDERFLOW_REMOVING_POST_FROM_BLOG)?;
+            new_blog_counters.posts_count = new_blog_counters.posts_count.checked_add(1).ok_or(MSG_OVERFLOW_ADDING_POST_ON_BLOG)?;
+            // The score earned by this post in its old blog moves along with it:
+            let score_in_blog = Self::post_score_in_blog((post.blog_id, post_id));
+            old_blog_counters.score = old_blog_counters.score.checked_sub(score_in_blog).ok_or(MSG_OUT_OF_BOUNDS_REVERTING_BLOG_SCORE)?;
+            new_blog_counters.score = new_blog_counters.score.checked_add(score_in_blog).ok_or(MSG_OUT_OF_BOUNDS_UPDATING_BLOG_SCORE)?;
+            Some((old_blog_counters, new_blog_counters, score_in_blog))
+          } else {
+            None
+          }
+        },
+        _ => None,
+      };
+
+      let payload_len = update.content.as_ref().map_or(0, |content| content.size())
+        + new_tags.as_ref().map_or(0, |tags| Self::tags_size(tags));
+      // Storage changes of a failed call are not reverted, so the fee is charged after all checks:
+      Self::charge_fee(&owner, PaidAction::UpdatePost, payload_len)?;
 
       let mut fields_updated = 0;
       let mut new_history_record = PostHistoryRecord {
         edited: Self::new_change(owner.clone()),
-        old_data: PostUpdate {blog_id: None, ipfs_hash: None}
+        old_data: PostUpdate {blog_id: None, content: None, tags: None}
       };
 
-      if let Some(ipfs_hash) = update.ipfs_hash {
-        if ipfs_hash != post.ipfs_hash {
-          Self::is_ipfs_hash_valid(ipfs_hash.clone())?;
-          new_history_record.old_data.ipfs_hash = Some(post.ipfs_hash);
-          post.ipfs_hash = ipfs_hash;
+      if let Some(content) = update.content {
+        if content != post.content {
+          new_history_record.old_data.content = Some(post.content);
+          post.content = content;
           fields_updated += 1;
         }
       }
 
       // Move this post to another blog:
       if let Some(blog_id) = update.blog_id {
-        if blog_id != post.blog_id {
-
-          let mut old_blog = Self::blog_by_id(post.blog_id).ok_or(MSG_BLOG_NOT_FOUND)?;
-          let mut new_blog = Self::blog_by_id(blog_id).ok_or(MSG_BLOG_NOT_FOUND)?;
-          old_blog.posts_count = old_blog.posts_count.checked_sub(1).ok_or(MSG_UNDERFLOW_REMOVING_POST_FROM_BLOG)?;
-          new_blog.posts_count = old_blog.posts_count.checked_add(1).ok_or(MSG_OVERFLOW_ADDING_POST_ON_BLOG)?;
-          
+        if let Some((old_blog_counters, new_blog_counters, score_in_blog)) = moved_blog_counters {
           // Remove post_id from its old blog:
           <PostIdsByBlogId<T>>::mutate(post.blog_id, |post_ids| Self::vec_remove_on(post_ids, post_id));
-          <BlogById<T>>::insert(post.blog_id, old_blog);
+          <BlogCountersById<T>>::insert(post.blog_id, old_blog_counters);
+          <PostScoreInBlog<T>>::remove((post.blog_id, post_id));
+          <PinnedPostIds<T>>::mutate(post.blog_id, |ids| ids.retain(|id| *id != post_id));
           
           // Add post_id to its new blog:
           <PostIdsByBlogId<T>>::mutate(blog_id.clone(), |ids| ids.push(post_id));
-          <BlogById<T>>::insert(blog_id, new_blog);
+          <BlogCountersById<T>>::insert(blog_id, new_blog_counters);
+          <PostScoreInBlog<T>>::insert((blog_id, post_id), score_in_blog);
+        }
+        if blog_id != post.blog_id {
           new_history_record.old_data.blog_id = Some(post.blog_id);
           post.blog_id = blog_id;
           fields_updated += 1;
         }
       }
 
+      if let Some(tags) = new_tags {
+        if tags != post.tags {
+          if is_public {
+            Self::replace_post_tags(post_id, &post.tags, &tags);
+          }
+          new_history_record.old_data.tags = Some(post.tags);
+          post.tags = tags;
+          fields_updated += 1;
+        }
+      }
+
       // Update this post only if at least one field should be updated:
       if fields_updated > 0 {
         post.updated = Some(Self::new_change(owner.clone()));
-        post.edit_history.push(new_history_record);
+        Self::add_post_revision(post_id, new_history_record);
         <PostById<T>>::insert(post_id, post);
-
-        Self::deposit_event(RawEvent::PostUpdated(owner.clone(), post_id));
       }
+
+      Self::deposit_event(RawEvent::PostUpdated(owner.clone(), post_id));
+      Self::add_mentions(owner, mentioned_accounts, post_id, None);
     }
     
-    pub fn update_comment(origin, comment_id: T::CommentId, update: CommentUpdate) {
+    pub fn update_comment(origin, comment_id: T::CommentId, update: CommentUpdate, mentions: Vec<Mention<T>>) {
       let owner = ensure_signed(origin)?;
 
       let mut comment = Self::comment_by_id(comment_id).ok_or(MSG_COMMENT_NOT_FOUND)?;
       ensure!(owner == comment.created.account, MSG_ONLY_COMMENT_AUTHOR_CAN_UPDATE_COMMENT);
 
-      let ipfs_hash = update.ipfs_hash;
-      ensure!(ipfs_hash != comment.ipfs_hash, MSG_NEW_COMMENT_HASH_DO_NOT_DIFFER);
-      Self::is_ipfs_hash_valid(ipfs_hash.clone())?;
+      let content = update.content;
+      ensure!(content != comment.content, MSG_NEW_COMMENT_CONTENT_DO_NOT_DIFFER);
+      Self::is_content_valid(&content, Some(Self::comment_max_len()))?;
+      let mentioned_accounts = Self::resolve_mentions(&owner, mentions)?;
+      Self::charge_fee(&owner, PaidAction::UpdateComment, content.size())?;
 
       let new_history_record = CommentHistoryRecord {
         edited: Self::new_change(owner.clone()),
-        old_data: CommentUpdate {ipfs_hash: comment.ipfs_hash}
+        old_data: CommentUpdate {content: comment.content}
       };
-      comment.edit_history.push(new_history_record);
+      Self::add_comment_revision(comment_id, new_history_record);
 
-      comment.ipfs_hash = ipfs_hash;
+      comment.content = content;
       comment.updated = Some(Self::new_change(owner.clone()));
+      let post_id = comment.post_id;
       <CommentById<T>>::insert(comment_id, comment);
 
       Self::deposit_event(RawEvent::CommentUpdated(owner.clone(), comment_id));
+      Self::add_mentions(owner, mentioned_accounts, post_id, Some(comment_id));
+    }
+
+    /// Restores the blog as it was before the given revision. The revert is recorded as a new revision.
+    pub fn revert_blog(origin, blog_id: T::BlogId, revision: u32) {
+      let update = Self::blog_state_before_revision(blog_id, revision)?;
+      Self::update_blog(origin, blog_id, update)?;
+    }
+
+    pub fn revert_post(origin, post_id: T::PostId, revision: u32) {
+      let update = Self::post_state_before_revision(post_id, revision)?;
+      Self::update_post(origin, post_id, update, vec![])?;
+    }
+
+    pub fn revert_comment(origin, comment_id: T::CommentId, revision: u32) {
+      let update = Self::comment_state_before_revision(comment_id, revision)?;
+      Self::update_comment(origin, comment_id, update, vec![])?;
     }
 
     pub fn update_post_reaction(origin, post_id: T::PostId, reaction_id: T::ReactionId, new_kind: ReactionKind) {
@@ -875,7 +2030,7 @@ decl_module! {
       );
 
       let mut reaction = Self::reaction_by_id(reaction_id).ok_or(MSG_REACTION_NOT_FOUND)?;
-      let ref mut post = Self::post_by_id(post_id).ok_or(MSG_POST_NOT_FOUND)?;
+      let ref post = Self::post_by_id(post_id).ok_or(MSG_POST_NOT_FOUND)?;
 
       ensure!(owner == reaction.created.account, MSG_ONLY_REACTION_OWNER_CAN_UPDATE_REACTION);
       ensure!(reaction.kind != new_kind, MSG_NEW_REACTION_KIND_DO_NOT_DIFFER);
@@ -885,26 +2040,33 @@ decl_module! {
       let action: ScoringAction;
       let action_to_cancel: ScoringAction;
       
+      let mut post_counters = Self::post_counters(post_id);
       match new_kind {
         ReactionKind::Upvote => {
-          post.upvotes_count += 1;
-          post.downvotes_count -= 1;
+          post_counters.upvotes_count += 1;
+          post_counters.downvotes_count -= 1;
           action_to_cancel = ScoringAction::DownvotePost;
           action = ScoringAction::UpvotePost;
         },
         ReactionKind::Downvote => {
-          post.downvotes_count += 1;
-          post.upvotes_count -= 1;
+          post_counters.downvotes_count += 1;
+          post_counters.upvotes_count -= 1;
           action_to_cancel = ScoringAction::UpvotePost;
           action = ScoringAction::DownvotePost;
         },
       }
+      // The new action reverts the opposite one first, as the cancelled action does:
+      Self::ensure_post_score_can_change(&owner, post, action)?;
+      // Storage changes of a failed call are not reverted, so the fee is charged after all checks:
+      Self::charge_fee(&owner, PaidAction::React, 0)?;
+
+      <PostCountersById<T>>::insert(post_id, post_counters);
       Self::change_post_score(owner.clone(), post, action_to_cancel)?;
       Self::change_post_score(owner.clone(), post, action)?;
 
       <ReactionById<T>>::insert(reaction_id, reaction);
-      <PostById<T>>::insert(post_id, post);
 
+      Self::notify(post.created.account.clone(), owner.clone(), NotificationKind::PostReacted(post_id, reaction_id));
       Self::deposit_event(RawEvent::PostReactionUpdated(owner.clone(), post_id, reaction_id));
     }
 
@@ -917,7 +2079,7 @@ decl_module! {
       );
 
       let mut reaction = Self::reaction_by_id(reaction_id).ok_or(MSG_REACTION_NOT_FOUND)?;
-      let ref mut comment = Self::comment_by_id(comment_id).ok_or(MSG_COMMENT_NOT_FOUND)?;
+      let ref comment = Self::comment_by_id(comment_id).ok_or(MSG_COMMENT_NOT_FOUND)?;
 
       ensure!(owner == reaction.created.account, MSG_ONLY_REACTION_OWNER_CAN_UPDATE_REACTION);
       ensure!(reaction.kind != new_kind, MSG_NEW_REACTION_KIND_DO_NOT_DIFFER);
@@ -927,29 +2089,49 @@ decl_module! {
       let action: ScoringAction;
       let action_to_cancel: ScoringAction;
       
+      let mut comment_counters = Self::comment_counters(comment_id);
       match new_kind {
         ReactionKind::Upvote => {
-          comment.upvotes_count += 1;
-          comment.downvotes_count -= 1;
+          comment_counters.upvotes_count += 1;
+          comment_counters.downvotes_count -= 1;
           action_to_cancel = ScoringAction::DownvoteComment;
           action = ScoringAction::UpvoteComment;
         },
         ReactionKind::Downvote => {
-          comment.downvotes_count += 1;
-          comment.upvotes_count -= 1;
+          comment_counters.downvotes_count += 1;
+          comment_counters.upvotes_count -= 1;
           action_to_cancel = ScoringAction::UpvoteComment;
           action = ScoringAction::DownvoteComment;
         },
       }
+      // The new action reverts the opposite one first, as the cancelled action does:
+      Self::ensure_comment_score_can_change(&owner, comment, action)?;
+      // Storage changes of a failed call are not reverted, so the fee is charged after all checks:
+      Self::charge_fee(&owner, PaidAction::React, 0)?;
+
+      <CommentCountersById<T>>::insert(comment_id, comment_counters);
       Self::change_comment_score(owner.clone(), comment, action_to_cancel)?;
       Self::change_comment_score(owner.clone(), comment, action)?;
 
       <ReactionById<T>>::insert(reaction_id, reaction);
-      <CommentById<T>>::insert(comment_id, comment);
 
+      Self::notify(comment.created.account.clone(), owner.clone(), NotificationKind::CommentReacted(comment_id, reaction_id));
       Self::deposit_event(RawEvent::CommentReactionUpdated(owner.clone(), comment_id, reaction_id));
     }
 
+    /// Marks notifications with indexes below `up_to` as read.
+    pub fn mark_notifications_read(origin, up_to: u32) {
+      let owner = ensure_signed(origin)?;
+
+      ensure!(up_to <= Self::notifications_count(owner.clone()), MSG_NOTIFICATION_NOT_FOUND);
+      ensure!(up_to > Self::notifications_read_up_to(owner.clone()), MSG_NOTIFICATIONS_ARE_ALREADY_READ);
+
+      <NotificationsReadUpTo<T>>::insert(owner.clone(), up_to);
+      Self::update_unread_notifications_count(owner.clone());
+
+      Self::deposit_event(RawEvent::NotificationsRead(owner, up_to));
+    }
+
     // TODO fn delete_blog(origin, blog_id: T::BlogId) {
       // TODO only owner can delete
       // TODO unfollow all blog followers
@@ -969,24 +2151,25 @@ decl_module! {
       
       let action_to_cancel: ScoringAction;
       let reaction = Self::reaction_by_id(reaction_id).ok_or(MSG_REACTION_NOT_FOUND)?;
-      let ref mut post = Self::post_by_id(post_id).ok_or(MSG_POST_NOT_FOUND)?;
+      let ref post = Self::post_by_id(post_id).ok_or(MSG_POST_NOT_FOUND)?;
 
       ensure!(owner == reaction.created.account, MSG_ONLY_REACTION_OWNER_CAN_UPDATE_REACTION);
 
+      let mut post_counters = Self::post_counters(post_id);
       match reaction.kind {
         ReactionKind::Upvote => {
-          post.upvotes_count -= 1;
+          post_counters.upvotes_count -= 1;
           action_to_cancel = ScoringAction::UpvotePost;
         },
         ReactionKind::Downvote => {
-          post.downvotes_count -= 1;
+          post_counters.downvotes_count -= 1;
           action_to_cancel = ScoringAction::DownvotePost;
 
         },
       }
+      <PostCountersById<T>>::insert(post_id, post_counters);
       Self::change_post_score(owner.clone(), post, action_to_cancel)?;
 
-      <PostById<T>>::insert(post_id, post);
       <ReactionById<T>>::remove(reaction_id);
       <ReactionIdsByPostId<T>>::mutate(post_id, |ids| Self::vec_remove_on(ids, reaction_id));
       <PostReactionIdByAccount<T>>::remove((owner.clone(), post_id));
@@ -1004,23 +2187,24 @@ decl_module! {
       
       let action_to_cancel: ScoringAction;
       let reaction = Self::reaction_by_id(reaction_id).ok_or(MSG_REACTION_NOT_FOUND)?;
-      let ref mut comment = Self::comment_by_id(comment_id).ok_or(MSG_COMMENT_NOT_FOUND)?;
+      let ref comment = Self::comment_by_id(comment_id).ok_or(MSG_COMMENT_NOT_FOUND)?;
       
       ensure!(owner == reaction.created.account, MSG_ONLY_REACTION_OWNER_CAN_UPDATE_REACTION);
 
+      let mut comment_counters = Self::comment_counters(comment_id);
       match reaction.kind {
         ReactionKind::Upvote => {
-          comment.upvotes_count -= 1;
+          comment_counters.upvotes_count -= 1;
           action_to_cancel = ScoringAction::UpvoteComment
         },
         ReactionKind::Downvote => {
-          comment.downvotes_count -= 1;
+          comment_counters.downvotes_count -= 1;
           action_to_cancel = ScoringAction::DownvoteComment
         },
       }
+      <CommentCountersById<T>>::insert(comment_id, comment_counters);
       Self::change_comment_score(owner.clone(), comment, action_to_cancel)?;
 
-      <CommentById<T>>::insert(comment_id, comment);
       <ReactionIdsByCommentId<T>>::mutate(comment_id, |ids| Self::vec_remove_on(ids, reaction_id));
       <ReactionById<T>>::remove(reaction_id);
       <CommentReactionIdByAccount<T>>::remove((owner.clone(), comment_id));
@@ -1028,6 +2212,180 @@ decl_module! {
       Self::deposit_event(RawEvent::CommentReactionDeleted(owner.clone(), comment_id, reaction_id));
     }
 
-    // TODO spend some tokens on: create/update a blog/post/comment.
+    pub fn set_action_base_fee(origin, action: PaidAction, fee: BalanceOf<T>) {
+      ensure_root(origin)?;
+      <BaseFeeByAction<T>>::insert(action, fee);
+    }
+
+    pub fn set_fee_per_byte(origin, fee: BalanceOf<T>) {
+      ensure_root(origin)?;
+      <FeePerByte<T>>::put(fee);
+    }
+
+    pub fn set_fee_reputation_thresholds(origin, discount_reputation: u32, discount_percent: u8, exempt_reputation: u32) {
+      ensure_root(origin)?;
+
+      ensure!(discount_percent <= 100, MSG_FEE_DISCOUNT_PERCENT_IS_TOO_BIG);
+      ensure!(exempt_reputation >= discount_reputation, MSG_FEE_EXEMPT_REPUTATION_BELOW_DISCOUNT);
+
+      <FeeDiscountReputation<T>>::put(discount_reputation);
+      <FeeDiscountPercent<T>>::put(discount_percent);
+      <FeeExemptReputation<T>>::put(exempt_reputation);
+    }
+
+    pub fn reserve_slugs(origin, slugs: Vec<Vec<u8>>) {
+      ensure_root(origin)?;
+
+      for slug in slugs {
+        <ReservedSlugs<T>>::insert(Self::to_lowercase(slug), true);
+      }
+    }
+
+    pub fn unreserve_slugs(origin, slugs: Vec<Vec<u8>>) {
+      ensure_root(origin)?;
+
+      for slug in slugs {
+        <ReservedSlugs<T>>::remove(Self::to_lowercase(slug));
+      }
+    }
+
+    pub fn set_slug_redirect_period(origin, period: T::BlockNumber) {
+      ensure_root(origin)?;
+      <SlugRedirectPeriod<T>>::put(period);
+    }
+
+    pub fn reserve_usernames(origin, usernames: Vec<Vec<u8>>) {
+      ensure_root(origin)?;
+
+      for username in usernames {
+        <ReservedUsernames<T>>::insert(Self::to_lowercase(username), true);
+      }
+    }
+
+    pub fn unreserve_usernames(origin, usernames: Vec<Vec<u8>>) {
+      ensure_root(origin)?;
+
+      for username in usernames {
+        <ReservedUsernames<T>>::remove(Self::to_lowercase(username));
+      }
+    }
+
+    pub fn set_username_release_cooldown(origin, cooldown: T::BlockNumber) {
+      ensure_root(origin)?;
+      <UsernameReleaseCooldown<T>>::put(cooldown);
+    }
+
+    pub fn set_username_deposit_base(origin, deposit: BalanceOf<T>) {
+      ensure_root(origin)?;
+      <UsernameDepositBase<T>>::put(deposit);
+    }
+
+    pub fn set_username_registration_period(origin, period: T::BlockNumber) {
+      ensure_root(origin)?;
+      <UsernameRegistrationPeriod<T>>::put(period);
+    }
+
+    pub fn set_tag_usage_buckets(origin, bucket_len: T::BlockNumber, buckets_count: u32, pruning_chunk_len: u32) {
+      ensure_root(origin)?;
+      ensure!(!bucket_len.is_zero() && buckets_count > 0 && pruning_chunk_len > 0, MSG_TAG_USAGE_BUCKETS_ARE_EMPTY);
+
+      <TagUsageBucketLen<T>>::put(bucket_len);
+      <TagUsageBucketsCount<T>>::put(buckets_count);
+      <TagPruningChunkLen<T>>::put(pruning_chunk_len);
+    }
+
+    pub fn set_reward_epoch(origin, epoch_length: T::BlockNumber, chunk_len: u32) {
+      ensure_root(origin)?;
+      ensure!(!epoch_length.is_zero() && chunk_len > 0, MSG_REWARD_EPOCH_IS_EMPTY);
+
+      <RewardEpochLength<T>>::put(epoch_length);
+      <RewardDistributionChunkLen<T>>::put(chunk_len);
+    }
+
+    pub fn set_legacy_migration_chunk_len(origin, chunk_len: u32) {
+      ensure_root(origin)?;
+      ensure!(chunk_len > 0, MSG_LEGACY_MIGRATION_CHUNK_IS_EMPTY);
+      <LegacyMigrationChunkLen<T>>::put(chunk_len);
+    }
+
+    pub fn set_voting_stake_params(origin, stake_weight_percent: u8, stake_per_point: BalanceOf<T>, unbonding_period: T::BlockNumber) {
+      ensure_root(origin)?;
+      ensure!(stake_weight_percent <= 100, MSG_STAKE_WEIGHT_PERCENT_IS_TOO_BIG);
+
+      <StakeWeightPercent<T>>::put(stake_weight_percent);
+      <StakePerPoint<T>>::put(stake_per_point);
+      <StakeUnbondingPeriod<T>>::put(unbonding_period);
+    }
+
+    pub fn set_curation_reward_percent(origin, percent: u8) {
+      ensure_root(origin)?;
+      ensure!(percent <= 100, MSG_CURATION_REWARD_PERCENT_IS_TOO_BIG);
+      <CurationRewardPercent<T>>::put(percent);
+    }
+
+    /// Adds newly issued funds to the reward pool.
+    pub fn mint_to_reward_pool(origin, amount: BalanceOf<T>) {
+      ensure_root(origin)?;
+      Self::add_to_reward_pool(amount);
+    }
+
+    pub fn set_max_pins(origin, max_pinned_posts_per_blog: u32, max_pinned_comments_per_post: u32) {
+      ensure_root(origin)?;
+      <MaxPinnedPostsPerBlog<T>>::put(max_pinned_posts_per_blog);
+      <MaxPinnedCommentsPerPost<T>>::put(max_pinned_comments_per_post);
+    }
+
+    pub fn set_max_edit_history_len(origin, max_len: u32) {
+      ensure_root(origin)?;
+      <MaxEditHistoryLen<T>>::put(max_len);
+    }
+
+    /// Notifications beyond a lowered limit are pruned on the next notification of each account.
+    pub fn set_max_notifications_per_account(origin, max_len: u32) {
+      ensure_root(origin)?;
+      <MaxNotificationsPerAccount<T>>::put(max_len);
+    }
+
+    /// Mentions beyond a lowered limit are pruned on the next mention of each account.
+    pub fn set_max_mentions_per_account(origin, max_len: u32) {
+      ensure_root(origin)?;
+      <MaxMentionsPerAccount<T>>::put(max_len);
+    }
+
+    /// Moves edit histories of profiles stored in the old layout to `ProfileHistory`.
+    /// The runtime upgrade migrates only the accounts it finds as owners, authors or followers,
+    /// as social accounts can not be enumerated. Usernames of the migrated accounts are indexed in lowercase too.
+    /// Accounts that are migrated already or have got their profiles after the upgrade are skipped.
+    pub fn migrate_profile_histories(origin, accounts: Vec<T::AccountId>) {
+      ensure_root(origin)?;
+      ensure!(Self::legacy_layout_upgraded_at().is_some(), MSG_LEGACY_LAYOUT_WAS_NOT_UPGRADED);
+
+      let migrated: Vec<T::AccountId> = accounts.into_iter()
+        .filter(|account| Self::migrate_profile_history(account.clone()))
+        .collect();
+      Self::migrate_username_keys(&migrated);
+    }
+
+    pub fn add_verifier(origin, verifier: T::AccountId) {
+      ensure_root(origin)?;
+      ensure!(!Self::is_verifier(verifier.clone()), MSG_ACCOUNT_IS_ALREADY_VERIFIER);
+
+      <IsVerifier<T>>::insert(verifier.clone(), true);
+      Self::deposit_event(RawEvent::VerifierAdded(verifier));
+    }
+
+    /// Attestations made by the verifier stay, but can be revoked with `force_revoke_attestation`.
+    pub fn remove_verifier(origin, verifier: T::AccountId) {
+      ensure_root(origin)?;
+      ensure!(Self::is_verifier(verifier.clone()), MSG_ACCOUNT_IS_NOT_VERIFIER);
+
+      <IsVerifier<T>>::remove(verifier.clone());
+      Self::deposit_event(RawEvent::VerifierRemoved(verifier));
+    }
+
+    pub fn force_revoke_attestation(origin, account: T::AccountId, index: u32) {
+      ensure_root(origin)?;
+      Self::revoke_attestation_at(account, index, None)?;
+    }
   }
 }
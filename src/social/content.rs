@@ -0,0 +1,218 @@
+use rstd::prelude::*;
+use parity_codec::{Decode, Input};
+use parity_codec_derive::Encode;
+
+/// Before `Content` was introduced, blogs, posts, comments and profiles stored a raw
+/// 46-byte IPFS hash (or an empty vector for shared posts). Its SCALE encoding starts with
+/// a compact length prefix, which lets us decode such legacy values as `Content`.
+const LEGACY_IPFS_HASH_LEN: usize = 46;
+const LEGACY_IPFS_HASH_PREFIX: u8 = (LEGACY_IPFS_HASH_LEN as u8) << 2;
+
+const BASE58_ALPHABET: &[u8] = b"123456789ABCDEFGHJKLMNPQRSTUVWXYZabcdefghijkmnopqrstuvwxyz";
+const BASE32_ALPHABET: &[u8] = b"abcdefghijklmnopqrstuvwxyz234567";
+const BASE16_ALPHABET: &[u8] = b"0123456789abcdef";
+const BASE64_URL_ALPHABET: &[u8] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789-_";
+
+const CID_V0_LEN: usize = 46;
+const CID_V1_VERSION: u64 = 1;
+
+/// Multicodecs of the content that can be addressed by CIDv1.
+const CID_CODECS: [u64; 5] = [
+  0x55,   // raw
+  0x70,   // dag-pb
+  0x71,   // dag-cbor
+  0x72,   // libp2p-key
+  0x0129, // dag-json
+];
+
+/// Supported multihash functions along with their digest lengths.
+const MULTIHASHES: [(u64, u64); 4] = [
+  (0x12, 32),   // sha2-256
+  (0x13, 64),   // sha2-512
+  (0x16, 32),   // sha3-256
+  (0xb220, 32), // blake2b-256
+];
+
+const SHA2_256: u64 = 0x12;
+const SHA2_256_LEN: u64 = 32;
+
+/// Hypercore public key is 32 bytes encoded as hex.
+const HYPER_HASH_LEN: usize = 64;
+/// Arweave transaction id is 32 bytes encoded as unpadded base64url.
+const ARWEAVE_HASH_LEN: usize = 43;
+
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize, Debug))]
+#[derive(Clone, Encode, PartialEq, Eq)]
+pub enum Content {
+  None,
+  Raw(Vec<u8>),
+  IPFS(Vec<u8>),
+  Hyper(Vec<u8>),
+  Arweave(Vec<u8>),
+}
+
+impl Default for Content {
+  fn default() -> Self {
+    Content::None
+  }
+}
+
+impl Decode for Content {
+  fn decode<I: Input>(input: &mut I) -> Option<Self> {
+    match input.read_byte()? {
+      0 => Some(Content::None),
+      1 => Some(Content::Raw(Decode::decode(input)?)),
+      2 => Some(Content::IPFS(Decode::decode(input)?)),
+      3 => Some(Content::Hyper(Decode::decode(input)?)),
+      4 => Some(Content::Arweave(Decode::decode(input)?)),
+      LEGACY_IPFS_HASH_PREFIX => {
+        let mut hash: Vec<u8> = Vec::new();
+        hash.resize(LEGACY_IPFS_HASH_LEN, 0);
+        if input.read(&mut hash) != LEGACY_IPFS_HASH_LEN {
+          return None;
+        }
+        Some(Content::IPFS(hash))
+      },
+      _ => None,
+    }
+  }
+}
+
+impl Content {
+  pub fn is_none(&self) -> bool {
+    *self == Content::None
+  }
+
+  /// Size in bytes of the content itself or of its identifier.
+  pub fn size(&self) -> usize {
+    match self {
+      Content::None => 0,
+      Content::Raw(bytes) | Content::IPFS(bytes) | Content::Hyper(bytes) | Content::Arweave(bytes) => bytes.len(),
+    }
+  }
+}
+
+/// Checks a textual CID: either a base58btc CIDv0 or a multibase-encoded CIDv1.
+pub fn is_ipfs_cid_valid(cid: &[u8]) -> bool {
+  if cid.len() == CID_V0_LEN && cid.starts_with(b"Qm") {
+    return base58_decode(cid).map_or(false, |multihash| {
+      multihash.len() == 2 + SHA2_256_LEN as usize
+        && multihash[0] as u64 == SHA2_256
+        && multihash[1] as u64 == SHA2_256_LEN
+    });
+  }
+
+  let (multibase, encoded) = match cid.split_first() {
+    Some(split) => split,
+    None => return false,
+  };
+  let decoded = match multibase {
+    b'b' => base32_decode(encoded),
+    b'z' => base58_decode(encoded),
+    b'f' => base16_decode(encoded),
+    _ => None,
+  };
+
+  decoded.map_or(false, |bytes| is_cid_v1_valid(&bytes))
+}
+
+/// Checks a hex-encoded Hypercore key.
+pub fn is_hyper_hash_valid(hash: &[u8]) -> bool {
+  hash.len() == HYPER_HASH_LEN && hash.iter().all(|c| BASE16_ALPHABET.contains(c))
+}
+
+/// Checks a base64url-encoded Arweave transaction id.
+pub fn is_arweave_hash_valid(hash: &[u8]) -> bool {
+  hash.len() == ARWEAVE_HASH_LEN && hash.iter().all(|c| BASE64_URL_ALPHABET.contains(c))
+}
+
+fn is_cid_v1_valid(bytes: &[u8]) -> bool {
+  let mut pos = 0;
+  if read_varint(bytes, &mut pos) != Some(CID_V1_VERSION) {
+    return false;
+  }
+  match read_varint(bytes, &mut pos) {
+    Some(codec) if CID_CODECS.contains(&codec) => {},
+    _ => return false,
+  }
+
+  let hash_code = match read_varint(bytes, &mut pos) {
+    Some(code) => code,
+    None => return false,
+  };
+  let digest_len = match read_varint(bytes, &mut pos) {
+    Some(len) => len,
+    None => return false,
+  };
+
+  MULTIHASHES.contains(&(hash_code, digest_len)) && (bytes.len() - pos) as u64 == digest_len
+}
+
+/// Reads an unsigned LEB128 varint, as used by multiformats.
+fn read_varint(bytes: &[u8], pos: &mut usize) -> Option<u64> {
+  let mut value: u64 = 0;
+  for shift in 0..9 {
+    let byte = *bytes.get(*pos)?;
+    *pos += 1;
+    value |= ((byte & 0x7f) as u64) << (shift * 7);
+    if byte & 0x80 == 0 {
+      return Some(value);
+    }
+  }
+  None
+}
+
+fn alphabet_index(alphabet: &[u8], c: u8) -> Option<u32> {
+  alphabet.iter().position(|&a| a == c).map(|i| i as u32)
+}
+
+fn base58_decode(input: &[u8]) -> Option<Vec<u8>> {
+  // Little-endian while decoding, reversed at the end.
+  let mut bytes: Vec<u8> = Vec::with_capacity(input.len());
+  for &c in input {
+    let mut carry = alphabet_index(BASE58_ALPHABET, c)?;
+    for byte in bytes.iter_mut() {
+      carry += (*byte as u32) * 58;
+      *byte = carry as u8;
+      carry >>= 8;
+    }
+    while carry > 0 {
+      bytes.push(carry as u8);
+      carry >>= 8;
+    }
+  }
+  for _ in input.iter().take_while(|&&c| c == BASE58_ALPHABET[0]) {
+    bytes.push(0);
+  }
+  bytes.reverse();
+  Some(bytes)
+}
+
+fn base32_decode(input: &[u8]) -> Option<Vec<u8>> {
+  let mut bytes: Vec<u8> = Vec::with_capacity(input.len() * 5 / 8);
+  let mut buffer: u32 = 0;
+  let mut bits = 0;
+  for &c in input {
+    buffer = (buffer << 5) | alphabet_index(BASE32_ALPHABET, c)?;
+    bits += 5;
+    if bits >= 8 {
+      bits -= 8;
+      bytes.push((buffer >> bits) as u8);
+      buffer &= (1 << bits) - 1;
+    }
+  }
+  // Trailing bits are padding and must be zero.
+  if buffer != 0 {
+    return None;
+  }
+  Some(bytes)
+}
+
+fn base16_decode(input: &[u8]) -> Option<Vec<u8>> {
+  if input.len() % 2 != 0 {
+    return None;
+  }
+  input.chunks(2)
+    .map(|pair| Some((alphabet_index(BASE16_ALPHABET, pair[0])? << 4 | alphabet_index(BASE16_ALPHABET, pair[1])?) as u8))
+    .collect()
+}
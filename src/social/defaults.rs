@@ -1,15 +1,57 @@
-pub const DEFAULT_IPFS_HASH_LEN: u32 = 46;
-
 pub const DEFAULT_SLUG_MIN_LEN: u32 = 5;
 pub const DEFAULT_SLUG_MAX_LEN: u32 = 50;
+// A week of 6-second blocks:
+pub const DEFAULT_SLUG_REDIRECT_PERIOD: u64 = 100_800;
+
+pub const DEFAULT_TAG_MAX_LEN: u32 = 32;
+pub const DEFAULT_MAX_TAGS_PER_POST: u32 = 10;
+// Tags trend by their usage within a day of 6-second blocks, counted by the hour:
+pub const DEFAULT_TAG_USAGE_BUCKET_LEN: u64 = 600;
+pub const DEFAULT_TAG_USAGE_BUCKETS_COUNT: u32 = 24;
+pub const DEFAULT_TAG_PRUNING_CHUNK_LEN: u32 = 100;
+
+// Rewards are paid out weekly, given 6-second blocks:
+pub const DEFAULT_REWARD_EPOCH_LENGTH: u64 = 100_800;
+pub const DEFAULT_REWARD_DISTRIBUTION_CHUNK_LEN: u32 = 100;
+pub const DEFAULT_LEGACY_MIGRATION_CHUNK_LEN: u32 = 100;
+pub const DEFAULT_CURATION_REWARD_PERCENT: u8 = 25;
+pub const DEFAULT_MAX_CURATORS_PER_POST: u32 = 100;
+
+// Stake does not count by default, so scores depend on reputation only:
+pub const DEFAULT_STAKE_WEIGHT_PERCENT: u8 = 0;
+// A week of 6-second blocks:
+pub const DEFAULT_STAKE_UNBONDING_PERIOD: u64 = 100_800;
+
+pub const DEFAULT_MAX_MENTIONS: u32 = 10;
+pub const DEFAULT_MAX_MENTIONS_PER_ACCOUNT: u32 = 100;
+
+pub const DEFAULT_MAX_BOOKMARKS_PER_ACCOUNT: u32 = 1_000;
+pub const DEFAULT_MAX_COLLECTIONS_PER_ACCOUNT: u32 = 20;
+pub const DEFAULT_MAX_ITEMS_PER_COLLECTION: u32 = 200;
+pub const DEFAULT_COLLECTION_NAME_MAX_LEN: u32 = 64;
+pub const DEFAULT_MAX_QUOTE_DEPTH: u32 = 5;
+pub const DEFAULT_MAX_CROSS_POSTS_PER_POST: u32 = 10;
+pub const DEFAULT_MAX_CO_AUTHORS_PER_POST: u32 = 5;
+pub const DEFAULT_MAX_PINNED_POSTS_PER_BLOG: u32 = 3;
+pub const DEFAULT_MAX_PINNED_COMMENTS_PER_POST: u32 = 3;
+
+pub const DEFAULT_POLL_MAX_OPTIONS: u32 = 10;
+pub const DEFAULT_POLL_OPTION_MAX_LEN: u32 = 100;
 
 pub const DEFAULT_USERNAME_MIN_LEN: u32 = 3;
 pub const DEFAULT_USERNAME_MAX_LEN: u32 = 50;
+// A month of 6-second blocks:
+pub const DEFAULT_USERNAME_RELEASE_COOLDOWN: u64 = 432_000;
+// A year of 6-second blocks:
+pub const DEFAULT_USERNAME_REGISTRATION_PERIOD: u64 = 5_256_000;
 
 pub const DEFAULT_BLOG_MAX_LEN: u32 = 1_000;
 pub const DEFAULT_POST_MAX_LEN: u32 = 10_000;
 pub const DEFAULT_COMMENT_MAX_LEN: u32 = 1_000;
 
+pub const DEFAULT_MAX_EDIT_HISTORY_LEN: u32 = 100;
+pub const DEFAULT_MAX_NOTIFICATIONS_PER_ACCOUNT: u32 = 100;
+
 pub const DEFAULT_FOLLOW_BLOG_ACTION_WEIGHT: i16 = 7;
 pub const DEFAULT_FOLLOW_ACCOUNT_ACTION_WEIGHT: i16 = 3;
 
@@ -21,3 +63,7 @@ pub const DEFAULT_CREATE_COMMENT_ACTION_WEIGHT: i16 = 5;
 pub const DEFAULT_UPVOTE_COMMENT_ACTION_WEIGHT: i16 = 4;
 pub const DEFAULT_DOWNVOTE_COMMENT_ACTION_WEIGHT: i16 = -2;
 pub const DEFAULT_SHARE_COMMENT_ACTION_WEIGHT: i16 = 3;
+
+pub const DEFAULT_FEE_DISCOUNT_REPUTATION: u32 = 100;
+pub const DEFAULT_FEE_DISCOUNT_PERCENT: u8 = 50;
+pub const DEFAULT_FEE_EXEMPT_REPUTATION: u32 = 1_000;
@@ -1,9 +1,14 @@
 use super::blogs::*;
 use super::messages::*;
+use super::content::{self, Content};
+
+use crate::currency::BalanceOf;
 
 use rstd::prelude::*;
+use parity_codec::Codec;
 use srml_support::{StorageMap, StorageValue, dispatch::Result, ensure};
-use runtime_primitives::traits::{As};
+use srml_support::traits::{Currency, Imbalance, LockableCurrency, ExistenceRequirement, WithdrawReason, WithdrawReasons};
+use runtime_primitives::traits::{As, Zero, Saturating, Bounded, CheckedAdd};
 use system::{self};
 use {timestamp};
 
@@ -14,6 +19,18 @@ impl<T: Trait> Module<T> {
     Ok(())
   }
 
+  pub fn ensure_post_is_public(post: &Post<T>) -> Result {
+    ensure!(post.visibility == PostVisibility::Public, MSG_POST_IS_NOT_PUBLIC);
+    Ok(())
+  }
+
+  /// A draft is visible only to its author and to the owner and writers of its blog.
+  pub fn is_post_visible_to(post: &Post<T>, account: &T::AccountId) -> bool {
+    post.visibility != PostVisibility::Draft
+      || post.created.account == *account
+      || Self::blog_by_id(post.blog_id).map_or(false, |blog| blog.created.account == *account || blog.writers.contains(account))
+  }
+
   pub fn new_change(account: T::AccountId) -> Change<T> {
     Change {
       account,
@@ -38,10 +55,13 @@ impl<T: Trait> Module<T> {
     reaction_id
   }
 
+  /// Charges the fee of the paid action once the follow is checked, as storage changes of a failed call are not reverted.
   pub fn add_blog_follower_and_insert_blog(
     follower: T::AccountId,
-    blog: &mut Blog<T>,
-    is_new_blog: bool
+    blog: &Blog<T>,
+    is_new_blog: bool,
+    paid_action: PaidAction,
+    payload_len: usize
   ) -> Result {
 
     let blog_id = blog.id;
@@ -50,15 +70,26 @@ impl<T: Trait> Module<T> {
       .checked_add(1)
       .ok_or(MSG_OVERFLOW_FOLLOWING_BLOG)?;
 
-    blog.followers_count = blog.followers_count.checked_add(1).ok_or(MSG_OVERFLOW_FOLLOWING_BLOG)?;
+    let mut blog_counters = Self::blog_counters(blog_id);
+    blog_counters.followers_count = blog_counters.followers_count.checked_add(1).ok_or(MSG_OVERFLOW_FOLLOWING_BLOG)?;
+    let mut author_reputation = None;
     if blog.created.account != follower {
       let author = blog.created.account.clone();
-      let score_diff = Self::get_score_diff(social_account.reputation, ScoringAction::FollowBlog);
-      blog.score = blog.score.checked_add(score_diff as i32).ok_or(MSG_OUT_OF_BOUNDS_UPDATING_BLOG_SCORE)?;
-      Self::change_social_account_reputation(author.clone(), follower.clone(), score_diff, ScoringAction::FollowBlog)?;
+      let score_diff = Self::get_account_score_diff(&follower, social_account.reputation, ScoringAction::FollowBlog);
+      blog_counters.score = blog_counters.score.checked_add(score_diff as i32).ok_or(MSG_OUT_OF_BOUNDS_UPDATING_BLOG_SCORE)?;
+      let reputation = Self::get_or_new_social_account(author.clone()).reputation;
+      author_reputation = Some((author, Self::reputation_after(reputation, score_diff)?));
     }
+    Self::charge_fee(&follower, paid_action, payload_len)?;
 
-    <BlogById<T>>::insert(blog_id, blog);
+    if let Some((author, (reputation, score_diff))) = author_reputation {
+      Self::record_reputation_change(author, follower.clone(), reputation, score_diff, ScoringAction::FollowBlog);
+    }
+    // Following an existing blog touches only its counters:
+    if is_new_blog {
+      <BlogById<T>>::insert(blog_id, blog);
+    }
+    <BlogCountersById<T>>::insert(blog_id, blog_counters);
     <SocialAccountById<T>>::insert(follower.clone(), social_account.clone());
     <BlogsFollowedByAccount<T>>::mutate(follower.clone(), |ids| ids.push(blog_id));
     <BlogFollowers<T>>::mutate(blog_id, |ids| ids.push(follower.clone()));
@@ -93,20 +124,31 @@ impl<T: Trait> Module<T> {
     }
   }
 
-  pub fn change_post_score(account: T::AccountId, post: &mut Post<T>, action: ScoringAction) -> Result {
+  pub fn change_post_score(account: T::AccountId, post: &Post<T>, action: ScoringAction) -> Result {
     let social_account = Self::get_or_new_social_account(account.clone());
     <SocialAccountById<T>>::insert(account.clone(), social_account.clone());
 
     let post_id = post.id;
-    let mut blog = Self::blog_by_id(post.blog_id).ok_or(MSG_BLOG_NOT_FOUND)?;
+    Self::ensure_blog_exists(post.blog_id)?;
     
     if post.created.account != account {
       if let Some(score_diff) = Self::post_score_by_account((account.clone(), post_id, action)) {
-        let reputation_diff = Self::account_reputation_diff_by_account((account.clone(), post.created.account.clone(), action)).ok_or(MSG_REPUTATION_DIFF_NOT_FOUND)?;
-        post.score = post.score.checked_add(score_diff as i32 * -1).ok_or(MSG_OUT_OF_BOUNDS_REVERTING_POST_SCORE)?;
-        blog.score = blog.score.checked_add(score_diff as i32 * -1).ok_or(MSG_OUT_OF_BOUNDS_REVERTING_BLOG_SCORE)?;
-        Self::change_social_account_reputation(post.created.account.clone(), account.clone(), reputation_diff * -1, action)?;
+        ensure!(
+          Self::post_reputation_diff_by_account((account.clone(), post_id, action, post.created.account.clone())).is_some(),
+          MSG_REPUTATION_DIFF_NOT_FOUND
+        );
+        let mut post_counters = Self::post_counters(post_id);
+        post_counters.score = post_counters.score.checked_add(score_diff as i32 * -1).ok_or(MSG_OUT_OF_BOUNDS_REVERTING_POST_SCORE)?;
+        let blog_scores = Self::post_blog_scores_after(post, score_diff as i32 * -1, MSG_OUT_OF_BOUNDS_REVERTING_BLOG_SCORE)?;
+        Self::revert_post_authors_reputation(post, account.clone(), action)?;
         <PostScoreByAccount<T>>::remove((account.clone(), post_id, action));
+        <PostCountersById<T>>::insert(post_id, post_counters);
+        Self::insert_post_blog_scores(post_id, blog_scores);
+        // A reversed upvote earns no more curation:
+        if action == ScoringAction::UpvotePost {
+          <PostUpvoters<T>>::mutate(post_id, |upvoters| upvoters.retain(|(upvoter, _)| *upvoter != account));
+        }
+        Self::revert_post_reward_score(post, account.clone(), action, score_diff as i32);
       } else {
         match action {
           ScoringAction::UpvotePost => {
@@ -121,21 +163,108 @@ impl<T: Trait> Module<T> {
           },
           _ => (),
         }
-        let score_diff = Self::get_score_diff(social_account.reputation, action);
-        post.score = post.score.checked_add(score_diff as i32).ok_or(MSG_OUT_OF_BOUNDS_UPDATING_POST_SCORE)?;
-        blog.score = blog.score.checked_add(score_diff as i32).ok_or(MSG_OUT_OF_BOUNDS_UPDATING_BLOG_SCORE)?;
-        Self::change_social_account_reputation(post.created.account.clone(), account.clone(), score_diff, action)?;
+        // Counters are read after the opposite action is reverted, as it writes them too:
+        let score_diff = Self::get_account_score_diff(&account, social_account.reputation, action);
+        let mut post_counters = Self::post_counters(post_id);
+        post_counters.score = post_counters.score.checked_add(score_diff as i32).ok_or(MSG_OUT_OF_BOUNDS_UPDATING_POST_SCORE)?;
+        let blog_scores = Self::post_blog_scores_after(post, score_diff as i32, MSG_OUT_OF_BOUNDS_UPDATING_BLOG_SCORE)?;
+        Self::change_post_authors_reputation(post, account.clone(), score_diff, action)?;
         <PostScoreByAccount<T>>::insert((account.clone(), post_id, action), score_diff);
+        <PostCountersById<T>>::insert(post_id, post_counters);
+        Self::insert_post_blog_scores(post_id, blog_scores);
+        // Upvoters are curated by the score gained after their vote:
+        let curation = Self::add_post_reward_score(post, score_diff as i32);
+        if !curation.is_empty() {
+          <PostCurationByAccount<T>>::insert((account.clone(), post_id, action), curation);
+        }
+        if action == ScoringAction::UpvotePost && Self::post_upvoters(post_id).len() < Self::max_curators_per_post() as usize {
+          <PostUpvoters<T>>::mutate(post_id, |upvoters| upvoters.push((account.clone(), social_account.reputation)));
+        }
       }
+    }
+
+    Ok(())
+  }
 
-      <PostById<T>>::insert(post_id, post.clone());
-      <BlogById<T>>::insert(post.blog_id, blog.clone());
+  /// Posts that are not public can be bookmarked only by their authors.
+  pub fn ensure_bookmark_exists(account: &T::AccountId, item: &Bookmark<T>) -> Result {
+    match item {
+      Bookmark::Post(post_id) => {
+        let post = Self::post_by_id(*post_id).ok_or(MSG_POST_NOT_FOUND)?;
+        let is_author = post.created.account == *account
+          || Self::co_authors(post.id).iter().any(|(co_author, _)| co_author == account);
+        if !is_author {
+          Self::ensure_post_is_public(&post)?;
+        }
+      },
+      Bookmark::Comment(comment_id) => {
+        ensure!(<CommentById<T>>::exists(comment_id), MSG_COMMENT_NOT_FOUND);
+      },
     }
+    Ok(())
+  }
 
+  /// Names are unique among collections of the same account.
+  pub fn ensure_collection_name_is_valid(collections: &[Collection<T>], name: &[u8]) -> Result {
+    ensure!(!name.is_empty(), MSG_COLLECTION_NAME_IS_EMPTY);
+    ensure!(name.len() <= Self::collection_name_max_len() as usize, MSG_COLLECTION_NAME_IS_TOO_LONG);
+    ensure!(core::str::from_utf8(name).is_ok(), MSG_COLLECTION_NAME_IS_NOT_UTF8);
+    ensure!(collections.iter().all(|collection| collection.name != name), MSG_COLLECTION_NAME_IS_TAKEN);
     Ok(())
   }
 
-  pub fn change_comment_score(account: T::AccountId, comment: &mut Comment<T>, action: ScoringAction) -> Result {
+  pub fn collection_index(collections: &[Collection<T>], name: &[u8]) -> rstd::result::Result<usize, &'static str> {
+    collections.iter().position(|collection| collection.name == name).ok_or(MSG_COLLECTION_NOT_FOUND)
+  }
+
+  /// Ids of posts listed in the blog, with the pinned ones first.
+  pub fn blog_post_ids(blog_id: T::BlogId) -> Vec<T::PostId> {
+    let mut post_ids = Self::pinned_post_ids(blog_id);
+    let not_pinned: Vec<T::PostId> = Self::post_ids_by_blog_id(blog_id).into_iter()
+      .filter(|id| !post_ids.contains(id))
+      .collect();
+    post_ids.extend(not_pinned);
+    post_ids
+  }
+
+  /// Ids of comments on the post, with the pinned ones first.
+  pub fn post_comment_ids(post_id: T::PostId) -> Vec<T::CommentId> {
+    let mut comment_ids = Self::pinned_comment_ids(post_id);
+    let not_pinned: Vec<T::CommentId> = Self::comment_ids_by_post_id(post_id).into_iter()
+      .filter(|id| !comment_ids.contains(id))
+      .collect();
+    comment_ids.extend(not_pinned);
+    comment_ids
+  }
+
+  /// The post's own blog and the blogs it is cross-posted to.
+  pub fn post_blog_ids(post: &Post<T>) -> Vec<T::BlogId> {
+    let mut blog_ids = vec![post.blog_id];
+    blog_ids.extend(Self::cross_post_blog_ids(post.id));
+    blog_ids
+  }
+
+  /// Every blog that lists the post gets the score diff, and keeps its own part of the post's score.
+  /// Returns the new counters of these blogs along with the post's scores in them, without writing them.
+  fn post_blog_scores_after(post: &Post<T>, diff: i32, error: &'static str) -> rstd::result::Result<Vec<(T::BlogId, BlogCounters, i32)>, &'static str> {
+    let mut blog_scores = Vec::new();
+    for blog_id in Self::post_blog_ids(post) {
+      let mut blog_counters = Self::blog_counters(blog_id);
+      blog_counters.score = blog_counters.score.checked_add(diff).ok_or(error)?;
+      let score_in_blog = Self::post_score_in_blog((blog_id, post.id)).checked_add(diff).ok_or(error)?;
+      blog_scores.push((blog_id, blog_counters, score_in_blog));
+    }
+    Ok(blog_scores)
+  }
+
+  fn insert_post_blog_scores(post_id: T::PostId, blog_scores: Vec<(T::BlogId, BlogCounters, i32)>) {
+    for (blog_id, blog_counters, score_in_blog) in blog_scores {
+      <BlogCountersById<T>>::insert(blog_id, blog_counters);
+      <PostScoreInBlog<T>>::insert((blog_id, post_id), score_in_blog);
+    }
+  }
+
+  pub fn change_comment_score(account: T::AccountId, comment: &Comment<T>, action: ScoringAction) -> Result {
     let social_account = Self::get_or_new_social_account(account.clone());
     <SocialAccountById<T>>::insert(account.clone(), social_account.clone());
 
@@ -144,9 +273,12 @@ impl<T: Trait> Module<T> {
     if comment.created.account != account {
       if let Some(score_diff) = Self::comment_score_by_account((account.clone(), comment_id, action)) {
         let reputation_diff = Self::account_reputation_diff_by_account((account.clone(), comment.created.account.clone(), action)).ok_or(MSG_REPUTATION_DIFF_NOT_FOUND)?;
-        comment.score = comment.score.checked_add(score_diff as i32 * -1).ok_or(MSG_OUT_OF_BOUNDS_REVERTING_COMMENT_SCORE)?;
+        let mut comment_counters = Self::comment_counters(comment_id);
+        comment_counters.score = comment_counters.score.checked_add(score_diff as i32 * -1).ok_or(MSG_OUT_OF_BOUNDS_REVERTING_COMMENT_SCORE)?;
         Self::change_social_account_reputation(comment.created.account.clone(), account.clone(), reputation_diff * -1, action)?;
         <CommentScoreByAccount<T>>::remove((account.clone(), comment_id, action));
+        <CommentCountersById<T>>::insert(comment_id, comment_counters);
+        Self::add_epoch_score(comment.created.account.clone(), score_diff as i32 * -1);
       } else {
         match action {
           ScoringAction::UpvoteComment => {
@@ -160,55 +292,429 @@ impl<T: Trait> Module<T> {
             }
           },
           ScoringAction::CreateComment => {
-            let ref mut post = Self::post_by_id(comment.post_id).ok_or(MSG_POST_NOT_FOUND)?;
+            let ref post = Self::post_by_id(comment.post_id).ok_or(MSG_POST_NOT_FOUND)?;
             Self::change_post_score(account.clone(), post, action)?;
           }
           _ => (),
         }
-        let score_diff = Self::get_score_diff(social_account.reputation, action);
-        comment.score = comment.score.checked_add(score_diff as i32).ok_or(MSG_OUT_OF_BOUNDS_UPDATING_COMMENT_SCORE)?;
+        let score_diff = Self::get_account_score_diff(&account, social_account.reputation, action);
+        let mut comment_counters = Self::comment_counters(comment_id);
+        comment_counters.score = comment_counters.score.checked_add(score_diff as i32).ok_or(MSG_OUT_OF_BOUNDS_UPDATING_COMMENT_SCORE)?;
         Self::change_social_account_reputation(comment.created.account.clone(), account.clone(), score_diff, action)?;
         <CommentScoreByAccount<T>>::insert((account, comment_id, action), score_diff);
+        <CommentCountersById<T>>::insert(comment_id, comment_counters);
+        Self::add_epoch_score(comment.created.account.clone(), score_diff as i32);
       }
-      <CommentById<T>>::insert(comment_id, comment.clone());
     }
 
     Ok(())
   }
 
-  pub fn change_social_account_reputation(account: T::AccountId, scorer: T::AccountId, mut score_diff: i16, action: ScoringAction) -> Result {
-    let mut social_account = Self::get_or_new_social_account(account.clone());
+  /// Checks what `change_post_score` checks, including the reverted opposite vote, without changing storage,
+  /// so that a fee can be charged before the score is changed.
+  pub fn ensure_post_score_can_change(account: &T::AccountId, post: &Post<T>, action: ScoringAction) -> Result {
+    Self::ensure_blog_exists(post.blog_id)?;
+    if post.created.account == *account {
+      return Ok(());
+    }
+
+    let is_reverted = Self::post_score_by_account((account.clone(), post.id, action)).is_some();
+    let reverted_action = match action {
+      _ if is_reverted => Some(action),
+      ScoringAction::UpvotePost => Some(ScoringAction::DownvotePost),
+      ScoringAction::DownvotePost => Some(ScoringAction::UpvotePost),
+      _ => None,
+    };
+
+    let mut score = Self::post_counters(post.id).score;
+    let mut blog_diff = 0i32;
+    let mut reputations = Vec::new();
+    if let Some(reverted_action) = reverted_action {
+      if let Some(score_diff) = Self::post_score_by_account((account.clone(), post.id, reverted_action)) {
+        ensure!(
+          Self::post_reputation_diff_by_account((account.clone(), post.id, reverted_action, post.created.account.clone())).is_some(),
+          MSG_REPUTATION_DIFF_NOT_FOUND
+        );
+        score = score.checked_add(score_diff as i32 * -1).ok_or(MSG_OUT_OF_BOUNDS_REVERTING_POST_SCORE)?;
+        blog_diff -= score_diff as i32;
+        Self::post_blog_scores_after(post, blog_diff, MSG_OUT_OF_BOUNDS_REVERTING_BLOG_SCORE)?;
 
-    if social_account.reputation as i64 + score_diff as i64 <= 1 {
-      social_account.reputation = 1;
-      score_diff = 0;
+        let co_authors = Self::co_authors(post.id).into_iter().map(|(co_author, _)| co_author);
+        for author in co_authors.chain(Some(post.created.account.clone())) {
+          if let Some(reputation_diff) = Self::post_reputation_diff_by_account((account.clone(), post.id, reverted_action, author.clone())) {
+            Self::simulate_reputation_diff(&mut reputations, author, reputation_diff * -1)?;
+          }
+        }
+      }
     }
 
-    if score_diff < 0 {
-      social_account.reputation = social_account.reputation.checked_sub((score_diff * -1) as u32).ok_or(MSG_OUT_OF_BOUNDS_UPDATING_ACCOUNT_REPUTATION)?;
-    } else {
-      social_account.reputation = social_account.reputation.checked_add(score_diff as u32).ok_or(MSG_OUT_OF_BOUNDS_UPDATING_ACCOUNT_REPUTATION)?;
+    if !is_reverted {
+      let reputation = Self::get_or_new_social_account(account.clone()).reputation;
+      let score_diff = Self::get_account_score_diff(account, reputation, action);
+      score.checked_add(score_diff as i32).ok_or(MSG_OUT_OF_BOUNDS_UPDATING_POST_SCORE)?;
+      Self::post_blog_scores_after(post, blog_diff + score_diff as i32, MSG_OUT_OF_BOUNDS_UPDATING_BLOG_SCORE)?;
+      for (author, author_diff) in Self::post_authors_diffs(post, account, score_diff) {
+        Self::simulate_reputation_diff(&mut reputations, author, author_diff)?;
+      }
     }
-    
+
+    Ok(())
+  }
+
+  /// Checks what `change_comment_score` checks, including the reverted opposite vote, without changing storage.
+  pub fn ensure_comment_score_can_change(account: &T::AccountId, comment: &Comment<T>, action: ScoringAction) -> Result {
+    if comment.created.account == *account {
+      return Ok(());
+    }
+
+    let is_reverted = Self::comment_score_by_account((account.clone(), comment.id, action)).is_some();
+    let reverted_action = match action {
+      _ if is_reverted => Some(action),
+      ScoringAction::UpvoteComment => Some(ScoringAction::DownvoteComment),
+      ScoringAction::DownvoteComment => Some(ScoringAction::UpvoteComment),
+      _ => None,
+    };
+
+    let author = comment.created.account.clone();
+    let mut score = Self::comment_counters(comment.id).score;
+    let mut reputation = Self::get_or_new_social_account(author.clone()).reputation;
+    if let Some(reverted_action) = reverted_action {
+      if let Some(score_diff) = Self::comment_score_by_account((account.clone(), comment.id, reverted_action)) {
+        let reputation_diff = Self::account_reputation_diff_by_account((account.clone(), author.clone(), reverted_action))
+          .ok_or(MSG_REPUTATION_DIFF_NOT_FOUND)?;
+        score = score.checked_add(score_diff as i32 * -1).ok_or(MSG_OUT_OF_BOUNDS_REVERTING_COMMENT_SCORE)?;
+        reputation = Self::reputation_after(reputation, reputation_diff * -1)?.0;
+      }
+    }
+
+    if !is_reverted {
+      if action == ScoringAction::CreateComment {
+        let ref post = Self::post_by_id(comment.post_id).ok_or(MSG_POST_NOT_FOUND)?;
+        Self::ensure_post_score_can_change(account, post, action)?;
+      }
+      let scorer_reputation = Self::get_or_new_social_account(account.clone()).reputation;
+      let score_diff = Self::get_account_score_diff(account, scorer_reputation, action);
+      score.checked_add(score_diff as i32).ok_or(MSG_OUT_OF_BOUNDS_UPDATING_COMMENT_SCORE)?;
+      Self::reputation_after(reputation, score_diff)?;
+    }
+
+    Ok(())
+  }
+
+  /// Splits a reputation change among the post creator and its accepted co-authors by their shares.
+  /// The creator gets the rounding remainder and the share of a co-author who scores the post.
+  pub fn change_post_authors_reputation(post: &Post<T>, scorer: T::AccountId, score_diff: i16, action: ScoringAction) -> Result {
+    for (author, author_diff) in Self::post_authors_diffs(post, &scorer, score_diff) {
+      Self::change_post_author_reputation(post.id, author, scorer.clone(), author_diff, action)?;
+    }
+    Ok(())
+  }
+
+  fn post_authors_diffs(post: &Post<T>, scorer: &T::AccountId, score_diff: i16) -> Vec<(T::AccountId, i16)> {
+    let mut diffs = Vec::new();
+    let mut creator_diff = score_diff;
+    for (co_author, share) in Self::co_authors(post.id) {
+      if co_author == *scorer {
+        continue;
+      }
+      let co_author_diff = (score_diff as i32 * share as i32 / 100) as i16;
+      creator_diff -= co_author_diff;
+      diffs.push((co_author, co_author_diff));
+    }
+    diffs.push((post.created.account.clone(), creator_diff));
+    diffs
+  }
+
+  fn change_post_author_reputation(post_id: T::PostId, author: T::AccountId, scorer: T::AccountId, score_diff: i16, action: ScoringAction) -> Result {
+    let reputation_diff = Self::apply_reputation_diff(author.clone(), score_diff, action)?;
+    <PostReputationDiffByAccount<T>>::insert((scorer, post_id, action, author), reputation_diff);
+    Ok(())
+  }
+
+  /// Reverts exactly the parts the authors have got from the scorer on this post.
+  /// Co-authors who accepted after the post was scored have got no part of the score.
+  pub fn revert_post_authors_reputation(post: &Post<T>, scorer: T::AccountId, action: ScoringAction) -> Result {
+    let co_authors = Self::co_authors(post.id).into_iter().map(|(co_author, _)| co_author);
+    for author in co_authors.chain(Some(post.created.account.clone())) {
+      let key = (scorer.clone(), post.id, action, author.clone());
+      if let Some(reputation_diff) = Self::post_reputation_diff_by_account(key.clone()) {
+        Self::apply_reputation_diff(author, reputation_diff * -1, action)?;
+        <PostReputationDiffByAccount<T>>::remove(key);
+      }
+    }
+    Ok(())
+  }
+
+  /// Withdraws the amount at once, so that nothing is paid out if the payer can not afford it.
+  /// A part too small to open an account goes to the creator, or back to the payer if the creator's
+  /// part can not open one either, so that nothing is burnt.
+  pub fn pay_post_authors(post: &Post<T>, payer: &T::AccountId, amount: BalanceOf<T>) -> Result {
+    Self::withdraw_transfer(payer, amount)?;
+
+    let mut creator_part = amount;
+    for (co_author, share) in Self::co_authors(post.id) {
+      // Divided before multiplying, so that big amounts are not saturated:
+      let hundred = BalanceOf::<T>::sa(100);
+      let share = BalanceOf::<T>::sa(share as u64);
+      let part = amount / hundred * share + amount % hundred * share / hundred;
+      if !T::Currency::deposit_creating(&co_author, part).peek().is_zero() {
+        creator_part = creator_part.saturating_sub(part);
+      }
+    }
+    if T::Currency::deposit_creating(&post.created.account, creator_part).peek().is_zero() {
+      let _ = T::Currency::deposit_into_existing(payer, creator_part);
+    }
+
+    Ok(())
+  }
+
+  /// The withdrawn amount is burnt, so it has to be deposited elsewhere.
+  pub fn withdraw_transfer(account: &T::AccountId, amount: BalanceOf<T>) -> Result {
+    let _ = T::Currency::withdraw(account, amount, WithdrawReason::Transfer, ExistenceRequirement::KeepAlive)?;
+    Ok(())
+  }
+
+  pub fn add_to_reward_pool(amount: BalanceOf<T>) {
+    <RewardPool<T>>::mutate(|pool| *pool = pool.saturating_add(amount));
+  }
+
+  /// Records the score an author has gained or lost within the current reward epoch.
+  pub fn add_epoch_score(account: T::AccountId, score_diff: i32) {
+    let epoch = Self::reward_epoch();
+    let key = (epoch, account.clone());
+    if !<EpochScoreByAccount<T>>::exists(key.clone()) {
+      let count = Self::epoch_scored_accounts_count(epoch);
+      <EpochScoredAccount<T>>::insert((epoch, count), account);
+      <EpochScoredAccountsCount<T>>::insert(epoch, count + 1);
+    }
+
+    let old_score = Self::epoch_score_by_account(key.clone());
+    let new_score = old_score.saturating_add(score_diff);
+    <EpochScoreByAccount<T>>::insert(key, new_score);
+    <EpochPositiveScore<T>>::mutate(epoch, |total| {
+      *total = total.saturating_sub(old_score.max(0) as u64).saturating_add(new_score.max(0) as u64)
+    });
+  }
+
+  /// Splits the score of a post among its creator and accepted co-authors by their shares.
+  pub fn add_post_epoch_score(post: &Post<T>, score_diff: i32) {
+    let mut creator_diff = score_diff;
+    for (co_author, share) in Self::co_authors(post.id) {
+      let co_author_diff = score_diff * share as i32 / 100;
+      creator_diff -= co_author_diff;
+      Self::add_epoch_score(co_author, co_author_diff);
+    }
+    Self::add_epoch_score(post.created.account.clone(), creator_diff);
+  }
+
+  /// Splits the curation part of a post score among its upvoters, weighted by their reputation
+  /// and by how early they voted. The authors get the rest. Returns the curation of each upvoter.
+  pub fn add_post_reward_score(post: &Post<T>, score_diff: i32) -> Vec<(T::AccountId, i32)> {
+    let upvoters = Self::post_upvoters(post.id);
+    let count = upvoters.len() as u64;
+    let weights: Vec<u64> = upvoters.iter().enumerate()
+      .map(|(position, (_, reputation))| *reputation as u64 * (count - position as u64))
+      .collect();
+    let total_weight: u64 = weights.iter().sum();
+
+    let mut curation = Vec::new();
+    let mut authors_diff = score_diff;
+    if total_weight > 0 {
+      let curation_diff = score_diff * Self::curation_reward_percent() as i32 / 100;
+      for ((curator, _), weight) in upvoters.into_iter().zip(weights) {
+        let curator_diff = (curation_diff as i64 * weight as i64 / total_weight as i64) as i32;
+        if curator_diff != 0 {
+          authors_diff -= curator_diff;
+          Self::add_epoch_score(curator.clone(), curator_diff);
+          curation.push((curator, curator_diff));
+        }
+      }
+    }
+    Self::add_post_epoch_score(post, authors_diff);
+    curation
+  }
+
+  /// Takes back exactly the curation a scoring action has paid to each upvoter,
+  /// whether or not they are still upvoters. The authors lose the rest.
+  pub fn revert_post_reward_score(post: &Post<T>, scorer: T::AccountId, action: ScoringAction, score_diff: i32) {
+    let mut authors_diff = score_diff;
+    for (curator, curator_diff) in <PostCurationByAccount<T>>::take((scorer, post.id, action)) {
+      authors_diff -= curator_diff;
+      Self::add_epoch_score(curator, curator_diff * -1);
+    }
+    Self::add_post_epoch_score(post, authors_diff * -1);
+  }
+
+  /// Starts paying out the pool for the ended epoch. If no score was gained, the pool is kept for the next one.
+  pub fn end_reward_epoch(now: T::BlockNumber) {
+    let epoch = Self::reward_epoch();
+    let amount = if Self::epoch_positive_score(epoch) > 0 { <RewardPool<T>>::take() } else { Zero::zero() };
+
+    <RewardEpoch<T>>::put(epoch + 1);
+    <RewardEpochStartedAt<T>>::put(now);
+    <PendingRewardDistribution<T>>::put(RewardDistribution { epoch, amount, paid: Zero::zero(), next_index: 0 });
+
+    Self::deposit_event(RawEvent::RewardEpochEnded(epoch));
+  }
+
+  /// Pays out to the next chunk of authors and clears their epoch scores.
+  /// The rounding remainder goes back to the pool once all authors are paid.
+  pub fn distribute_rewards_chunk() {
+    let mut distribution = match Self::pending_reward_distribution() {
+      Some(distribution) => distribution,
+      None => return,
+    };
+    let epoch = distribution.epoch;
+    let total_score = Self::epoch_positive_score(epoch);
+    let count = Self::epoch_scored_accounts_count(epoch);
+    let end = count.min(distribution.next_index.saturating_add(Self::reward_distribution_chunk_len()));
+
+    for index in distribution.next_index..end {
+      if let Some(account) = <EpochScoredAccount<T>>::take((epoch, index)) {
+        let score = <EpochScoreByAccount<T>>::take((epoch, account.clone()));
+        if score <= 0 || total_score == 0 {
+          continue;
+        }
+
+        // A reward below the existential deposit to a new account is not paid, but goes back to the pool:
+        let reward = distribution.amount.saturating_mul(BalanceOf::<T>::sa(score as u64)) / BalanceOf::<T>::sa(total_score);
+        let paid = T::Currency::deposit_creating(&account, reward).peek();
+        if !paid.is_zero() {
+          distribution.paid = distribution.paid.saturating_add(paid);
+          Self::deposit_event(RawEvent::CreatorRewarded(account, epoch));
+        }
+      }
+    }
+    distribution.next_index = end;
+
+    if end < count {
+      <PendingRewardDistribution<T>>::put(distribution);
+      return;
+    }
+
+    Self::add_to_reward_pool(distribution.amount.saturating_sub(distribution.paid));
+    <EpochScoredAccountsCount<T>>::remove(epoch);
+    <EpochPositiveScore<T>>::remove(epoch);
+    <PendingRewardDistribution<T>>::kill();
+
+    Self::deposit_event(RawEvent::RewardsDistributed(epoch));
+  }
+
+  pub fn change_social_account_reputation(account: T::AccountId, scorer: T::AccountId, score_diff: i16, action: ScoringAction) -> Result {
+    let (reputation, score_diff) = Self::reputation_after(Self::get_or_new_social_account(account.clone()).reputation, score_diff)?;
+    Self::record_reputation_change(account, scorer, reputation, score_diff, action);
+    Ok(())
+  }
+
+  /// Writes a reputation change checked by `reputation_after`. The diff given by the scorer is kept,
+  /// or forgotten if the change reverts the one it has given before.
+  pub fn record_reputation_change(account: T::AccountId, scorer: T::AccountId, reputation: u32, score_diff: i16, action: ScoringAction) {
+    Self::set_reputation(account.clone(), reputation, action);
+
     if Self::account_reputation_diff_by_account((scorer.clone(), account.clone(), action)).is_some() {
-      <AccountReputationDiffByAccount<T>>::remove((scorer.clone(), account.clone(), action));
+      <AccountReputationDiffByAccount<T>>::remove((scorer, account, action));
     } else {
-      <AccountReputationDiffByAccount<T>>::insert((scorer.clone(), account.clone(), action), score_diff);
+      <AccountReputationDiffByAccount<T>>::insert((scorer, account, action), score_diff);
     }
+  }
 
-    <SocialAccountById<T>>::insert(account.clone(), social_account.clone());
+  /// Returns the diff that was actually applied, as reputation never goes below 1.
+  fn apply_reputation_diff(account: T::AccountId, score_diff: i16, action: ScoringAction) -> rstd::result::Result<i16, &'static str> {
+    let (reputation, score_diff) = Self::reputation_after(Self::get_or_new_social_account(account.clone()).reputation, score_diff)?;
+    Self::set_reputation(account, reputation, action);
+    Ok(score_diff)
+  }
+
+  fn set_reputation(account: T::AccountId, reputation: u32, action: ScoringAction) {
+    let mut social_account = Self::get_or_new_social_account(account.clone());
+    social_account.reputation = reputation;
+    <SocialAccountById<T>>::insert(account.clone(), social_account);
 
-    Self::deposit_event(RawEvent::AccountReputationChanged(account, action, social_account.reputation));
+    Self::deposit_event(RawEvent::AccountReputationChanged(account, action, reputation));
+  }
+
+  /// Returns the reputation after the diff and the diff that would actually be applied,
+  /// as reputation never goes below 1.
+  pub fn reputation_after(reputation: u32, score_diff: i16) -> rstd::result::Result<(u32, i16), &'static str> {
+    if reputation as i64 + score_diff as i64 <= 1 {
+      return Ok((1, 0));
+    }
 
+    let reputation = if score_diff < 0 {
+      reputation.checked_sub((score_diff as i32 * -1) as u32).ok_or(MSG_OUT_OF_BOUNDS_UPDATING_ACCOUNT_REPUTATION)?
+    } else {
+      reputation.checked_add(score_diff as u32).ok_or(MSG_OUT_OF_BOUNDS_UPDATING_ACCOUNT_REPUTATION)?
+    };
+    Ok((reputation, score_diff))
+  }
+
+  /// Applies a diff to the reputations simulated so far, failing the way `apply_reputation_diff` would.
+  fn simulate_reputation_diff(reputations: &mut Vec<(T::AccountId, u32)>, account: T::AccountId, score_diff: i16) -> Result {
+    let index = match reputations.iter().position(|(other, _)| *other == account) {
+      Some(index) => index,
+      None => {
+        let reputation = Self::get_or_new_social_account(account.clone()).reputation;
+        reputations.push((account, reputation));
+        reputations.len() - 1
+      },
+    };
+    reputations[index].1 = Self::reputation_after(reputations[index].1, score_diff)?.0;
     Ok(())
   }
 
+  /// The score diff given by a reputation alone, as `get_account_score_diff` gives it while stake does not count.
   pub fn get_score_diff(reputation: u32, action: ScoringAction) -> i16 {
-    let r = Self::log_2(reputation);
-    let d = (reputation - (2 as u32).pow(r)) * 100 / (2 as u32).pow(r);
-    let score_diff = ((r + 1) * 100 + d) / 100;
-    
-    score_diff as i16 * Self::weight_of_scoring_action(action)
+    Self::mixed_score_diff(reputation, 0, 0, action)
+  }
+
+  /// Mixes the levels of reputation and of voting stake by `StakeWeightPercent`. Both grow logarithmically,
+  /// so an account without stake has little weight once stake counts.
+  pub fn get_account_score_diff(account: &T::AccountId, reputation: u32, action: ScoringAction) -> i16 {
+    let stake_percent = Self::stake_weight_percent();
+    let stake_points = if stake_percent == 0 { 0 } else { Self::voting_stake_points(account) };
+    Self::mixed_score_diff(reputation, stake_points, stake_percent, action)
+  }
+
+  /// Levels are multiplied by the action weight before they are divided, so that their fractions count.
+  fn mixed_score_diff(reputation: u32, stake_points: u32, stake_percent: u8, action: ScoringAction) -> i16 {
+    let stake_percent = stake_percent as i64;
+    let stake_level = if stake_points == 0 { 0 } else { Self::score_level_percent(stake_points) as i64 };
+    let mixed_level = Self::score_level_percent(reputation) as i64 * (100 - stake_percent) + stake_level * stake_percent;
+
+    (mixed_level * Self::weight_of_scoring_action(action) as i64 / 10_000) as i16
+  }
+
+  /// Level of a reputation or of stake points, in hundredths.
+  pub fn score_level_percent(value: u32) -> u32 {
+    let r = Self::log_2(value);
+    // Multiplying by 100 does not fit in u32 for big values:
+    let d = (value as u64 - (2 as u64).pow(r)) * 100 / (2 as u64).pow(r);
+    ((r as u64 + 1) * 100).saturating_add(d).min(u32::max_value() as u64) as u32
+  }
+
+  pub fn voting_stake_points(account: &T::AccountId) -> u32 {
+    let stake_per_point = Self::stake_per_point();
+    if stake_per_point.is_zero() {
+      return 0;
+    }
+    let points: u64 = (Self::voting_stake(account.clone()) / stake_per_point).as_();
+    points.min(u32::max_value() as u64) as u32
+  }
+
+  pub fn unbonding_amount(account: &T::AccountId) -> BalanceOf<T> {
+    Self::unbonding_stake(account.clone()).map_or(Zero::zero(), |(amount, _)| amount)
+  }
+
+  /// Keeps both the voting and the unbonding stake locked.
+  pub fn update_voting_stake_lock(account: &T::AccountId) -> Result {
+    let locked = Self::voting_stake(account.clone()).checked_add(&Self::unbonding_amount(account))
+      .ok_or(MSG_OUT_OF_BOUNDS_LOCKING_VOTING_STAKE)?;
+    if locked.is_zero() {
+      T::Currency::remove_lock(VOTING_STAKE_LOCK_ID, account);
+    } else {
+      T::Currency::set_lock(VOTING_STAKE_LOCK_ID, account, locked, T::BlockNumber::max_value(), WithdrawReasons::all());
+    }
+    Ok(())
   }
 
   // TODO write unit tests for this method.
@@ -233,50 +739,657 @@ impl<T: Trait> Module<T> {
     Self::num_bits::<u32>() as u32 - x.leading_zeros() - 1
   }
 
-  pub fn is_username_valid(username: Vec<u8>) -> Result {
-    ensure!(Self::account_by_profile_username(username.clone()).is_none(), MSG_USERNAME_IS_BUSY);
+  /// Base fee of the action plus a per-byte fee for its payload.
+  /// Accounts with high reputation get a discount or don't pay at all.
+  pub fn fee_for(account: &T::AccountId, action: PaidAction, payload_len: usize) -> BalanceOf<T> {
+    let reputation = Self::social_account_by_id(account.clone()).map_or(1, |social_account| social_account.reputation);
+    if reputation >= Self::fee_exempt_reputation() {
+      return Zero::zero();
+    }
+
+    let byte_fee = Self::fee_per_byte().saturating_mul(BalanceOf::<T>::sa(payload_len as u64));
+    let fee = Self::base_fee_by_action(action).saturating_add(byte_fee);
+
+    if reputation >= Self::fee_discount_reputation() {
+      let discount = fee.saturating_mul(BalanceOf::<T>::sa(Self::fee_discount_percent() as u64)) / BalanceOf::<T>::sa(100);
+      fee.saturating_sub(discount)
+    } else {
+      fee
+    }
+  }
+
+  /// Burns the fee of the action. Should be called once every check of the call has passed, before any storage
+  /// is changed. Scoring that follows it is checked beforehand by `ensure_post_score_can_change` and the like.
+  pub fn charge_fee(account: &T::AccountId, action: PaidAction, payload_len: usize) -> Result {
+    let fee = Self::fee_for(account, action, payload_len);
+    if !fee.is_zero() {
+      let _ = T::Currency::withdraw(
+        account,
+        fee,
+        WithdrawReason::Fee,
+        ExistenceRequirement::KeepAlive
+      )?;
+    }
+
+    Ok(())
+  }
+
+  pub fn to_lowercase(text: Vec<u8>) -> Vec<u8> {
+    text.iter().map(|c| c.to_ascii_lowercase()).collect()
+  }
+
+  /// Lowercases a slug and checks that it can be used by `blog_id`: it should not be
+  /// reserved or resolve to another blog.
+  pub fn normalize_slug(slug: Vec<u8>, blog_id: T::BlogId) -> rstd::result::Result<Vec<u8>, &'static str> {
+    let slug = Self::to_lowercase(slug);
+
+    ensure!(slug.len() >= Self::slug_min_len() as usize, MSG_BLOG_SLUG_IS_TOO_SHORT);
+    ensure!(slug.len() <= Self::slug_max_len() as usize, MSG_BLOG_SLUG_IS_TOO_LONG);
+    ensure!(
+      slug.iter().all(|&c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == b'-' || c == b'_'),
+      MSG_BLOG_SLUG_HAS_INVALID_CHARS
+    );
+    ensure!(!Self::is_slug_reserved(slug.clone()), MSG_BLOG_SLUG_IS_RESERVED);
+
+    if let Some(slug_blog_id) = Self::resolve_slug(slug.clone()) {
+      ensure!(slug_blog_id == blog_id, MSG_BLOG_SLUG_IS_NOT_UNIQUE);
+    }
+
+    Ok(slug)
+  }
+
+  /// Finds a blog by its current slug or by an old one that still redirects to it.
+  pub fn resolve_slug(slug: Vec<u8>) -> Option<T::BlogId> {
+    let slug = Self::to_lowercase(slug);
+    let is_redirect_expired = Self::slug_redirect_expires_at(slug.clone())
+      .map_or(false, |expires_at| expires_at <= <system::Module<T>>::block_number());
+
+    if is_redirect_expired { None } else { Self::blog_id_by_slug(slug) }
+  }
+
+  pub fn claim_slug(slug: Vec<u8>, blog_id: T::BlogId) {
+    <SlugRedirectExpiresAt<T>>::remove(slug.clone());
+    <BlogIdBySlug<T>>::insert(slug, blog_id);
+  }
+
+  /// Resolves mentions to distinct accounts. Authors do not mention themselves.
+  pub fn resolve_mentions(author: &T::AccountId, mentions: Vec<Mention<T>>) -> rstd::result::Result<Vec<T::AccountId>, &'static str> {
+    ensure!(mentions.len() <= Self::max_mentions() as usize, MSG_TOO_MANY_MENTIONS);
+
+    let mut accounts: Vec<T::AccountId> = Vec::with_capacity(mentions.len());
+    for mention in mentions {
+      let account = match mention {
+        Mention::Account(account) => account,
+        Mention::Username(username) => Self::account_by_profile_username(Self::to_lowercase(username))
+          .ok_or(MSG_MENTIONED_USERNAME_NOT_FOUND)?,
+      };
+
+      // Accounts that blocked the author are skipped rather than failing the call:
+      let is_blocked = Self::account_blocked_by_account((account.clone(), author.clone()));
+      if account != *author && !is_blocked && !accounts.contains(&account) {
+        accounts.push(account);
+      }
+    }
+
+    Ok(accounts)
+  }
+
+  /// Indexes and announces mentions of accounts that were not mentioned in this post or comment yet.
+  pub fn add_mentions(author: T::AccountId, accounts: Vec<T::AccountId>, post_id: T::PostId, comment_id: Option<T::CommentId>) {
+    for account in accounts {
+      if Self::is_mentioned_in((account.clone(), post_id, comment_id)) {
+        continue;
+      }
+
+      <IsMentionedIn<T>>::insert((account.clone(), post_id, comment_id), true);
+      let pruned = Self::push_bounded::<_, _, MentionByAccount<T>, MentionsCount<T>>(
+        account.clone(), (post_id, comment_id), Self::max_mentions_per_account()
+      );
+      // Flags are kept only for kept mentions, so a pruned mention can be indexed again:
+      for (pruned_post_id, pruned_comment_id) in pruned {
+        <IsMentionedIn<T>>::remove((account.clone(), pruned_post_id, pruned_comment_id));
+      }
+      Self::deposit_event(RawEvent::AccountMentioned(author.clone(), account, post_id, comment_id));
+    }
+  }
+
+  /// A share without content is a plain repost, otherwise the content is the sharer's commentary.
+  pub fn ensure_quote_is_valid(content: &Content) -> Result {
+    if content.is_none() {
+      return Ok(());
+    }
+    Self::is_content_valid(content, Some(Self::post_max_len()))
+  }
+
+  /// Posts shared one by another, starting from the post shared by the given one up to the original.
+  pub fn attribution_chain(post_id: T::PostId) -> Vec<T::PostId> {
+    let mut chain = Vec::new();
+    let mut post = Self::post_by_id(post_id);
+    while let Some(PostExtension::SharedPost(shared_post_id)) = post.map(|post| post.extension) {
+      chain.push(shared_post_id);
+      post = Self::post_by_id(shared_post_id);
+    }
+    chain
+  }
+
+  pub fn ensure_poll_is_valid(poll: &Poll<T>) -> Result {
+    ensure!(poll.options.len() >= 2, MSG_POLL_HAS_TOO_FEW_OPTIONS);
+    ensure!(poll.options.len() <= Self::poll_max_options() as usize, MSG_POLL_HAS_TOO_MANY_OPTIONS);
+    for option in poll.options.iter() {
+      ensure!(!option.is_empty(), MSG_POLL_OPTION_IS_EMPTY);
+      ensure!(option.len() <= Self::poll_option_max_len() as usize, MSG_POLL_OPTION_IS_TOO_LONG);
+      ensure!(core::str::from_utf8(option).is_ok(), MSG_POLL_OPTION_IS_NOT_UTF8);
+    }
+    ensure!(poll.ends_at > <system::Module<T>>::block_number(), MSG_POLL_ENDS_IN_PAST);
+    Ok(())
+  }
+
+  /// Lowercases tags, strips a leading `#` and drops duplicates, keeping the order.
+  pub fn normalize_tags(tags: Vec<Vec<u8>>) -> rstd::result::Result<Vec<Vec<u8>>, &'static str> {
+    ensure!(tags.len() <= Self::max_tags_per_post() as usize, MSG_TOO_MANY_TAGS_ON_POST);
+
+    let mut normalized: Vec<Vec<u8>> = Vec::with_capacity(tags.len());
+    for tag in tags {
+      let tag = Self::to_lowercase(if tag.first() == Some(&b'#') { tag[1..].to_vec() } else { tag });
+
+      ensure!(!tag.is_empty(), MSG_TAG_IS_EMPTY);
+      ensure!(tag.len() <= Self::tag_max_len() as usize, MSG_TAG_IS_TOO_LONG);
+      ensure!(
+        tag.iter().all(|&c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == b'_'),
+        MSG_TAG_HAS_INVALID_CHARS
+      );
+
+      if !normalized.contains(&tag) {
+        normalized.push(tag);
+      }
+    }
+
+    Ok(normalized)
+  }
+
+  pub fn tags_size(tags: &[Vec<u8>]) -> usize {
+    tags.iter().map(|tag| tag.len()).sum()
+  }
+
+  fn current_tag_usage_bucket() -> u64 {
+    <system::Module<T>>::block_number().as_() / Self::tag_usage_bucket_len().as_()
+  }
+
+  /// The oldest bucket that is still within the trending window.
+  fn first_trending_bucket() -> u64 {
+    (Self::current_tag_usage_bucket() + 1).saturating_sub(Self::tag_usage_buckets_count() as u64)
+  }
+
+  /// Indexes the post by its tags and counts their usage.
+  pub fn add_post_tags(post_id: T::PostId, tags: &[Vec<u8>]) {
+    let bucket = Self::current_tag_usage_bucket();
+    let first_bucket = Self::first_trending_bucket();
+
+    for tag in tags {
+      <PostIdsByTag<T>>::mutate(tag.clone(), |ids| ids.push(post_id));
+
+      let mut usage = Self::tag_usage(tag.clone());
+      usage.retain(|(usage_bucket, _)| *usage_bucket >= first_bucket);
+      match usage.last_mut() {
+        Some((last_bucket, count)) if *last_bucket == bucket => *count = count.saturating_add(1),
+        _ => usage.push((bucket, 1)),
+      }
+      <TagUsage<T>>::insert(tag.clone(), usage);
+
+      if Self::recent_tag_index(tag.clone()).is_none() {
+        let index = Self::recent_tags_count();
+        <RecentTag<T>>::insert(index, tag.clone());
+        <RecentTagIndex<T>>::insert(tag.clone(), index);
+        <RecentTagsCount<T>>::put(index + 1);
+      }
+    }
+  }
+
+  /// Only the tags that were actually added count as used again.
+  pub fn replace_post_tags(post_id: T::PostId, old_tags: &[Vec<u8>], new_tags: &[Vec<u8>]) {
+    for tag in old_tags.iter().filter(|tag| !new_tags.contains(tag)) {
+      <PostIdsByTag<T>>::mutate(tag.clone(), |ids| Self::vec_remove_on(ids, post_id));
+    }
+
+    let added_tags: Vec<Vec<u8>> = new_tags.iter().filter(|tag| !old_tags.contains(tag)).cloned().collect();
+    Self::add_post_tags(post_id, &added_tags);
+  }
+
+  /// All tags used within the trending window, in no particular order.
+  pub fn recent_tags() -> Vec<Vec<u8>> {
+    (0..Self::recent_tags_count()).filter_map(|index| Self::recent_tag(index)).collect()
+  }
+
+  /// Forgets usage of the next chunk of tags that were not used within the trending window.
+  /// Tags are checked from the last one down, so that a pruned tag is replaced by an already checked one.
+  pub fn prune_recent_tags_chunk() {
+    let mut cursor = match Self::tag_pruning_cursor() {
+      Some(cursor) => cursor,
+      None => return,
+    };
+    let first_bucket = Self::first_trending_bucket();
+    let end = cursor.saturating_sub(Self::tag_pruning_chunk_len());
+
+    while cursor > end {
+      cursor -= 1;
+      let tag = match Self::recent_tag(cursor) {
+        Some(tag) => tag,
+        None => continue,
+      };
+
+      let mut usage = Self::tag_usage(tag.clone());
+      usage.retain(|(usage_bucket, _)| *usage_bucket >= first_bucket);
+      if !usage.is_empty() {
+        <TagUsage<T>>::insert(tag, usage);
+        continue;
+      }
+
+      <TagUsage<T>>::remove(tag.clone());
+      <RecentTagIndex<T>>::remove(tag);
+      let last_index = Self::recent_tags_count() - 1;
+      if let Some(last_tag) = <RecentTag<T>>::take(last_index) {
+        if last_index != cursor {
+          <RecentTag<T>>::insert(cursor, last_tag.clone());
+          <RecentTagIndex<T>>::insert(last_tag, cursor);
+        }
+      }
+      <RecentTagsCount<T>>::put(last_index);
+    }
+
+    if cursor > 0 {
+      <TagPruningCursor<T>>::put(cursor);
+    } else {
+      <TagPruningCursor<T>>::kill();
+    }
+  }
+
+  /// Up to `limit` tags that were used the most within the trending window, along with their usage.
+  pub fn trending_tags(limit: u32) -> Vec<(Vec<u8>, u32)> {
+    let first_bucket = Self::first_trending_bucket();
+
+    let mut trending: Vec<(Vec<u8>, u32)> = Self::recent_tags().into_iter()
+      .map(|tag| {
+        let usage = Self::tag_usage(tag.clone()).iter()
+          .filter(|(usage_bucket, _)| *usage_bucket >= first_bucket)
+          .fold(0u32, |total, (_, count)| total.saturating_add(*count));
+        (tag, usage)
+      })
+      .filter(|(_, usage)| *usage > 0)
+      .collect();
+
+    trending.sort_unstable_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+    trending.truncate(limit as usize);
+    trending
+  }
+
+  /// Registers `username` for `owner` and locks its deposit for the registration period.
+  pub fn claim_username(username: Vec<u8>, owner: T::AccountId) {
+    let expires_at = <system::Module<T>>::block_number() + Self::username_registration_period();
+    Self::lock_username_deposit(&owner, &username, expires_at);
+
+    let username_key = Self::to_lowercase(username);
+    <ReleasedUsername<T>>::remove(username_key.clone());
+    <UsernameExpiresAt<T>>::insert(username_key.clone(), expires_at);
+    <AccountByProfileUsername<T>>::insert(username_key, owner);
+  }
+
+  /// Frees a username that `owner` stopped using, leaving it to them for the release cooldown.
+  /// The deposit lock is not removed, as it is moved to the next username of the owner.
+  pub fn release_username(username_key: Vec<u8>, owner: T::AccountId) {
+    let cooldown_ends_at = <system::Module<T>>::block_number() + Self::username_release_cooldown();
+    <AccountByProfileUsername<T>>::remove(username_key.clone());
+    <UsernameExpiresAt<T>>::remove(username_key.clone());
+    <ReleasedUsername<T>>::insert(username_key, (owner, cooldown_ends_at));
+  }
+
+  /// The shorter a username is, the bigger deposit it requires.
+  pub fn username_deposit(username: &[u8]) -> BalanceOf<T> {
+    Self::username_deposit_base() / BalanceOf::<T>::sa(username.len().max(1) as u64)
+  }
+
+  pub fn ensure_can_lock_username_deposit(owner: &T::AccountId, username: &[u8]) -> Result {
+    ensure!(
+      T::Currency::free_balance(owner) >= Self::username_deposit(username),
+      MSG_NOT_ENOUGH_BALANCE_FOR_USERNAME_DEPOSIT
+    );
+    Ok(())
+  }
+
+  pub fn lock_username_deposit(owner: &T::AccountId, username: &[u8], expires_at: T::BlockNumber) {
+    let deposit = Self::username_deposit(username);
+    if deposit.is_zero() {
+      Self::unlock_username_deposit(owner);
+    } else {
+      T::Currency::set_lock(USERNAME_LOCK_ID, owner, deposit, expires_at, WithdrawReasons::all());
+    }
+  }
+
+  pub fn unlock_username_deposit(owner: &T::AccountId) {
+    T::Currency::remove_lock(USERNAME_LOCK_ID, owner);
+  }
+
+  /// Usernames are unique regardless of their case, so they are indexed in lowercase.
+  /// A username can be taken again by the `owner` it already belongs to or was released by.
+  pub fn is_username_valid(username: Vec<u8>, owner: T::AccountId) -> Result {
+    let username_key = Self::to_lowercase(username.clone());
+    ensure!(
+      Self::account_by_profile_username(username_key.clone()).map_or(true, |account| account == owner),
+      MSG_USERNAME_IS_BUSY
+    );
+    if let Some((previous_owner, cooldown_ends_at)) = Self::released_username(username_key.clone()) {
+      ensure!(
+        previous_owner == owner || cooldown_ends_at <= <system::Module<T>>::block_number(),
+        MSG_USERNAME_IS_IN_COOLDOWN
+      );
+    }
+    ensure!(!Self::is_username_reserved(username_key), MSG_USERNAME_IS_RESERVED);
     ensure!(username.len() >= Self::username_min_len() as usize, MSG_USERNAME_TOO_SHORT);
     ensure!(username.len() <= Self::username_max_len() as usize, MSG_USERNAME_TOO_LONG);
     ensure!(username.iter().all(|&x| x.is_ascii_alphanumeric()), MSG_USERNAME_NOT_ALPHANUMERIC);
+    Self::ensure_can_lock_username_deposit(&owner, &username)?;
+
+    Ok(())
+  }
+
+  /// Appends a record to a queue indexed from its oldest record, keeping only the last `max_len` records.
+  /// If `max_len` was lowered since the last record, all the records that fell out of the window are pruned.
+  /// Returns the pruned records.
+  fn push_bounded<Id, Record, Records, Count>(id: Id, record: Record, max_len: u32) -> Vec<Record>
+  where
+    Id: Codec + Clone,
+    Record: Codec,
+    Records: StorageMap<(Id, u32), Record, Query = Option<Record>>,
+    Count: StorageMap<Id, u32, Query = u32>,
+  {
+    let index = Count::get(id.clone());
+    Records::insert((id.clone(), index), record);
+    Count::insert(id.clone(), index + 1);
+
+    // Kept records are contiguous, so pruning stops at the first one that is already removed:
+    let mut pruned = Vec::new();
+    let mut first_kept = (index + 1).saturating_sub(max_len);
+    while first_kept > 0 {
+      match Records::take((id.clone(), first_kept - 1)) {
+        Some(pruned_record) => pruned.push(pruned_record),
+        None => break,
+      }
+      first_kept -= 1;
+    }
+    pruned
+  }
+
+  fn add_revision<Id, Record, History, RevisionsCount>(id: Id, record: Record)
+  where
+    Id: Codec + Clone,
+    Record: Codec,
+    History: StorageMap<(Id, u32), Record, Query = Option<Record>>,
+    RevisionsCount: StorageMap<Id, u32, Query = u32>,
+  {
+    Self::push_bounded::<_, _, History, RevisionsCount>(id, record, Self::max_edit_history_len());
+  }
+
+  fn history_page<Id, Record, History, RevisionsCount>(id: Id, offset: u32, limit: u32) -> Vec<Record>
+  where
+    Id: Codec + Clone,
+    Record: Codec,
+    History: StorageMap<(Id, u32), Record, Query = Option<Record>>,
+    RevisionsCount: StorageMap<Id, u32, Query = u32>,
+  {
+    let end = RevisionsCount::get(id.clone()).min(offset.saturating_add(limit));
+    (offset..end).filter_map(|index| History::get((id.clone(), index))).collect()
+  }
+
+  pub fn add_blog_revision(blog_id: T::BlogId, record: BlogHistoryRecord<T>) {
+    Self::add_revision::<_, _, BlogHistory<T>, BlogRevisionsCount<T>>(blog_id, record);
+  }
+
+  pub fn add_post_revision(post_id: T::PostId, record: PostHistoryRecord<T>) {
+    Self::add_revision::<_, _, PostHistory<T>, PostRevisionsCount<T>>(post_id, record);
+  }
+
+  pub fn add_comment_revision(comment_id: T::CommentId, record: CommentHistoryRecord<T>) {
+    Self::add_revision::<_, _, CommentHistory<T>, CommentRevisionsCount<T>>(comment_id, record);
+  }
+
+  pub fn add_profile_revision(account: T::AccountId, record: ProfileHistoryRecord<T>) {
+    Self::add_revision::<_, _, ProfileHistory<T>, ProfileRevisionsCount<T>>(account, record);
+  }
+
+  /// Returns all revisions starting from the given one, failing if any of them is pruned.
+  fn revisions_since<Id, Record, History, RevisionsCount>(id: Id, revision: u32) -> rstd::result::Result<Vec<Record>, &'static str>
+  where
+    Id: Codec + Clone,
+    Record: Codec,
+    History: StorageMap<(Id, u32), Record, Query = Option<Record>>,
+    RevisionsCount: StorageMap<Id, u32, Query = u32>,
+  {
+    let count = RevisionsCount::get(id.clone());
+    ensure!(revision < count, MSG_REVISION_NOT_FOUND);
+
+    (revision..count)
+      .map(|index| History::get((id.clone(), index)).ok_or(MSG_REVISION_IS_PRUNED))
+      .collect()
+  }
+
+  /// Undoes revisions from the latest one down to the given one.
+  /// Fields that were not edited since then are left as `None`.
+  pub fn blog_state_before_revision(blog_id: T::BlogId, revision: u32) -> rstd::result::Result<BlogUpdate<T>, &'static str> {
+    let records = Self::revisions_since::<_, _, BlogHistory<T>, BlogRevisionsCount<T>>(blog_id, revision)?;
+
+    let mut state = BlogUpdate { writers: None, slug: None, content: None };
+    for record in records.into_iter().rev() {
+      let old_data = record.old_data;
+      if old_data.writers.is_some() { state.writers = old_data.writers; }
+      if old_data.slug.is_some() { state.slug = old_data.slug; }
+      if old_data.content.is_some() { state.content = old_data.content; }
+    }
+    Ok(state)
+  }
+
+  pub fn post_state_before_revision(post_id: T::PostId, revision: u32) -> rstd::result::Result<PostUpdate<T>, &'static str> {
+    let records = Self::revisions_since::<_, _, PostHistory<T>, PostRevisionsCount<T>>(post_id, revision)?;
+
+    let mut state = PostUpdate { blog_id: None, content: None, tags: None };
+    for record in records.into_iter().rev() {
+      let old_data = record.old_data;
+      if old_data.blog_id.is_some() { state.blog_id = old_data.blog_id; }
+      if old_data.content.is_some() { state.content = old_data.content; }
+      if old_data.tags.is_some() { state.tags = old_data.tags; }
+    }
+    Ok(state)
+  }
+
+  pub fn comment_state_before_revision(comment_id: T::CommentId, revision: u32) -> rstd::result::Result<CommentUpdate, &'static str> {
+    let mut records = Self::revisions_since::<_, _, CommentHistory<T>, CommentRevisionsCount<T>>(comment_id, revision)?;
+
+    // Every comment revision keeps the old content, so the given one is enough:
+    Ok(records.swap_remove(0).old_data)
+  }
+
+  /// Returns up to `limit` revisions starting from the `offset`-th one. Pruned revisions are skipped.
+  pub fn blog_history_page(blog_id: T::BlogId, offset: u32, limit: u32) -> Vec<BlogHistoryRecord<T>> {
+    Self::history_page::<_, _, BlogHistory<T>, BlogRevisionsCount<T>>(blog_id, offset, limit)
+  }
+
+  pub fn post_history_page(post_id: T::PostId, offset: u32, limit: u32) -> Vec<PostHistoryRecord<T>> {
+    Self::history_page::<_, _, PostHistory<T>, PostRevisionsCount<T>>(post_id, offset, limit)
+  }
+
+  pub fn comment_history_page(comment_id: T::CommentId, offset: u32, limit: u32) -> Vec<CommentHistoryRecord<T>> {
+    Self::history_page::<_, _, CommentHistory<T>, CommentRevisionsCount<T>>(comment_id, offset, limit)
+  }
+
+  pub fn profile_history_page(account: T::AccountId, offset: u32, limit: u32) -> Vec<ProfileHistoryRecord<T>> {
+    Self::history_page::<_, _, ProfileHistory<T>, ProfileRevisionsCount<T>>(account, offset, limit)
+  }
+
+  /// Accounts are not notified about their own actions or by accounts they blocked.
+  pub fn notify(account: T::AccountId, actor: T::AccountId, kind: NotificationKind<T>) {
+    if account == actor || Self::account_blocked_by_account((account.clone(), actor.clone())) {
+      return;
+    }
+
+    let notification = Notification { created: Self::new_change(actor), kind };
+    Self::push_bounded::<_, _, NotificationByAccount<T>, NotificationsCount<T>>(
+      account.clone(), notification, Self::max_notifications_per_account()
+    );
+    Self::update_unread_notifications_count(account);
+  }
+
+  /// Pruned notifications are not counted as unread.
+  pub fn update_unread_notifications_count(account: T::AccountId) {
+    let count = Self::notifications_count(account.clone());
+    let first_kept = count.saturating_sub(Self::max_notifications_per_account());
+    let first_unread = Self::notifications_read_up_to(account.clone()).max(first_kept);
+
+    <UnreadNotificationsCount<T>>::insert(account, count - first_unread);
+  }
+
+  /// Returns up to `limit` notifications starting from the `offset`-th one. Pruned notifications are skipped.
+  pub fn notifications_page(account: T::AccountId, offset: u32, limit: u32) -> Vec<Notification<T>> {
+    Self::history_page::<_, _, NotificationByAccount<T>, NotificationsCount<T>>(account, offset, limit)
+  }
+
+  /// Returns up to `limit` mentions starting from the `offset`-th one. Pruned mentions are skipped.
+  pub fn mentions_page(account: T::AccountId, offset: u32, limit: u32) -> Vec<(T::PostId, Option<T::CommentId>)> {
+    Self::history_page::<_, _, MentionByAccount<T>, MentionsCount<T>>(account, offset, limit)
+  }
+
+  /// Both participants share the same key, whoever sends a message.
+  pub fn conversation_key(account: T::AccountId, other: T::AccountId) -> (T::AccountId, T::AccountId) {
+    if account < other { (account, other) } else { (other, account) }
+  }
 
+  /// Returns up to `limit` ids of messages starting from the `offset`-th one. Deleted messages are skipped.
+  pub fn conversation_page(account: T::AccountId, other: T::AccountId, offset: u32, limit: u32) -> Vec<T::MessageId> {
+    Self::history_page::<_, _, ConversationMessageId<T>, ConversationMessagesCount<T>>(
+      Self::conversation_key(account, other), offset, limit
+    )
+  }
+
+  pub fn is_attestation_active(attestation: &Attestation<T>) -> bool {
+    attestation.revoked.is_none()
+      && attestation.expires_at.map_or(true, |expires_at| expires_at > <system::Module<T>>::block_number())
+  }
+
+  /// Marks the attestation as revoked. If `revoker` is `None`, the call is made by root
+  /// and the revocation is recorded on behalf of the verifier.
+  pub fn revoke_attestation_at(account: T::AccountId, index: u32, revoker: Option<T::AccountId>) -> Result {
+    let mut attestations = Self::attestations_by_account(account.clone());
+    let verifier = {
+      let attestation = attestations.get_mut(index as usize).ok_or(MSG_ATTESTATION_NOT_FOUND)?;
+      ensure!(attestation.revoked.is_none(), MSG_ATTESTATION_IS_ALREADY_REVOKED);
+      if let Some(ref revoker) = revoker {
+        ensure!(*revoker == attestation.verifier, MSG_ONLY_VERIFIER_CAN_REVOKE_ATTESTATION);
+      }
+
+      let verifier = attestation.verifier.clone();
+      attestation.revoked = Some(Self::new_change(revoker.unwrap_or_else(|| verifier.clone())));
+      verifier
+    };
+    <AttestationsByAccount<T>>::insert(account.clone(), attestations);
+
+    Self::deposit_event(RawEvent::AttestationRevoked(verifier, account));
     Ok(())
   }
 
-  pub fn is_ipfs_hash_valid(ipfs_hash: Vec<u8>) -> Result {
-    ensure!(ipfs_hash.len() == Self::ipfs_hash_len() as usize, MSG_IPFS_IS_INCORRECT);
+  /// Attestations of the account that are neither revoked nor expired.
+  pub fn active_attestations(account: T::AccountId) -> Vec<Attestation<T>> {
+    Self::attestations_by_account(account).into_iter()
+      .filter(|attestation| Self::is_attestation_active(attestation))
+      .collect()
+  }
+
+  /// Verifier, claim and expiration block of each active attestation, as the runtime API returns them.
+  pub fn active_attestation_claims(account: T::AccountId) -> Vec<(T::AccountId, AttestationClaim, Option<T::BlockNumber>)> {
+    Self::active_attestations(account).into_iter()
+      .map(|attestation| (attestation.verifier, attestation.claim, attestation.expires_at))
+      .collect()
+  }
 
+  /// `raw_max_len` is the limit for inline content, `None` if it is not allowed.
+  pub fn is_content_valid(content: &Content, raw_max_len: Option<u32>) -> Result {
+    match content {
+      Content::None => Err(MSG_CONTENT_IS_EMPTY),
+      Content::Raw(text) => {
+        let max_len = raw_max_len.ok_or(MSG_RAW_CONTENT_IS_NOT_ALLOWED)?;
+        ensure!(!text.is_empty(), MSG_CONTENT_IS_EMPTY);
+        ensure!(text.len() <= max_len as usize, MSG_RAW_CONTENT_IS_TOO_LONG);
+        ensure!(core::str::from_utf8(text).is_ok(), MSG_RAW_CONTENT_IS_NOT_UTF8);
+        Ok(())
+      },
+      Content::IPFS(cid) => {
+        ensure!(content::is_ipfs_cid_valid(cid), MSG_IPFS_IS_INCORRECT);
+        Ok(())
+      },
+      Content::Hyper(key) => {
+        ensure!(content::is_hyper_hash_valid(key), MSG_HYPER_HASH_IS_INCORRECT);
+        Ok(())
+      },
+      Content::Arweave(tx_id) => {
+        ensure!(content::is_arweave_hash_valid(tx_id), MSG_ARWEAVE_HASH_IS_INCORRECT);
+        Ok(())
+      },
+    }
+  }
+
+  /// Checks what `share_post` checks, without changing storage.
+  pub fn ensure_post_can_be_shared(account: &T::AccountId, original_post_id: T::PostId) -> Result {
+    let ref original_post = Self::post_by_id(original_post_id).ok_or(MSG_ORIGINAL_POST_NOT_FOUND)?;
+    Self::post_counters(original_post_id).shares_count.checked_add(1).ok_or(MSG_OVERFLOW_TOTAL_SHARES_SHARING_POST)?;
+    let shares_by_account = Self::post_shares_by_account((account.clone(), original_post_id))
+      .checked_add(1).ok_or(MSG_OVERFLOW_POST_SHARES_BY_ACCOUNT)?;
+    if shares_by_account == 1 {
+      Self::ensure_post_score_can_change(account, original_post, ScoringAction::SharePost)?;
+    }
+    Ok(())
+  }
+
+  /// Checks what `share_comment` checks, without changing storage.
+  pub fn ensure_comment_can_be_shared(account: &T::AccountId, original_comment_id: T::CommentId) -> Result {
+    let ref original_comment = Self::comment_by_id(original_comment_id).ok_or(MSG_ORIGINAL_COMMENT_NOT_FOUND)?;
+    Self::comment_counters(original_comment_id).shares_count.checked_add(1).ok_or(MSG_OVERFLOW_TOTAL_SHARES_SHARING_COMMENT)?;
+    let shares_count = Self::comment_shares_by_account((account.clone(), original_comment_id))
+      .checked_add(1).ok_or(MSG_OVERFLOW_COMMENT_SHARES_BY_ACCOUNT)?;
+    if shares_count == 1 {
+      Self::ensure_comment_score_can_change(account, original_comment, ScoringAction::ShareComment)?;
+    }
     Ok(())
   }
 
   pub fn share_post(account: T::AccountId, original_post_id: T::PostId, shared_post_id: T::PostId) -> Result {
-    let ref mut original_post = Self::post_by_id(original_post_id).ok_or(MSG_ORIGINAL_POST_NOT_FOUND)?;
-    original_post.shares_count = original_post.shares_count.checked_add(1)
+    let ref original_post = Self::post_by_id(original_post_id).ok_or(MSG_ORIGINAL_POST_NOT_FOUND)?;
+    let mut post_counters = Self::post_counters(original_post_id);
+    post_counters.shares_count = post_counters.shares_count.checked_add(1)
       .ok_or(MSG_OVERFLOW_TOTAL_SHARES_SHARING_POST)?;
 
     let mut shares_by_account = Self::post_shares_by_account((account.clone(), original_post_id));
     shares_by_account = shares_by_account.checked_add(1).ok_or(MSG_OVERFLOW_POST_SHARES_BY_ACCOUNT)?;
 
+    <PostCountersById<T>>::insert(original_post_id, post_counters);
     if shares_by_account == 1 {
       Self::change_post_score(account.clone(), original_post, ScoringAction::SharePost)?;
     }
 
-    <PostById<T>>::insert(original_post_id, original_post);
     <PostSharesByAccount<T>>::insert((account.clone(), original_post_id), shares_by_account); // TODO Maybe use mutate instead?
     <SharedPostIdsByOriginalPostId<T>>::mutate(original_post_id, |ids| ids.push(shared_post_id));
 
+    Self::notify(original_post.created.account.clone(), account.clone(), NotificationKind::PostShared(original_post_id, shared_post_id));
     Self::deposit_event(RawEvent::PostShared(account, original_post_id));
 
     Ok(())
   }
 
   pub fn share_comment(account: T::AccountId, original_comment_id: T::CommentId, shared_post_id: T::PostId) -> Result {
-    let ref mut original_comment = Self::comment_by_id(original_comment_id).ok_or(MSG_ORIGINAL_COMMENT_NOT_FOUND)?;
-    original_comment.shares_count = original_comment.shares_count.checked_add(1)
+    let ref original_comment = Self::comment_by_id(original_comment_id).ok_or(MSG_ORIGINAL_COMMENT_NOT_FOUND)?;
+    let mut comment_counters = Self::comment_counters(original_comment_id);
+    comment_counters.shares_count = comment_counters.shares_count.checked_add(1)
       .ok_or(MSG_OVERFLOW_TOTAL_SHARES_SHARING_COMMENT)?;
 
     let mut shares_count = Self::comment_shares_by_account((account.clone(), original_comment_id));
     shares_count = shares_count.checked_add(1).ok_or(MSG_OVERFLOW_COMMENT_SHARES_BY_ACCOUNT)?;
 
+    <CommentCountersById<T>>::insert(original_comment_id, comment_counters);
     if shares_count == 1 {
       Self::change_comment_score(account.clone(), original_comment, ScoringAction::ShareComment)?;
     }
@@ -284,6 +1397,7 @@ impl<T: Trait> Module<T> {
     <CommentSharesByAccount<T>>::insert((account.clone(), original_comment_id), shares_count); // TODO Maybe use mutate instead?
     <SharedPostIdsByOriginalCommentId<T>>::mutate(original_comment_id, |ids| ids.push(shared_post_id));
 
+    Self::notify(original_comment.created.account.clone(), account.clone(), NotificationKind::CommentShared(original_comment_id, shared_post_id));
     Self::deposit_event(RawEvent::CommentShared(account, original_comment_id));
 
     Ok(())
@@ -0,0 +1,595 @@
+#![cfg(test)]
+use super::mock::*;
+
+use super::blogs::*;
+use super::defaults::*;
+use super::content::Content;
+
+use runtime_io::with_externalities;
+use srml_support::StorageMap;
+use srml_support::traits::Currency;
+use runtime_primitives::traits::OnFinalize;
+
+/// Accounts that are inspected by `check_invariants()` and driven by the random sequences.
+pub const TEST_ACCOUNTS: [AccountId; 4] = [1, 2, 3, 4];
+
+const POST_SCORING_ACTIONS: [ScoringAction; 4] = [
+  ScoringAction::UpvotePost,
+  ScoringAction::DownvotePost,
+  ScoringAction::SharePost,
+  ScoringAction::CreateComment,
+];
+
+const TAGS: [&str; 3] = ["rust", "Wasm", "#substrate"];
+
+const COMMENT_SCORING_ACTIONS: [ScoringAction; 3] = [
+  ScoringAction::UpvoteComment,
+  ScoringAction::DownvoteComment,
+  ScoringAction::ShareComment,
+];
+
+fn blog_ids() -> Vec<BlogId> {
+  (1..Blogs::next_blog_id()).filter(|id| Blogs::blog_by_id(*id).is_some()).collect()
+}
+
+fn post_ids() -> Vec<PostId> {
+  (1..Blogs::next_post_id()).filter(|id| Blogs::post_by_id(*id).is_some()).collect()
+}
+
+fn comment_ids() -> Vec<CommentId> {
+  (1..Blogs::next_comment_id()).filter(|id| Blogs::comment_by_id(*id).is_some()).collect()
+}
+
+fn count_reactions(reaction_ids: Vec<ReactionId>) -> (u16, u16) {
+  let mut upvotes = 0;
+  let mut downvotes = 0;
+
+  for reaction_id in reaction_ids {
+    let reaction = Blogs::reaction_by_id(reaction_id).expect("indexed reaction should exist");
+    match reaction.kind {
+      ReactionKind::Upvote => upvotes += 1,
+      ReactionKind::Downvote => downvotes += 1,
+    }
+  }
+
+  (upvotes, downvotes)
+}
+
+fn check_blog_invariants() {
+  let mut total_followers = 0;
+
+  for blog_id in blog_ids() {
+    let blog = Blogs::blog_by_id(blog_id).unwrap();
+    let blog_counters = Blogs::blog_counters(blog_id);
+    let followers = Blogs::blog_followers(blog_id);
+
+    assert_eq!(blog_counters.followers_count as usize, followers.len(), "followers count of blog {}", blog_id);
+    for follower in followers {
+      assert!(Blogs::blog_followed_by_account((follower, blog_id)), "blog {} follower {} flag", blog_id, follower);
+      assert!(Blogs::blogs_followed_by_account(follower).contains(&blog_id), "blog {} follower {} index", blog_id, follower);
+    }
+    total_followers += blog_counters.followers_count;
+
+    assert_eq!(blog_counters.posts_count as usize, Blogs::post_ids_by_blog_id(blog_id).len(), "posts count of blog {}", blog_id);
+    for post_id in Blogs::pinned_post_ids(blog_id) {
+      assert!(Blogs::post_ids_by_blog_id(blog_id).contains(&post_id), "pinned post {} of blog {}", post_id, blog_id);
+    }
+    assert_eq!(Blogs::blog_id_by_slug(blog.slug.clone()), Some(blog_id), "slug of blog {}", blog_id);
+    assert!(Blogs::slug_redirect_expires_at(blog.slug.clone()).is_none(), "current slug of blog {} is a redirect", blog_id);
+    assert!(Blogs::blog_ids_by_owner(blog.created.account).contains(&blog_id), "owner index of blog {}", blog_id);
+  }
+
+  let total_following: u32 = TEST_ACCOUNTS.iter()
+    .filter_map(|account| Blogs::social_account_by_id(*account))
+    .map(|social_account| social_account.following_blogs_count as u32)
+    .sum();
+  assert_eq!(total_following, total_followers, "sum of followed blogs and sum of blog followers");
+}
+
+fn check_account_invariants() {
+  for account in TEST_ACCOUNTS.iter().cloned() {
+    let notifications_count = Blogs::notifications_count(account);
+    let first_kept = notifications_count.saturating_sub(Blogs::max_notifications_per_account());
+    let first_unread = Blogs::notifications_read_up_to(account).max(first_kept);
+    assert!(Blogs::notifications_read_up_to(account) <= notifications_count, "read notifications of {}", account);
+    assert_eq!(Blogs::unread_notifications_count(account), notifications_count - first_unread, "unread notifications of {}", account);
+    for index in first_kept..notifications_count {
+      let notification = Blogs::notification_by_account((account, index)).expect("kept notification");
+      assert!(notification.created.account != account, "self notification of {}", account);
+    }
+
+    let staked = Blogs::voting_stake(account) + Blogs::unbonding_amount(&account);
+    let stake_lock = Balances::locks(account).into_iter().find(|lock| lock.id == VOTING_STAKE_LOCK_ID).map_or(0, |lock| lock.amount);
+    assert_eq!(stake_lock, staked, "voting stake lock of {}", account);
+    assert!(Balances::free_balance(account) >= staked, "voting stake of {} exceeds balance", account);
+
+    let social_account = match Blogs::social_account_by_id(account) {
+      Some(social_account) => social_account,
+      None => continue,
+    };
+
+    assert_eq!(social_account.following_blogs_count as usize, Blogs::blogs_followed_by_account(account).len(), "followed blogs of {}", account);
+    assert_eq!(social_account.followers_count as usize, Blogs::account_followers(account).len(), "followers of {}", account);
+    assert_eq!(social_account.following_accounts_count as usize, Blogs::accounts_followed_by_account(account).len(), "followed accounts of {}", account);
+    assert!(social_account.reputation >= 1, "reputation of {}", account);
+
+    for followed in Blogs::accounts_followed_by_account(account) {
+      assert!(Blogs::account_followed_by_account((account, followed)), "{} follows {} flag", account, followed);
+    }
+    let bookmarks = Blogs::bookmarks_by_account(account);
+    assert!(bookmarks.iter().enumerate().all(|(i, item)| !bookmarks[..i].contains(item)), "duplicate bookmarks of {}", account);
+
+    for blocked in Blogs::accounts_blocked_by_account(account) {
+      assert!(Blogs::account_blocked_by_account((account, blocked)), "{} blocks {} flag", account, blocked);
+    }
+
+    let mentions_count = Blogs::mentions_count(account);
+    let first_kept_mention = mentions_count.saturating_sub(Blogs::max_mentions_per_account());
+    for index in first_kept_mention..mentions_count {
+      let (post_id, comment_id) = Blogs::mention_by_account((account, index)).expect("kept mention");
+      assert!(Blogs::is_mentioned_in((account, post_id, comment_id)), "mention flag of {} in post {}", account, post_id);
+      assert!(Blogs::post_by_id(post_id).is_some(), "post {} mentioning {}", post_id, account);
+      if let Some(comment_id) = comment_id {
+        assert_eq!(Blogs::comment_by_id(comment_id).map(|comment| comment.post_id), Some(post_id), "comment {} mentioning {}", comment_id, account);
+      }
+    }
+
+    if let Some(profile) = social_account.profile.filter(|profile| !profile.username.is_empty()) {
+      let username_key = Blogs::to_lowercase(profile.username);
+      assert_eq!(Blogs::account_by_profile_username(username_key.clone()), Some(account), "username of {}", account);
+      assert!(Blogs::released_username(username_key.clone()).is_none(), "username of {} is released", account);
+      assert!(Blogs::username_expires_at(username_key).is_some(), "username of {} has no expiry", account);
+    }
+  }
+}
+
+fn check_post_invariants() {
+  for post_id in post_ids() {
+    let post = Blogs::post_by_id(post_id).unwrap();
+    let post_counters = Blogs::post_counters(post_id);
+
+    let is_public = post.visibility == PostVisibility::Public;
+    assert_eq!(Blogs::post_ids_by_blog_id(post.blog_id).contains(&post_id), is_public, "blog index of post {}", post_id);
+    if !is_public {
+      assert!(Blogs::cross_post_blog_ids(post_id).is_empty(), "unpublished post {} is cross-posted", post_id);
+      assert_eq!(post_counters.comments_count + post_counters.upvotes_count + post_counters.downvotes_count + post_counters.shares_count, 0,
+        "unpublished post {} has interactions", post_id);
+    }
+    for comment_id in Blogs::pinned_comment_ids(post_id) {
+      assert!(Blogs::comment_ids_by_post_id(post_id).contains(&comment_id), "pinned comment {} of post {}", comment_id, post_id);
+    }
+    for blog_id in Blogs::cross_post_blog_ids(post_id) {
+      assert!(blog_id != post.blog_id, "post {} is cross-posted to its own blog", post_id);
+      assert!(Blogs::post_ids_by_blog_id(blog_id).contains(&post_id), "blog {} index of cross-post {}", blog_id, post_id);
+    }
+    assert!(Blogs::attribution_chain(post_id).len() <= Blogs::max_quote_depth() as usize, "attribution chain of post {}", post_id);
+    let co_authors: Vec<_> = Blogs::co_author_invites(post_id).into_iter().chain(Blogs::co_authors(post_id)).collect();
+    assert!(co_authors.len() <= Blogs::max_co_authors_per_post() as usize, "co-authors count of post {}", post_id);
+    assert!(co_authors.iter().map(|(_, share)| *share as u32).sum::<u32>() < 100, "co-author shares of post {}", post_id);
+    for (i, (co_author, share)) in co_authors.iter().enumerate() {
+      assert!(*co_author != post.created.account && *share > 0, "co-author {} of post {}", co_author, post_id);
+      assert!(co_authors[i + 1..].iter().all(|(other, _)| other != co_author), "duplicate co-author {} of post {}", co_author, post_id);
+    }
+    for tag in TAGS.iter() {
+      let tag = Blogs::normalize_tags(vec![tag.as_bytes().to_vec()]).unwrap().remove(0);
+      let indexed = Blogs::post_ids_by_tag(tag.clone()).iter().filter(|id| **id == post_id).count();
+      assert_eq!(indexed, (is_public && post.tags.contains(&tag)) as usize, "tag index of post {}", post_id);
+    }
+    assert_eq!(post_counters.comments_count as usize, Blogs::comment_ids_by_post_id(post_id).len(), "comments count of post {}", post_id);
+    assert_eq!(post_counters.shares_count as usize, Blogs::shared_post_ids_by_original_post_id(post_id).len(), "shares count of post {}", post_id);
+
+    let reaction_ids = Blogs::reaction_ids_by_post_id(post_id);
+    for reaction_id in reaction_ids.iter() {
+      let reaction = Blogs::reaction_by_id(*reaction_id).unwrap();
+      assert_eq!(Blogs::post_reaction_id_by_account((reaction.created.account, post_id)), *reaction_id, "reaction index of post {}", post_id);
+    }
+
+    if let PostExtension::Poll(ref poll) = post.extension {
+      let mut votes = vec![0u64; poll.options.len()];
+      for account in TEST_ACCOUNTS.iter().cloned() {
+        for option in Blogs::poll_vote_by_account((post_id, account)).unwrap_or_default() {
+          votes[option as usize] += 1;
+        }
+      }
+      let tallies = Blogs::poll_tallies(post_id);
+      assert_eq!(tallies.len(), poll.options.len(), "tallies of poll {}", post_id);
+      if !poll.weighted_by_reputation {
+        assert_eq!(tallies, votes, "tallies of poll {}", post_id);
+      }
+    }
+    assert_eq!((post_counters.upvotes_count, post_counters.downvotes_count), count_reactions(reaction_ids), "reaction counters of post {}", post_id);
+
+    for account in TEST_ACCOUNTS.iter().cloned() {
+      for action in POST_SCORING_ACTIONS.iter().cloned() {
+        if Blogs::post_score_by_account((account, post_id, action)).is_some() {
+          assert!(
+            Blogs::post_reputation_diff_by_account((account, post_id, action, post.created.account)).is_some(),
+            "reputation diff of {} for {:?} on post {}", account, action, post_id
+          );
+        } else {
+          assert!(Blogs::post_curation_by_account((account, post_id, action)).is_empty(), "curation by {} for {:?} on post {}", account, action, post_id);
+        }
+      }
+      let upvotes = Blogs::post_upvoters(post_id).iter().filter(|(upvoter, _)| *upvoter == account).count();
+      let upvoted = Blogs::post_score_by_account((account, post_id, ScoringAction::UpvotePost)).is_some();
+      assert!(upvotes <= upvoted as usize, "curation of {} on post {}", account, post_id);
+    }
+    assert!(Blogs::post_upvoters(post_id).len() <= Blogs::max_curators_per_post() as usize, "curators count of post {}", post_id);
+  }
+
+  let recent_tags_count = Blogs::recent_tags_count();
+  for index in 0..recent_tags_count {
+    let tag = Blogs::recent_tag(index).expect("recent tag");
+    assert_eq!(Blogs::recent_tag_index(tag), Some(index), "index of recent tag {}", index);
+  }
+  assert!(Blogs::tag_pruning_cursor().map_or(true, |cursor| cursor <= recent_tags_count), "tag pruning cursor");
+}
+
+fn check_comment_invariants() {
+  let comment_ids = comment_ids();
+
+  for comment_id in comment_ids.iter().cloned() {
+    let comment = Blogs::comment_by_id(comment_id).unwrap();
+    let comment_counters = Blogs::comment_counters(comment_id);
+
+    assert!(Blogs::comment_ids_by_post_id(comment.post_id).contains(&comment_id), "post index of comment {}", comment_id);
+
+    let replies = comment_ids.iter()
+      .filter(|id| Blogs::comment_by_id(**id).unwrap().parent_id == Some(comment_id))
+      .count();
+    assert_eq!(comment_counters.direct_replies_count as usize, replies, "replies count of comment {}", comment_id);
+    assert_eq!(comment_counters.shares_count as usize, Blogs::shared_post_ids_by_original_comment_id(comment_id).len(), "shares count of comment {}", comment_id);
+
+    let reaction_ids = Blogs::reaction_ids_by_comment_id(comment_id);
+    for reaction_id in reaction_ids.iter() {
+      let reaction = Blogs::reaction_by_id(*reaction_id).unwrap();
+      assert_eq!(Blogs::comment_reaction_id_by_account((reaction.created.account, comment_id)), *reaction_id, "reaction index of comment {}", comment_id);
+    }
+    assert_eq!((comment_counters.upvotes_count, comment_counters.downvotes_count), count_reactions(reaction_ids), "reaction counters of comment {}", comment_id);
+
+    for account in TEST_ACCOUNTS.iter().cloned() {
+      for action in COMMENT_SCORING_ACTIONS.iter().cloned() {
+        if Blogs::comment_score_by_account((account, comment_id, action)).is_some() {
+          assert!(
+            Blogs::account_reputation_diff_by_account((account, comment.created.account, action)).is_some(),
+            "reputation diff of {} for {:?} on comment {}", account, action, comment_id
+          );
+        }
+      }
+    }
+  }
+}
+
+fn check_message_invariants() {
+  for reader in TEST_ACCOUNTS.iter().cloned() {
+    for sender in TEST_ACCOUNTS.iter().cloned().filter(|sender| *sender != reader) {
+      let conversation = Blogs::conversation_key(reader, sender);
+      let read_up_to = Blogs::conversation_read_up_to((reader, sender));
+      let unread = (read_up_to..Blogs::conversation_messages_count(conversation))
+        .filter_map(|index| Blogs::conversation_message_id((conversation, index)))
+        .map(|message_id| Blogs::message_by_id(message_id).expect("indexed message"))
+        .filter(|message| message.created.account == sender)
+        .count();
+      assert_eq!(Blogs::unread_messages_count((reader, sender)) as usize, unread, "unread messages of {} from {}", reader, sender);
+    }
+  }
+}
+
+fn check_reward_invariants() {
+  let epoch = Blogs::reward_epoch();
+  let mut scored_accounts = vec![];
+  for index in 0..Blogs::epoch_scored_accounts_count(epoch) {
+    let account = Blogs::epoch_scored_account((epoch, index)).expect("scored account");
+    assert!(!scored_accounts.contains(&account), "{} is scored twice in epoch {}", account, epoch);
+    scored_accounts.push(account);
+  }
+  let positive_score: u64 = scored_accounts.iter()
+    .map(|account| Blogs::epoch_score_by_account((epoch, *account)).max(0) as u64)
+    .sum();
+  assert_eq!(Blogs::epoch_positive_score(epoch), positive_score, "positive score of epoch {}", epoch);
+
+  if let Some(distribution) = Blogs::pending_reward_distribution() {
+    assert!(distribution.epoch < epoch, "distribution of the current epoch");
+    assert!(distribution.paid <= distribution.amount, "overpaid distribution of epoch {}", distribution.epoch);
+    assert!(distribution.next_index <= Blogs::epoch_scored_accounts_count(distribution.epoch), "distribution index");
+  }
+}
+
+/// Verifies global consistency of the Blogs storage. Meant to be called after every dispatch.
+pub fn check_invariants() {
+  check_blog_invariants();
+  check_account_invariants();
+  check_post_invariants();
+  check_comment_invariants();
+  check_message_invariants();
+  check_reward_invariants();
+}
+
+/// Xorshift generator, so that a failing sequence can be replayed from its seed.
+struct Rng(u64);
+
+impl Rng {
+  fn next(&mut self) -> u64 {
+    self.0 ^= self.0 << 13;
+    self.0 ^= self.0 >> 7;
+    self.0 ^= self.0 << 17;
+    self.0
+  }
+
+  fn below(&mut self, n: usize) -> usize {
+    (self.next() % n as u64) as usize
+  }
+
+  fn pick<X: Clone>(&mut self, items: &[X]) -> Option<X> {
+    if items.is_empty() {
+      None
+    } else {
+      Some(items[self.below(items.len())].clone())
+    }
+  }
+}
+
+fn content(n: usize) -> Content {
+  if n % 4 == 3 {
+    return Content::Raw(format!("inline text {}", n).into_bytes());
+  }
+  let hashes: [&[u8]; 3] = [
+    b"QmRAQB6YaCyidP37UdDnjFY5vQuiBrcqdyoW1CuDgwxkD4",
+    b"QmRAQB6YaCyidP37UdDnjFY5vQuiBrcqdyoW2CuDgwxkD4",
+    b"QmYA2fn8cMbVWo4v95RwcwJVyQsNtnEwHerfWR8UNtEwoE",
+  ];
+  Content::IPFS(hashes[n % hashes.len()].to_vec())
+}
+
+/// Up to two tags out of a few, in any case, so that posts share tags.
+fn tags(rng: &mut Rng) -> Vec<Vec<u8>> {
+  (0..rng.below(3)).map(|_| TAGS[rng.below(TAGS.len())].as_bytes().to_vec()).collect()
+}
+
+/// Mentions the other account at most once, but sometimes along with the author.
+fn mentions(rng: &mut Rng, account: AccountId, other: AccountId) -> Vec<Mention<Test>> {
+  match rng.below(3) {
+    0 => vec![],
+    1 => vec![Mention::Account(other)],
+    _ => vec![Mention::Account(other), Mention::Account(account)],
+  }
+}
+
+/// Mostly public posts, so that there is enough to interact with.
+fn visibility(rng: &mut Rng) -> PostVisibility {
+  match rng.below(4) {
+    0 => PostVisibility::Draft,
+    1 => PostVisibility::Unlisted,
+    _ => PostVisibility::Public,
+  }
+}
+
+fn poll(rng: &mut Rng) -> Poll<Test> {
+  Poll {
+    options: vec![b"yes".to_vec(), b"no".to_vec(), b"maybe".to_vec()],
+    ends_at: System::block_number() + 1 + rng.below(20) as u64,
+    multiple_choice: rng.below(2) == 0,
+    weighted_by_reputation: rng.below(2) == 0,
+  }
+}
+
+fn reaction_kind(rng: &mut Rng) -> ReactionKind {
+  if rng.below(2) == 0 { ReactionKind::Upvote } else { ReactionKind::Downvote }
+}
+
+fn authored_posts(account: AccountId) -> Vec<PostId> {
+  post_ids().into_iter().filter(|id| Blogs::post_by_id(*id).unwrap().created.account == account).collect()
+}
+
+fn authored_comments(account: AccountId) -> Vec<CommentId> {
+  comment_ids().into_iter().filter(|id| Blogs::comment_by_id(*id).unwrap().created.account == account).collect()
+}
+
+fn sent_messages(account: AccountId) -> Vec<MessageId> {
+  (1..Blogs::next_message_id())
+    .filter(|id| Blogs::message_by_id(*id).map_or(false, |message| message.created.account == account))
+    .collect()
+}
+
+/// Dispatches one random extrinsic. The result is ignored: failing calls are expected
+/// and must leave the storage consistent as well.
+///
+/// Reputation diffs of comments are tracked per (scorer, author, action) rather than per comment,
+/// so every account authors at most one comment in a sequence.
+fn dispatch_random_call(rng: &mut Rng, step: usize) {
+  let account = rng.pick(&TEST_ACCOUNTS).unwrap();
+  let origin = Origin::signed(account);
+  let other = rng.pick(&TEST_ACCOUNTS).unwrap();
+  let blog = rng.pick(&blog_ids());
+  let post = rng.pick(&post_ids());
+  let comment = rng.pick(&comment_ids());
+
+  let _ = match rng.below(34) {
+    0 => Blogs::create_blog(origin, format!("blog_{}_{}", account, step).into_bytes(), content(step)),
+    1 => match rng.pick(&Blogs::blog_ids_by_owner(account)) {
+      Some(blog_id) => Blogs::update_blog(origin, blog_id, BlogUpdate {
+        writers: None,
+        slug: Some(format!("slug_{}_{}", account, step).into_bytes()),
+        content: Some(content(step)),
+      }),
+      None => Ok(()),
+    },
+    2 => blog.map_or(Ok(()), |blog_id| Blogs::follow_blog(origin, blog_id)),
+    3 => blog.map_or(Ok(()), |blog_id| Blogs::unfollow_blog(origin, blog_id)),
+    4 => Blogs::follow_account(origin, other),
+    5 => Blogs::unfollow_account(origin, other),
+    6 => match blog {
+      Some(blog_id) => {
+        let extension = match (rng.below(4), post, comment) {
+          (1, Some(post_id), _) => PostExtension::SharedPost(post_id),
+          (2, _, Some(comment_id)) => PostExtension::SharedComment(comment_id),
+          (3, _, _) => PostExtension::Poll(poll(rng)),
+          _ => PostExtension::RegularPost,
+        };
+        let post_content = match extension {
+          PostExtension::SharedPost(_) | PostExtension::SharedComment(_) if rng.below(2) == 0 => Content::None,
+          _ => content(step),
+        };
+        let visibility = visibility(rng);
+        let mentions = if visibility == PostVisibility::Draft { vec![] } else { mentions(rng, account, other) };
+        Blogs::create_post(origin, blog_id, post_content, extension, tags(rng), mentions, visibility)
+      },
+      _ => Ok(()),
+    },
+    7 => match rng.pick(&authored_posts(account)) {
+      Some(post_id) => {
+        let update = PostUpdate { blog_id: blog, content: Some(content(step)), tags: Some(tags(rng)) };
+        Blogs::update_post(origin, post_id, update, mentions(rng, account, other))
+      },
+      None => Ok(()),
+    },
+    8 => match post {
+      Some(post_id) if authored_comments(account).is_empty() => {
+        let parent_id = rng.pick(&Blogs::comment_ids_by_post_id(post_id));
+        Blogs::create_comment(origin, post_id, parent_id, content(step), mentions(rng, account, other))
+      },
+      _ => Ok(()),
+    },
+    9 => match rng.pick(&authored_comments(account)) {
+      Some(comment_id) => {
+        let update = CommentUpdate { content: content(step) };
+        Blogs::update_comment(origin, comment_id, update, mentions(rng, account, other))
+      },
+      None => Ok(()),
+    },
+    10 => post.map_or(Ok(()), |post_id| Blogs::create_post_reaction(origin, post_id, reaction_kind(rng))),
+    11 => match post {
+      Some(post_id) if <PostReactionIdByAccount<Test>>::exists((account, post_id)) => {
+        let reaction_id = Blogs::post_reaction_id_by_account((account, post_id));
+        Blogs::update_post_reaction(origin, post_id, reaction_id, reaction_kind(rng))
+      },
+      _ => Ok(()),
+    },
+    12 => match post {
+      Some(post_id) if <PostReactionIdByAccount<Test>>::exists((account, post_id)) => {
+        let reaction_id = Blogs::post_reaction_id_by_account((account, post_id));
+        Blogs::delete_post_reaction(origin, post_id, reaction_id)
+      },
+      _ => Ok(()),
+    },
+    13 => comment.map_or(Ok(()), |comment_id| Blogs::create_comment_reaction(origin, comment_id, reaction_kind(rng))),
+    14 => match comment {
+      Some(comment_id) if <CommentReactionIdByAccount<Test>>::exists((account, comment_id)) => {
+        let reaction_id = Blogs::comment_reaction_id_by_account((account, comment_id));
+        Blogs::update_comment_reaction(origin, comment_id, reaction_id, reaction_kind(rng))
+      },
+      _ => Ok(()),
+    },
+    15 => match comment {
+      Some(comment_id) if <CommentReactionIdByAccount<Test>>::exists((account, comment_id)) => {
+        let reaction_id = Blogs::comment_reaction_id_by_account((account, comment_id));
+        Blogs::delete_comment_reaction(origin, comment_id, reaction_id)
+      },
+      _ => Ok(()),
+    },
+    16 => Blogs::renew_username(origin),
+    17 => {
+      // Let some registrations expire:
+      System::set_block_number(System::block_number() + DEFAULT_USERNAME_REGISTRATION_PERIOD / 4);
+      Blogs::reclaim_expired_username(origin, format!("user{}", rng.below(6)).into_bytes())
+    },
+    18 => if Blogs::account_blocked_by_account((account, other)) {
+      Blogs::unblock_account(origin, other)
+    } else {
+      Blogs::block_account(origin, other)
+    },
+    19 => Blogs::send_message(origin, other, content(step)),
+    20 => rng.pick(&sent_messages(account)).map_or(Ok(()), |message_id| Blogs::delete_message(origin, message_id)),
+    21 => Blogs::mark_conversation_read(origin, other),
+    22 => match post {
+      Some(post_id) => {
+        let options = (0..1 + rng.below(2)).map(|_| rng.below(3) as u16).collect();
+        System::set_block_number(System::block_number() + 1);
+        Blogs::vote_in_poll(origin, post_id, options)
+      },
+      None => Ok(()),
+    },
+    23 => match (rng.pick(&authored_posts(account)), blog) {
+      (Some(post_id), Some(blog_id)) => Blogs::cross_post(origin, post_id, blog_id),
+      _ => Ok(()),
+    },
+    24 => match (post, blog) {
+      (Some(post_id), Some(blog_id)) => Blogs::remove_cross_post(origin, post_id, blog_id),
+      _ => Ok(()),
+    },
+    25 => match (blog, post) {
+      (Some(blog_id), Some(post_id)) if Blogs::pinned_post_ids(blog_id).contains(&post_id) => Blogs::unpin_post(origin, blog_id, post_id),
+      (Some(blog_id), Some(post_id)) => Blogs::pin_post(origin, blog_id, post_id),
+      _ => Ok(()),
+    },
+    26 => match comment.and_then(|comment_id| Blogs::comment_by_id(comment_id)) {
+      Some(ref comment) if Blogs::pinned_comment_ids(comment.post_id).contains(&comment.id) => {
+        Blogs::unpin_comment(origin, comment.post_id, comment.id)
+      },
+      Some(comment) => Blogs::pin_comment(origin, comment.post_id, comment.id),
+      None => Ok(()),
+    },
+    27 => match post.map(Bookmark::Post) {
+      Some(item) => if Blogs::bookmarks_by_account(account).contains(&item) {
+        Blogs::unbookmark(origin, item)
+      } else {
+        Blogs::bookmark(origin, item)
+      },
+      None => Ok(()),
+    },
+    28 => rng.pick(&authored_posts(account)).map_or(Ok(()), |post_id| Blogs::publish_post(origin, post_id)),
+    29 => match rng.pick(&authored_posts(account)) {
+      Some(post_id) => Blogs::invite_co_author(origin, post_id, other, 10 + rng.below(40) as u8),
+      None => Ok(()),
+    },
+    30 => match post {
+      Some(post_id) if rng.below(3) == 0 => Blogs::remove_co_author_invite(origin, post_id, other),
+      Some(post_id) => Blogs::accept_co_authorship(origin, post_id),
+      None => Ok(()),
+    },
+    31 => {
+      let now = System::block_number() + 1;
+      System::set_block_number(now);
+      Blogs::on_finalize(now);
+      Ok(())
+    },
+    32 => match rng.below(3) {
+      0 => Blogs::lock_voting_stake(origin, 1 + rng.below(300) as u64),
+      1 => Blogs::unbond_voting_stake(origin, 1 + rng.below(300) as u64),
+      _ => Blogs::withdraw_unbonded_stake(origin),
+    },
+    _ => {
+      let username_prefix = if rng.below(2) == 0 { "user" } else { "User" };
+      let username = format!("{}{}", username_prefix, rng.below(6)).into_bytes();
+      if Blogs::social_account_by_id(account).map_or(false, |social_account| social_account.profile.is_some()) {
+        Blogs::update_profile(origin, ProfileUpdate { username: Some(username), content: Some(content(step)) })
+      } else {
+        Blogs::create_profile(origin, username, content(step))
+      }
+    },
+  };
+}
+
+#[test]
+fn invariants_should_hold_for_random_call_sequences() {
+  const SEQUENCES: u64 = 32;
+  const STEPS: usize = 150;
+
+  for seed in 1..=SEQUENCES {
+    with_externalities(&mut build_ext(), || {
+      let mut rng = Rng(seed);
+      // Short epochs, so that rewards are paid out within a sequence:
+      assert_eq!(Blogs::set_reward_epoch(system::RawOrigin::Root.into(), 5, 2), Ok(()));
+      assert_eq!(Blogs::mint_to_reward_pool(system::RawOrigin::Root.into(), 1_000), Ok(()));
+      // Stake counts as much as reputation and can be unbonded within a sequence:
+      assert_eq!(Blogs::set_voting_stake_params(system::RawOrigin::Root.into(), 50, 10, 3), Ok(()));
+      for account in TEST_ACCOUNTS.iter() {
+        let _ = Balances::deposit_creating(account, 1_000);
+      }
+      for step in 0..STEPS {
+        dispatch_random_call(&mut rng, step);
+        check_invariants();
+      }
+    });
+  }
+}
@@ -0,0 +1,593 @@
+//! Storage layout of the last deployed runtime (spec version 2), used only to migrate stored values:
+//! blogs, posts, comments and profiles that kept a raw IPFS hash, their counters and edit history inline.
+
+use super::blogs::{
+  self, Trait, Module, Change, PostExtension, PostVisibility, ScoringAction, LegacyMigration, LegacyMigrationStep,
+  Blog, BlogCounters, BlogUpdate, BlogHistoryRecord,
+  Post, PostCounters, PostUpdate, PostHistoryRecord,
+  Comment, CommentCounters, CommentUpdate, CommentHistoryRecord,
+  SocialAccount, Profile, ProfileUpdate, ProfileHistoryRecord,
+};
+use super::content::Content;
+
+use rstd::prelude::*;
+use rstd::marker::PhantomData;
+use parity_codec_derive::{Encode, Decode};
+use srml_support::{StorageMap, decl_storage};
+use runtime_primitives::traits::As;
+
+#[derive(Encode, Decode)]
+pub struct LegacyBlog<T: Trait> {
+  pub id: T::BlogId,
+  pub created: Change<T>,
+  pub updated: Option<Change<T>>,
+  pub writers: Vec<T::AccountId>,
+  pub slug: Vec<u8>,
+  pub ipfs_hash: Vec<u8>,
+  pub posts_count: u16,
+  pub followers_count: u32,
+  pub edit_history: Vec<LegacyBlogHistoryRecord<T>>,
+  pub score: i32,
+}
+
+#[derive(Encode, Decode)]
+pub struct LegacyBlogUpdate<T: Trait> {
+  pub writers: Option<Vec<T::AccountId>>,
+  pub slug: Option<Vec<u8>>,
+  pub ipfs_hash: Option<Vec<u8>>,
+}
+
+#[derive(Encode, Decode)]
+pub struct LegacyBlogHistoryRecord<T: Trait> {
+  pub edited: Change<T>,
+  pub old_data: LegacyBlogUpdate<T>,
+}
+
+#[derive(Encode, Decode)]
+pub struct LegacyPost<T: Trait> {
+  pub id: T::PostId,
+  pub blog_id: T::BlogId,
+  pub created: Change<T>,
+  pub updated: Option<Change<T>>,
+  pub extension: PostExtension<T>,
+  pub ipfs_hash: Vec<u8>,
+  pub comments_count: u16,
+  pub upvotes_count: u16,
+  pub downvotes_count: u16,
+  pub shares_count: u16,
+  pub edit_history: Vec<LegacyPostHistoryRecord<T>>,
+  pub score: i32,
+}
+
+#[derive(Encode, Decode)]
+pub struct LegacyPostUpdate<T: Trait> {
+  pub blog_id: Option<T::BlogId>,
+  pub ipfs_hash: Option<Vec<u8>>,
+}
+
+#[derive(Encode, Decode)]
+pub struct LegacyPostHistoryRecord<T: Trait> {
+  pub edited: Change<T>,
+  pub old_data: LegacyPostUpdate<T>,
+}
+
+#[derive(Encode, Decode)]
+pub struct LegacyComment<T: Trait> {
+  pub id: T::CommentId,
+  pub parent_id: Option<T::CommentId>,
+  pub post_id: T::PostId,
+  pub created: Change<T>,
+  pub updated: Option<Change<T>>,
+  pub ipfs_hash: Vec<u8>,
+  pub upvotes_count: u16,
+  pub downvotes_count: u16,
+  pub shares_count: u16,
+  pub direct_replies_count: u16,
+  pub edit_history: Vec<LegacyCommentHistoryRecord<T>>,
+  pub score: i32,
+}
+
+#[derive(Encode, Decode)]
+pub struct LegacyCommentUpdate {
+  pub ipfs_hash: Vec<u8>,
+}
+
+#[derive(Encode, Decode)]
+pub struct LegacyCommentHistoryRecord<T: Trait> {
+  pub edited: Change<T>,
+  pub old_data: LegacyCommentUpdate,
+}
+
+#[derive(Encode, Decode)]
+pub struct LegacySocialAccount<T: Trait> {
+  pub followers_count: u32,
+  pub following_accounts_count: u16,
+  pub following_blogs_count: u16,
+  pub reputation: u32,
+  pub profile: Option<LegacyProfile<T>>,
+}
+
+#[derive(Encode, Decode)]
+pub struct LegacyProfile<T: Trait> {
+  pub created: Change<T>,
+  pub updated: Option<Change<T>>,
+  pub username: Vec<u8>,
+  pub ipfs_hash: Vec<u8>,
+  pub edit_history: Vec<LegacyProfileHistoryRecord<T>>,
+}
+
+#[derive(Encode, Decode)]
+pub struct LegacyProfileUpdate {
+  pub username: Option<Vec<u8>>,
+  pub ipfs_hash: Option<Vec<u8>>,
+}
+
+#[derive(Encode, Decode)]
+pub struct LegacyProfileHistoryRecord<T: Trait> {
+  pub edited: Change<T>,
+  pub old_data: LegacyProfileUpdate,
+}
+
+/// Reads the same storage keys as the Blogs module, but with the legacy value types.
+pub struct LegacyStorage<T: Trait>(PhantomData<T>);
+
+decl_storage! {
+  trait Store for LegacyStorage<T: Trait> as Blogs {
+    pub BlogById get(blog_by_id): map T::BlogId => Option<LegacyBlog<T>>;
+    pub PostById get(post_by_id): map T::PostId => Option<LegacyPost<T>>;
+    pub CommentById get(comment_by_id): map T::CommentId => Option<LegacyComment<T>>;
+    pub SocialAccountById get(social_account_by_id): map T::AccountId => Option<LegacySocialAccount<T>>;
+  }
+}
+
+/// Shared posts had an empty hash.
+fn legacy_content(ipfs_hash: Vec<u8>) -> Content {
+  if ipfs_hash.is_empty() {
+    Content::None
+  } else {
+    Content::IPFS(ipfs_hash)
+  }
+}
+
+fn decimal(mut number: u64) -> Vec<u8> {
+  let mut digits = Vec::new();
+  loop {
+    digits.push(b'0' + (number % 10) as u8);
+    number /= 10;
+    if number == 0 {
+      break;
+    }
+  }
+  digits.reverse();
+  digits
+}
+
+impl<T: Trait> Module<T> {
+
+  /// Starts rewriting everything stored by spec version 2 in the current layout, which is then done
+  /// in chunks of `LegacyMigrationChunkLen` items per block. Legacy values do not decode in the current
+  /// layout, so calls on the items that are not migrated yet fail until the migration reaches them.
+  pub fn start_legacy_migration() {
+    <blogs::LegacyLayoutUpgradedAt<T>>::put(<system::Module<T>>::block_number());
+    <blogs::PendingLegacyMigration<T>>::put(LegacyMigration {
+      step: LegacyMigrationStep::Blogs(T::BlogId::sa(1)),
+      next_blog_id: Self::next_blog_id(),
+      next_post_id: Self::next_post_id(),
+      next_comment_id: Self::next_comment_id(),
+      next_reaction_id: Self::next_reaction_id(),
+    });
+  }
+
+  /// Migrates the next chunk of items. Each item is migrated only once,
+  /// as some values in the current layout still decode as legacy ones.
+  pub fn migrate_legacy_chunk() {
+    let mut migration = match Self::pending_legacy_migration() {
+      Some(migration) => migration,
+      None => return,
+    };
+
+    for _ in 0..Self::legacy_migration_chunk_len() {
+      match Self::migrate_legacy_item(&migration) {
+        Some(step) => migration.step = step,
+        None => {
+          <blogs::LegacyAccountsCount<T>>::kill();
+          <blogs::PendingLegacyMigration<T>>::kill();
+          return;
+        },
+      }
+    }
+    <blogs::PendingLegacyMigration<T>>::put(migration);
+  }
+
+  /// Migrates the item of the current step and returns the next step, or `None` once all items are migrated.
+  /// Slug and username keys are migrated in two passes, as a legacy key may be the lowercase key of another item.
+  fn migrate_legacy_item(migration: &LegacyMigration<T>) -> Option<LegacyMigrationStep<T>> {
+    let next_step = match migration.step.clone() {
+      LegacyMigrationStep::Blogs(blog_id) if blog_id < migration.next_blog_id => {
+        Self::migrate_legacy_blog(blog_id);
+        LegacyMigrationStep::Blogs(blog_id + T::BlogId::sa(1))
+      },
+      LegacyMigrationStep::Blogs(_) => LegacyMigrationStep::LegacySlugKeys(T::BlogId::sa(1)),
+
+      LegacyMigrationStep::LegacySlugKeys(blog_id) if blog_id < migration.next_blog_id => {
+        Self::remove_legacy_slug_key(blog_id);
+        LegacyMigrationStep::LegacySlugKeys(blog_id + T::BlogId::sa(1))
+      },
+      LegacyMigrationStep::LegacySlugKeys(_) => LegacyMigrationStep::SlugKeys(T::BlogId::sa(1)),
+
+      LegacyMigrationStep::SlugKeys(blog_id) if blog_id < migration.next_blog_id => {
+        Self::migrate_slug_key(blog_id);
+        LegacyMigrationStep::SlugKeys(blog_id + T::BlogId::sa(1))
+      },
+      LegacyMigrationStep::SlugKeys(_) => LegacyMigrationStep::Posts(T::PostId::sa(1)),
+
+      LegacyMigrationStep::Posts(post_id) if post_id < migration.next_post_id => {
+        Self::migrate_legacy_post(post_id);
+        LegacyMigrationStep::Posts(post_id + T::PostId::sa(1))
+      },
+      LegacyMigrationStep::Posts(_) => LegacyMigrationStep::Comments(T::CommentId::sa(1)),
+
+      LegacyMigrationStep::Comments(comment_id) if comment_id < migration.next_comment_id => {
+        Self::migrate_legacy_comment(comment_id);
+        LegacyMigrationStep::Comments(comment_id + T::CommentId::sa(1))
+      },
+      LegacyMigrationStep::Comments(_) => LegacyMigrationStep::Reactions(T::ReactionId::sa(1)),
+
+      LegacyMigrationStep::Reactions(reaction_id) if reaction_id < migration.next_reaction_id => {
+        if let Some(reaction) = Self::reaction_by_id(reaction_id) {
+          Self::find_legacy_account(reaction.created.account);
+        }
+        LegacyMigrationStep::Reactions(reaction_id + T::ReactionId::sa(1))
+      },
+      LegacyMigrationStep::Reactions(_) => LegacyMigrationStep::PostScores(T::PostId::sa(1)),
+
+      LegacyMigrationStep::PostScores(post_id) if post_id < migration.next_post_id => {
+        Self::migrate_post_reputation_diffs(post_id);
+        LegacyMigrationStep::PostScores(post_id + T::PostId::sa(1))
+      },
+      LegacyMigrationStep::PostScores(_) => LegacyMigrationStep::Profiles(0),
+
+      // Accounts reachable only by following, in either direction, are queued along the way:
+      LegacyMigrationStep::Profiles(index) if index < Self::legacy_accounts_count() => {
+        if let Some(account) = Self::legacy_account(index) {
+          let _ = Self::migrate_profile_history(account.clone());
+          let followers = Self::account_followers(account.clone());
+          for other in followers.into_iter().chain(Self::accounts_followed_by_account(account)) {
+            Self::find_legacy_account(other);
+          }
+        }
+        LegacyMigrationStep::Profiles(index + 1)
+      },
+      LegacyMigrationStep::Profiles(_) => LegacyMigrationStep::LegacyUsernameKeys(0),
+
+      LegacyMigrationStep::LegacyUsernameKeys(index) if index < Self::legacy_accounts_count() => {
+        if let Some(account) = Self::legacy_account(index) {
+          Self::remove_legacy_username_key(account);
+        }
+        LegacyMigrationStep::LegacyUsernameKeys(index + 1)
+      },
+      LegacyMigrationStep::LegacyUsernameKeys(_) => LegacyMigrationStep::UsernameKeys(0),
+
+      LegacyMigrationStep::UsernameKeys(index) if index < Self::legacy_accounts_count() => {
+        if let Some(account) = <blogs::LegacyAccount<T>>::take(index) {
+          <blogs::IsLegacyAccountFound<T>>::remove(account.clone());
+          Self::migrate_username_key(account);
+        }
+        LegacyMigrationStep::UsernameKeys(index + 1)
+      },
+      LegacyMigrationStep::UsernameKeys(_) => return None,
+    };
+
+    Some(next_step)
+  }
+
+  /// Social accounts can not be enumerated, so these are the accounts that have left a trace
+  /// in enumerable storage: owners, writers, authors, reactors and followers.
+  fn find_legacy_account(account: T::AccountId) {
+    if Self::is_legacy_account_found(account.clone()) {
+      return;
+    }
+
+    let index = Self::legacy_accounts_count();
+    <blogs::IsLegacyAccountFound<T>>::insert(account.clone(), true);
+    <blogs::LegacyAccount<T>>::insert(index, account);
+    <blogs::LegacyAccountsCount<T>>::put(index + 1);
+  }
+
+  fn migrate_legacy_blog(blog_id: T::BlogId) {
+    let legacy = match <BlogById<T>>::get(blog_id) {
+      Some(legacy) => legacy,
+      None => return,
+    };
+
+    for record in legacy.edit_history {
+      Self::add_blog_revision(blog_id, BlogHistoryRecord {
+        edited: record.edited,
+        old_data: BlogUpdate {
+          writers: record.old_data.writers,
+          slug: record.old_data.slug,
+          content: record.old_data.ipfs_hash.map(legacy_content),
+        },
+      });
+    }
+    <blogs::BlogCountersById<T>>::insert(blog_id, BlogCounters {
+      posts_count: legacy.posts_count,
+      followers_count: legacy.followers_count,
+      score: legacy.score,
+    });
+
+    Self::find_legacy_account(legacy.created.account.clone());
+    for account in legacy.writers.iter().cloned().chain(Self::blog_followers(blog_id)) {
+      Self::find_legacy_account(account);
+    }
+
+    <blogs::BlogById<T>>::insert(blog_id, Blog {
+      id: legacy.id,
+      created: legacy.created,
+      updated: legacy.updated,
+      writers: legacy.writers,
+      slug: legacy.slug,
+      content: legacy_content(legacy.ipfs_hash),
+    });
+  }
+
+  fn remove_legacy_slug_key(blog_id: T::BlogId) {
+    if let Some(blog) = Self::blog_by_id(blog_id) {
+      if Self::blog_id_by_slug(blog.slug.clone()) == Some(blog_id) {
+        <blogs::BlogIdBySlug<T>>::remove(blog.slug);
+      }
+    }
+  }
+
+  /// Slugs were indexed as they were typed, but are stored and looked up in lowercase now.
+  /// Of the blogs whose slugs differ only in case, the earliest one keeps it and the others
+  /// get their id appended to it, which is recorded in their edit history.
+  fn migrate_slug_key(blog_id: T::BlogId) {
+    let mut blog = match Self::blog_by_id(blog_id) {
+      Some(blog) => blog,
+      None => return,
+    };
+
+    let mut slug = Self::to_lowercase(blog.slug.clone());
+    while Self::blog_id_by_slug(slug.clone()).map_or(false, |holder| holder != blog_id) {
+      slug.push(b'-');
+      slug.extend(decimal(<T::BlogId as As<u64>>::as_(blog_id)));
+    }
+    <blogs::BlogIdBySlug<T>>::insert(slug.clone(), blog_id);
+
+    if slug != blog.slug {
+      if slug != Self::to_lowercase(blog.slug.clone()) {
+        let owner = blog.created.account.clone();
+        Self::add_blog_revision(blog_id, BlogHistoryRecord {
+          edited: Self::new_change(owner.clone()),
+          old_data: BlogUpdate { writers: None, slug: Some(blog.slug), content: None },
+        });
+        blog.updated = Some(Self::new_change(owner));
+      }
+      blog.slug = slug;
+      <blogs::BlogById<T>>::insert(blog_id, blog);
+    }
+  }
+
+  /// Every post was listed in its blog before, so all of them become public.
+  fn migrate_legacy_post(post_id: T::PostId) {
+    let legacy = match <PostById<T>>::get(post_id) {
+      Some(legacy) => legacy,
+      None => return,
+    };
+
+    for record in legacy.edit_history {
+      Self::add_post_revision(post_id, PostHistoryRecord {
+        edited: record.edited,
+        old_data: PostUpdate {
+          blog_id: record.old_data.blog_id,
+          content: record.old_data.ipfs_hash.map(legacy_content),
+          tags: None,
+        },
+      });
+    }
+    <blogs::PostCountersById<T>>::insert(post_id, PostCounters {
+      comments_count: legacy.comments_count,
+      upvotes_count: legacy.upvotes_count,
+      downvotes_count: legacy.downvotes_count,
+      shares_count: legacy.shares_count,
+      score: legacy.score,
+    });
+
+    <blogs::PostScoreInBlog<T>>::insert((legacy.blog_id, post_id), legacy.score);
+
+    Self::find_legacy_account(legacy.created.account.clone());
+    <blogs::PostById<T>>::insert(post_id, Post {
+      id: legacy.id,
+      blog_id: legacy.blog_id,
+      created: legacy.created,
+      updated: legacy.updated,
+      extension: legacy.extension,
+      content: legacy_content(legacy.ipfs_hash),
+      tags: vec![],
+      visibility: PostVisibility::Public,
+    });
+  }
+
+  fn migrate_legacy_comment(comment_id: T::CommentId) {
+    let legacy = match <CommentById<T>>::get(comment_id) {
+      Some(legacy) => legacy,
+      None => return,
+    };
+
+    for record in legacy.edit_history {
+      Self::add_comment_revision(comment_id, CommentHistoryRecord {
+        edited: record.edited,
+        old_data: CommentUpdate { content: legacy_content(record.old_data.ipfs_hash) },
+      });
+    }
+    <blogs::CommentCountersById<T>>::insert(comment_id, CommentCounters {
+      upvotes_count: legacy.upvotes_count,
+      downvotes_count: legacy.downvotes_count,
+      shares_count: legacy.shares_count,
+      direct_replies_count: legacy.direct_replies_count,
+      score: legacy.score,
+    });
+
+    Self::find_legacy_account(legacy.created.account.clone());
+    <blogs::CommentById<T>>::insert(comment_id, Comment {
+      id: legacy.id,
+      parent_id: legacy.parent_id,
+      post_id: legacy.post_id,
+      created: legacy.created,
+      updated: legacy.updated,
+      content: legacy_content(legacy.ipfs_hash),
+    });
+  }
+
+  /// Reputation given by scoring a post was kept per scorer and author, but is reverted per post now.
+  /// The scorers are the accounts that reacted to, commented on or shared the post. Posts had no co-authors,
+  /// so the whole legacy diff goes to the creator, or the score diff if the legacy one was overwritten.
+  fn migrate_post_reputation_diffs(post_id: T::PostId) {
+    let author = match Self::post_by_id(post_id) {
+      Some(post) => post.created.account,
+      None => return,
+    };
+
+    let reactors = Self::reaction_ids_by_post_id(post_id).into_iter()
+      .filter_map(|reaction_id| Self::reaction_by_id(reaction_id))
+      .map(|reaction| reaction.created.account);
+    let commenters = Self::comment_ids_by_post_id(post_id).into_iter()
+      .filter_map(|comment_id| Self::comment_by_id(comment_id))
+      .map(|comment| comment.created.account);
+    let sharers = Self::shared_post_ids_by_original_post_id(post_id).into_iter()
+      .filter_map(|shared_post_id| Self::post_by_id(shared_post_id))
+      .map(|shared_post| shared_post.created.account);
+    let scorers: Vec<T::AccountId> = reactors.chain(commenters).chain(sharers).collect();
+
+    let actions = [ScoringAction::UpvotePost, ScoringAction::DownvotePost, ScoringAction::SharePost, ScoringAction::CreateComment];
+    for scorer in scorers {
+      for action in actions.iter().cloned() {
+        let score_diff = match Self::post_score_by_account((scorer.clone(), post_id, action)) {
+          Some(score_diff) => score_diff,
+          None => continue,
+        };
+        let key = (scorer.clone(), post_id, action, author.clone());
+        if Self::post_reputation_diff_by_account(key.clone()).is_some() {
+          continue;
+        }
+        let reputation_diff = <blogs::AccountReputationDiffByAccount<T>>::take((scorer.clone(), author.clone(), action))
+          .unwrap_or(score_diff);
+        <blogs::PostReputationDiffByAccount<T>>::insert(key, reputation_diff);
+      }
+    }
+  }
+
+  /// Usernames were indexed as they were typed, but are looked up in lowercase now.
+  /// Accounts that are already indexed in lowercase stay as they are.
+  pub fn migrate_username_keys(accounts: &[T::AccountId]) {
+    for account in accounts.iter().cloned() {
+      Self::remove_legacy_username_key(account);
+    }
+    for account in accounts.iter().cloned() {
+      Self::migrate_username_key(account);
+    }
+  }
+
+  fn remove_legacy_username_key(account: T::AccountId) {
+    if let Some(profile) = Self::social_account_by_id(account.clone()).and_then(|social_account| social_account.profile) {
+      if Self::account_by_profile_username(profile.username.clone()) == Some(account) {
+        <blogs::AccountByProfileUsername<T>>::remove(profile.username);
+      }
+    }
+  }
+
+  /// Of the profiles whose usernames differ only in case, the earliest created one keeps it
+  /// and the others lose theirs, whatever order they are migrated in.
+  fn migrate_username_key(account: T::AccountId) {
+    let profile = match Self::social_account_by_id(account.clone()).and_then(|social_account| social_account.profile) {
+      Some(profile) => profile,
+      None => return,
+    };
+    if profile.username.is_empty() {
+      return;
+    }
+
+    let username_key = Self::to_lowercase(profile.username);
+    if let Some(holder) = Self::account_by_profile_username(username_key.clone()) {
+      if holder == account {
+        return;
+      }
+      let holder_created = Self::social_account_by_id(holder.clone())
+        .and_then(|social_account| social_account.profile)
+        .map(|holder_profile| holder_profile.created.block);
+      match holder_created {
+        Some(block) if (profile.created.block, &account) < (block, &holder) => Self::drop_username(holder),
+        _ => {
+          Self::drop_username(account);
+          return;
+        },
+      }
+    }
+    <blogs::AccountByProfileUsername<T>>::insert(username_key, account);
+  }
+
+  /// Clears the username of a profile that lost it to another one, recording it in the edit history.
+  fn drop_username(account: T::AccountId) {
+    let mut social_account = match Self::social_account_by_id(account.clone()) {
+      Some(social_account) => social_account,
+      None => return,
+    };
+    if let Some(mut profile) = social_account.profile {
+      Self::add_profile_revision(account.clone(), ProfileHistoryRecord {
+        edited: Self::new_change(account.clone()),
+        old_data: ProfileUpdate { username: Some(profile.username), content: None },
+      });
+      profile.username = vec![];
+      profile.updated = Some(Self::new_change(account.clone()));
+      social_account.profile = Some(profile);
+      <blogs::SocialAccountById<T>>::insert(account, social_account);
+    }
+  }
+
+  /// Moves the inline edit history of a profile to `ProfileHistory` and returns whether it did. A legacy
+  /// social account without a profile already decodes in the new layout, so it is left as is. So are
+  /// the profiles migrated before and the ones created after the upgrade, as they may decode as legacy ones.
+  pub fn migrate_profile_history(account: T::AccountId) -> bool {
+    let upgraded_at = match Self::legacy_layout_upgraded_at() {
+      Some(upgraded_at) => upgraded_at,
+      None => return false,
+    };
+    if Self::is_profile_migrated(account.clone()) {
+      return false;
+    }
+
+    let legacy = match <SocialAccountById<T>>::get(account.clone()) {
+      Some(legacy) => legacy,
+      None => return false,
+    };
+    let legacy_profile = match legacy.profile {
+      Some(profile) if profile.created.block < upgraded_at => profile,
+      _ => return false,
+    };
+
+    for record in legacy_profile.edit_history {
+      Self::add_profile_revision(account.clone(), ProfileHistoryRecord {
+        edited: record.edited,
+        old_data: ProfileUpdate {
+          username: record.old_data.username,
+          content: record.old_data.ipfs_hash.map(legacy_content),
+        },
+      });
+    }
+    <blogs::IsProfileMigrated<T>>::insert(account.clone(), true);
+    <blogs::SocialAccountById<T>>::insert(account, SocialAccount {
+      followers_count: legacy.followers_count,
+      following_accounts_count: legacy.following_accounts_count,
+      following_blogs_count: legacy.following_blogs_count,
+      reputation: legacy.reputation,
+      profile: Some(Profile {
+        created: legacy_profile.created,
+        updated: legacy_profile.updated,
+        username: legacy_profile.username,
+        content: legacy_content(legacy_profile.ipfs_hash),
+      }),
+    });
+    true
+  }
+}
@@ -2,6 +2,8 @@ pub const MSG_BLOG_NOT_FOUND: &str = "Blog was not found by id";
 pub const MSG_BLOG_SLUG_IS_TOO_SHORT: &str = "Blog slug is too short";
 pub const MSG_BLOG_SLUG_IS_TOO_LONG: &str = "Blog slug is too long";
 pub const MSG_BLOG_SLUG_IS_NOT_UNIQUE: &str = "Blog slug is not unique";
+pub const MSG_BLOG_SLUG_HAS_INVALID_CHARS: &str = "Blog slug can contain only latin letters, digits, dashes and underscores";
+pub const MSG_BLOG_SLUG_IS_RESERVED: &str = "Blog slug is reserved";
 pub const MSG_NOTHING_TO_UPDATE_IN_BLOG: &str = "Nothing to update in a blog";
 pub const MSG_ONLY_BLOG_OWNER_CAN_UPDATE_BLOG: &str = "Only a blog owner can update their blog";
 
@@ -10,11 +12,15 @@ pub const MSG_NOTHING_TO_UPDATE_IN_POST: &str = "Nothing to update in a post";
 pub const MSG_ONLY_POST_OWNER_CAN_UPDATE_POST: &str = "Only post owner can update their post";
 pub const MSG_OVERFLOW_ADDING_POST_ON_BLOG: &str = "Overflow adding a post to a blog";
 pub const MSG_UNDERFLOW_REMOVING_POST_FROM_BLOG: &str = "Underflow caused by removing a post from its current blog";
+pub const MSG_POST_IS_NOT_PUBLIC: &str = "Post is a draft or unlisted";
+pub const MSG_POST_IS_ALREADY_PUBLIC: &str = "Post is already public";
+pub const MSG_ONLY_POST_OWNER_CAN_PUBLISH_POST: &str = "Only post owner can publish their post";
+pub const MSG_CANNOT_MENTION_IN_DRAFT: &str = "Accounts can not be mentioned in a draft";
 
 pub const MSG_COMMENT_NOT_FOUND: &str = "Comment was not found by id";
 pub const MSG_UNKNOWN_PARENT_COMMENT: &str = "Unknown parent comment id";
 pub const MSG_ONLY_COMMENT_AUTHOR_CAN_UPDATE_COMMENT: &str = "Only comment author can update their comment";
-pub const MSG_NEW_COMMENT_HASH_DO_NOT_DIFFER: &str = "New comment IPFS-hash is the same as old one";
+pub const MSG_NEW_COMMENT_CONTENT_DO_NOT_DIFFER: &str = "New comment content is the same as old one";
 pub const MSG_OVERFLOW_ADDING_COMMENT_ON_POST: &str = "Overflow adding comment on post";
 pub const MSG_OVERFLOW_REPLYING_ON_COMMENT: &str = "Overflow replying on comment";
 
@@ -47,7 +53,13 @@ pub const MSG_SOCIAL_ACCOUNT_NOT_FOUND: &str = "Social account was not found by
 pub const MSG_FOLLOWER_ACCOUNT_NOT_FOUND: &str = "Follower social account was not found by id";
 pub const MSG_FOLLOWED_ACCOUNT_NOT_FOUND: &str = "Followed social account was not found by id";
 
-pub const MSG_IPFS_IS_INCORRECT: &str = "IPFS-hash is not correct";
+pub const MSG_CONTENT_IS_EMPTY: &str = "Content should not be empty";
+pub const MSG_RAW_CONTENT_IS_NOT_ALLOWED: &str = "Inline content is not allowed here";
+pub const MSG_RAW_CONTENT_IS_TOO_LONG: &str = "Inline content is too long";
+pub const MSG_RAW_CONTENT_IS_NOT_UTF8: &str = "Inline content is not a valid UTF-8 text";
+pub const MSG_IPFS_IS_INCORRECT: &str = "IPFS CID is not correct";
+pub const MSG_HYPER_HASH_IS_INCORRECT: &str = "Hypercore key is not correct";
+pub const MSG_ARWEAVE_HASH_IS_INCORRECT: &str = "Arweave transaction id is not correct";
 
 pub const MSG_OUT_OF_BOUNDS_UPDATING_BLOG_SCORE: &str = "Out of bounds updating blog score";
 pub const MSG_OUT_OF_BOUNDS_REVERTING_BLOG_SCORE: &str = "Out of bounds reverting blog score";
@@ -61,15 +73,130 @@ pub const MSG_REPUTATION_DIFF_NOT_FOUND: &str = "Scored account reputation diffe
 pub const MSG_ORIGINAL_POST_NOT_FOUND: &str = "Original post not found when sharing";
 pub const MSG_OVERFLOW_TOTAL_SHARES_SHARING_POST: &str = "Overflow total shares counter when sharing post";
 pub const MSG_OVERFLOW_POST_SHARES_BY_ACCOUNT: &str = "Overflow shares by account counter when sharing post";
-pub const MSG_CANNOT_SHARE_SHARED_POST: &str = "Cannot share post that is not regular post";
+pub const MSG_CANNOT_SHARE_SHARED_POST: &str = "Shared post can only be quoted with own content";
+pub const MSG_SHARE_SHOULD_BE_PUBLIC: &str = "A share can not be a draft or unlisted post";
+pub const MSG_QUOTE_CHAIN_IS_TOO_LONG: &str = "Too many shares are quoting each other";
 pub const MSG_ORIGINAL_COMMENT_NOT_FOUND: &str = "Original comment not found when sharing";
 pub const MSG_OVERFLOW_TOTAL_SHARES_SHARING_COMMENT: &str = "Overflow total shares counter when sharing comment";
 pub const MSG_OVERFLOW_COMMENT_SHARES_BY_ACCOUNT: &str = "Overflow shares by account counter when sharing comment";
 
+pub const MSG_NOTIFICATION_NOT_FOUND: &str = "Notification was not found by index";
+pub const MSG_NOTIFICATIONS_ARE_ALREADY_READ: &str = "Notifications up to this index are already read";
+
+pub const MSG_ACCOUNT_CANNOT_BLOCK_ITSELF: &str = "Account cannot block itself";
+pub const MSG_ACCOUNT_IS_ALREADY_BLOCKED: &str = "Account is already blocked";
+pub const MSG_ACCOUNT_IS_NOT_BLOCKED: &str = "Account is not blocked";
+
+pub const MSG_ACCOUNT_CANNOT_MESSAGE_ITSELF: &str = "Account cannot send a message to itself";
+pub const MSG_SENDER_IS_BLOCKED_BY_RECIPIENT: &str = "Sender is blocked by the recipient";
+pub const MSG_MESSAGE_NOT_FOUND: &str = "Message was not found by id";
+pub const MSG_ONLY_MESSAGE_SENDER_CAN_DELETE_MESSAGE: &str = "Only a message sender can delete their message";
+pub const MSG_NO_UNREAD_MESSAGES_IN_CONVERSATION: &str = "There are no unread messages in this conversation";
+
+pub const MSG_POLL_HAS_TOO_FEW_OPTIONS: &str = "Poll should have at least two options";
+pub const MSG_POLL_HAS_TOO_MANY_OPTIONS: &str = "Poll has too many options";
+pub const MSG_POLL_OPTION_IS_EMPTY: &str = "Poll option is empty";
+pub const MSG_POLL_OPTION_IS_TOO_LONG: &str = "Poll option is too long";
+pub const MSG_POLL_OPTION_IS_NOT_UTF8: &str = "Poll option is not a valid UTF-8 string";
+pub const MSG_POLL_ENDS_IN_PAST: &str = "Poll should end after the current block";
+pub const MSG_POST_IS_NOT_POLL: &str = "Post is not a poll";
+pub const MSG_POLL_IS_CLOSED: &str = "Poll is closed";
+pub const MSG_ACCOUNT_ALREADY_VOTED_IN_POLL: &str = "Account has already voted in this poll";
+pub const MSG_NO_POLL_OPTIONS_CHOSEN: &str = "No poll options are chosen";
+pub const MSG_POLL_IS_SINGLE_CHOICE: &str = "Only one option can be chosen in this poll";
+pub const MSG_POLL_OPTION_NOT_FOUND: &str = "Poll option was not found by index";
+pub const MSG_POLL_OPTION_IS_CHOSEN_TWICE: &str = "Poll option is chosen more than once";
+
+pub const MSG_ONLY_POST_OWNER_CAN_CROSS_POST: &str = "Only post owner can cross-post it";
+pub const MSG_NO_PERMISSION_TO_CROSS_POST: &str = "Only owner or writers of a blog can cross-post to it";
+pub const MSG_POST_IS_ALREADY_IN_BLOG: &str = "Post is already listed in this blog";
+pub const MSG_TOO_MANY_CROSS_POSTS: &str = "Post is cross-posted to too many blogs";
+pub const MSG_NO_PERMISSION_TO_REMOVE_CROSS_POST: &str = "Only post owner or blog owner can remove a cross-post";
+pub const MSG_POST_IS_NOT_CROSS_POSTED_TO_BLOG: &str = "Post is not cross-posted to this blog";
+
+pub const MSG_ONLY_POST_OWNER_CAN_INVITE_CO_AUTHOR: &str = "Only post owner can invite co-authors";
+pub const MSG_POST_OWNER_CANNOT_BE_CO_AUTHOR: &str = "Post owner can not be a co-author of their post";
+pub const MSG_CO_AUTHOR_SHARE_IS_ZERO: &str = "Co-author share should be greater than zero";
+pub const MSG_POST_OWNER_IS_BLOCKED_BY_CO_AUTHOR: &str = "Post owner is blocked by the invited co-author";
+pub const MSG_ACCOUNT_IS_ALREADY_CO_AUTHOR: &str = "Account is already a co-author or invited to be one";
+pub const MSG_TOO_MANY_CO_AUTHORS: &str = "Post has too many co-authors";
+pub const MSG_CO_AUTHOR_SHARES_ARE_TOO_BIG: &str = "Co-author shares should leave a share to the post owner";
+pub const MSG_CO_AUTHOR_INVITE_NOT_FOUND: &str = "Account is not invited to co-author this post";
+pub const MSG_NO_PERMISSION_TO_REMOVE_CO_AUTHOR_INVITE: &str = "Only post owner or the invited account can remove an invite";
+pub const MSG_TIP_IS_ZERO: &str = "Tip should be greater than zero";
+pub const MSG_REWARD_DEPOSIT_IS_ZERO: &str = "Reward pool deposit should be greater than zero";
+pub const MSG_REWARD_EPOCH_IS_EMPTY: &str = "Reward epoch length and distribution chunk length should be greater than zero";
+pub const MSG_LEGACY_MIGRATION_CHUNK_IS_EMPTY: &str = "Legacy migration chunk length should be greater than zero";
+pub const MSG_LEGACY_LAYOUT_WAS_NOT_UPGRADED: &str = "This chain has never stored the legacy layout";
+pub const MSG_CURATION_REWARD_PERCENT_IS_TOO_BIG: &str = "Curation reward can not be bigger than 100 percent";
+
+pub const MSG_VOTING_STAKE_IS_ZERO: &str = "Voting stake amount should be greater than zero";
+pub const MSG_NOT_ENOUGH_BALANCE_FOR_VOTING_STAKE: &str = "Not enough free balance to lock this voting stake";
+pub const MSG_OUT_OF_BOUNDS_LOCKING_VOTING_STAKE: &str = "Out of bounds locking voting stake";
+pub const MSG_OUT_OF_BOUNDS_UNBONDING_VOTING_STAKE: &str = "Out of bounds unbonding voting stake";
+pub const MSG_NOT_ENOUGH_VOTING_STAKE_TO_UNBOND: &str = "Can not unbond more than the locked voting stake";
+pub const MSG_NO_UNBONDING_STAKE: &str = "Account has no unbonding stake";
+pub const MSG_STAKE_IS_STILL_UNBONDING: &str = "Stake is still unbonding";
+pub const MSG_STAKE_WEIGHT_PERCENT_IS_TOO_BIG: &str = "Stake weight can not be bigger than 100 percent";
+
+pub const MSG_ONLY_BLOG_OWNER_CAN_PIN_POST: &str = "Only a blog owner can pin posts in their blog";
+pub const MSG_POST_IS_NOT_IN_BLOG: &str = "Post is not listed in this blog";
+pub const MSG_POST_IS_ALREADY_PINNED: &str = "Post is already pinned in this blog";
+pub const MSG_POST_IS_NOT_PINNED: &str = "Post is not pinned in this blog";
+pub const MSG_TOO_MANY_PINNED_POSTS: &str = "Too many posts are pinned in this blog";
+pub const MSG_ONLY_POST_OWNER_CAN_PIN_COMMENT: &str = "Only post owner can pin comments on their post";
+pub const MSG_COMMENT_IS_NOT_ON_POST: &str = "Comment does not belong to this post";
+pub const MSG_COMMENT_IS_ALREADY_PINNED: &str = "Comment is already pinned on this post";
+pub const MSG_COMMENT_IS_NOT_PINNED: &str = "Comment is not pinned on this post";
+pub const MSG_TOO_MANY_PINNED_COMMENTS: &str = "Too many comments are pinned on this post";
+
+pub const MSG_ITEM_IS_ALREADY_BOOKMARKED: &str = "Item is already bookmarked";
+pub const MSG_ITEM_IS_NOT_BOOKMARKED: &str = "Item is not bookmarked";
+pub const MSG_TOO_MANY_BOOKMARKS: &str = "Account has too many bookmarks";
+pub const MSG_COLLECTION_NAME_IS_EMPTY: &str = "Collection name is empty";
+pub const MSG_COLLECTION_NAME_IS_TOO_LONG: &str = "Collection name is too long";
+pub const MSG_COLLECTION_NAME_IS_NOT_UTF8: &str = "Collection name is not a valid UTF-8 string";
+pub const MSG_COLLECTION_NAME_IS_TAKEN: &str = "Account already has a collection with this name";
+pub const MSG_COLLECTION_NOT_FOUND: &str = "Collection was not found by name";
+pub const MSG_TOO_MANY_COLLECTIONS: &str = "Account has too many collections";
+pub const MSG_ITEM_IS_ALREADY_IN_COLLECTION: &str = "Item is already in this collection";
+pub const MSG_ITEM_IS_NOT_IN_COLLECTION: &str = "Item is not in this collection";
+pub const MSG_TOO_MANY_ITEMS_IN_COLLECTION: &str = "Collection has too many items";
+
+pub const MSG_TOO_MANY_MENTIONS: &str = "Too many accounts are mentioned";
+pub const MSG_MENTIONED_USERNAME_NOT_FOUND: &str = "Mentioned username does not belong to any account";
+
+pub const MSG_TOO_MANY_TAGS_ON_POST: &str = "Too many tags on a post";
+pub const MSG_TAG_IS_EMPTY: &str = "Tag is empty";
+pub const MSG_TAG_IS_TOO_LONG: &str = "Tag is too long";
+pub const MSG_TAG_HAS_INVALID_CHARS: &str = "Tag can contain only latin letters, digits and underscores";
+pub const MSG_TAG_USAGE_BUCKETS_ARE_EMPTY: &str = "Tag usage bucket length, count and pruning chunk length should be positive";
+
 pub const MSG_PROFILE_ALREADY_EXISTS: &str = "Profile for this account already exists";
 pub const MSG_NOTHING_TO_UPDATE_IN_PROFILE: &str = "Nothing to update in a profile";
 pub const MSG_PROFILE_DOESNT_EXIST: &str = "Account has no profile yet";
 pub const MSG_USERNAME_IS_BUSY: &str = "Profile username is busy";
+pub const MSG_USERNAME_IS_RESERVED: &str = "Username is reserved";
+pub const MSG_USERNAME_IS_IN_COOLDOWN: &str = "Username was released recently and can be claimed only by its previous owner";
 pub const MSG_USERNAME_TOO_SHORT: &str = "Username is too short";
 pub const MSG_USERNAME_TOO_LONG: &str = "Username is too long";
 pub const MSG_USERNAME_NOT_ALPHANUMERIC: &str = "Username is not alphanumeric";
+pub const MSG_NOT_ENOUGH_BALANCE_FOR_USERNAME_DEPOSIT: &str = "Not enough free balance to lock a deposit for this username";
+pub const MSG_PROFILE_HAS_NO_USERNAME: &str = "Profile has no username";
+pub const MSG_USERNAME_HAS_NO_EXPIRY: &str = "Username is not registered or does not expire";
+pub const MSG_USERNAME_HAS_NOT_EXPIRED: &str = "Username registration has not expired yet";
+
+pub const MSG_ACCOUNT_IS_ALREADY_VERIFIER: &str = "Account is already a verifier";
+pub const MSG_ACCOUNT_IS_NOT_VERIFIER: &str = "Account is not a verifier";
+pub const MSG_ONLY_VERIFIER_CAN_ATTEST: &str = "Only a verifier can attest accounts";
+pub const MSG_ONLY_VERIFIER_CAN_REVOKE_ATTESTATION: &str = "Only a verifier who made an attestation can revoke it";
+pub const MSG_ATTESTATION_EXPIRES_IN_PAST: &str = "Attestation should expire in the future";
+pub const MSG_ATTESTATION_ALREADY_EXISTS: &str = "Verifier has already attested this claim";
+pub const MSG_ATTESTATION_NOT_FOUND: &str = "Attestation was not found by index";
+pub const MSG_ATTESTATION_IS_ALREADY_REVOKED: &str = "Attestation is already revoked";
+
+pub const MSG_REVISION_NOT_FOUND: &str = "Revision not found in the edit history";
+pub const MSG_REVISION_IS_PRUNED: &str = "Revision or a later one was pruned from the edit history";
+
+pub const MSG_FEE_DISCOUNT_PERCENT_IS_TOO_BIG: &str = "Fee discount can not be bigger than 100 percent";
+pub const MSG_FEE_EXEMPT_REPUTATION_BELOW_DISCOUNT: &str = "Fee exempt reputation should not be lower than fee discount reputation";
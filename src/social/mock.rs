@@ -1,6 +1,7 @@
 #![cfg(test)]
 
 pub use super::blogs;
+pub use crate::currency::GovernanceCurrency;
 pub use system;
 
 pub use primitives::{H256, Blake2Hasher};
@@ -21,6 +22,7 @@ pub type BlogId = u32;
 pub type PostId = u32;
 pub type CommentId = u32;
 pub type ReactionId = u32;
+pub type MessageId = u32;
 
 #[derive(Clone, Eq, PartialEq, Debug)]
 pub struct Test;
@@ -43,12 +45,27 @@ impl timestamp::Trait for Test {
   type OnTimestampSet = ();
 }
 
+impl balances::Trait for Test {
+  type Balance = u64;
+  type OnFreeBalanceZero = ();
+  type OnNewAccount = ();
+  type Event = ();
+  type TransactionPayment = ();
+  type DustRemoval = ();
+  type TransferPayment = ();
+}
+
+impl GovernanceCurrency for Test {
+  type Currency = balances::Module<Self>;
+}
+
 impl blogs::Trait for Test {
   type Event = ();
   type BlogId = BlogId;
   type PostId = PostId;
   type CommentId = CommentId;
   type ReactionId = ReactionId;
+  type MessageId = MessageId;
 }
 
 pub fn build_ext() -> runtime_io::TestExternalities<Blake2Hasher> {
@@ -60,4 +77,20 @@ pub fn build_ext() -> runtime_io::TestExternalities<Blake2Hasher> {
   t.into()
 }
 
+/// Deposits below the existential deposit can not open an account.
+pub fn build_ext_with_existential_deposit(existential_deposit: u64) -> runtime_io::TestExternalities<Blake2Hasher> {
+  let mut t = system::GenesisConfig::<Test>::default()
+    .build_storage()
+    .unwrap()
+    .0;
+  t.extend(balances::GenesisConfig::<Test> { existential_deposit, ..Default::default() }
+    .build_storage()
+    .unwrap()
+    .0);
+
+  t.into()
+}
+
+pub type System = system::Module<Test>;
+pub type Balances = balances::Module<Test>;
 pub type Blogs = blogs::Module<Test>;
\ No newline at end of file
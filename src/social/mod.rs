@@ -1,8 +1,12 @@
 pub mod defaults;
 pub mod messages;
+pub mod content;
 pub mod functions;
 pub mod blogs;
+pub mod legacy;
+pub mod api;
 
 // Tests related:
 mod mock;
 mod tests;
+mod invariants;
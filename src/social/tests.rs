@@ -4,9 +4,14 @@ use super::mock::*;
 use super::blogs::*;
 use super::defaults::*;
 use super::messages::*;
+use super::content::Content;
+use super::invariants::check_invariants;
 
 use runtime_io::with_externalities;
+use parity_codec::{Encode, Decode};
 use srml_support::*;
+use srml_support::traits::Currency;
+use runtime_primitives::traits::OnFinalize;
 
 const ACCOUNT1 : AccountId = 1;
 const ACCOUNT2 : AccountId = 2;
@@ -15,40 +20,41 @@ fn blog_slug() -> Vec<u8> {
   b"blog_slug".to_vec()
 }
 
-fn blog_ipfs_hash() -> Vec<u8> {
-  b"QmRAQB6YaCyidP37UdDnjFY5vQuiBrcqdyoW1CuDgwxkD4".to_vec()
+fn blog_content() -> Content {
+  Content::IPFS(b"QmRAQB6YaCyidP37UdDnjFY5vQuiBrcqdyoW1CuDgwxkD4".to_vec())
 }
 
-fn blog_update(writers: Option<Vec<AccountId>>, slug: Option<Vec<u8>>, ipfs_hash: Option<Vec<u8>>) -> BlogUpdate<Test> {
+fn blog_update(writers: Option<Vec<AccountId>>, slug: Option<Vec<u8>>, content: Option<Content>) -> BlogUpdate<Test> {
   BlogUpdate {
     writers,
     slug,
-    ipfs_hash
+    content
   }
 }
 
-fn post_ipfs_hash() -> Vec<u8> {
-  b"QmRAQB6YaCyidP37UdDnjFY5vQuiBrcqdyoW2CuDgwxkD4".to_vec()
+fn post_content() -> Content {
+  Content::IPFS(b"QmRAQB6YaCyidP37UdDnjFY5vQuiBrcqdyoW2CuDgwxkD4".to_vec())
 }
 
-fn post_update(blog_id: Option<BlogId>, ipfs_hash: Option<Vec<u8>>) -> PostUpdate<Test> {
+fn post_update(blog_id: Option<BlogId>, content: Option<Content>) -> PostUpdate<Test> {
   PostUpdate {
     blog_id,
-    ipfs_hash
+    content,
+    tags: None
   }
 }
 
-fn comment_ipfs_hash() -> Vec<u8> {
-  b"QmRAQB6YaCyidP37UdDnjFY5vQuiBrcqdyoW1CuDgwxkD4".to_vec()
+fn comment_content() -> Content {
+  Content::IPFS(b"QmRAQB6YaCyidP37UdDnjFY5vQuiBrcqdyoW1CuDgwxkD4".to_vec())
 }
 
-fn subcomment_ipfs_hash() -> Vec<u8> {
-  b"QmYA2fn8cMbVWo4v95RwcwJVyQsNtnEwHerfWR8UNtEwoE".to_vec()
+fn subcomment_content() -> Content {
+  Content::IPFS(b"QmYA2fn8cMbVWo4v95RwcwJVyQsNtnEwHerfWR8UNtEwoE".to_vec())
 }
 
-fn comment_update(ipfs_hash: Vec<u8>) -> CommentUpdate {
+fn comment_update(content: Content) -> CommentUpdate {
   CommentUpdate {
-    ipfs_hash
+    content
   }
 }
 
@@ -59,8 +65,8 @@ fn bob_username() -> Vec<u8> {
   b"Bob".to_vec()
 }
 
-fn profile_ipfs_hash() -> Vec<u8> {
-  b"QmRAQB6YaCyidP37UdDnjFY5vQuiaRtqdyoW2CuDgwxkA5".to_vec()
+fn profile_content() -> Content {
+  Content::IPFS(b"QmRAQB6YaCyidP37UdDnjFY5vQuiaRtqdyoW2CuDgwxkA5".to_vec())
 }
 
 fn reaction_upvote() -> ReactionKind {
@@ -108,24 +114,30 @@ fn extension_shared_comment(comment_id: CommentId) -> PostExtension<Test> {
   PostExtension::SharedComment(comment_id)
 }
 
+// Every dispatch made through the helpers below is followed by a storage consistency check.
+fn checked(result: dispatch::Result) -> dispatch::Result {
+  check_invariants();
+  result
+}
+
 fn _create_default_blog() -> dispatch::Result {
   _create_blog(None, None, None)
 }
 
-fn _create_blog(origin: Option<Origin>, slug: Option<Vec<u8>>, ipfs_hash: Option<Vec<u8>>) -> dispatch::Result {
-  Blogs::create_blog(
+fn _create_blog(origin: Option<Origin>, slug: Option<Vec<u8>>, content: Option<Content>) -> dispatch::Result {
+  checked(Blogs::create_blog(
     origin.unwrap_or(Origin::signed(ACCOUNT1)),
     slug.unwrap_or(self::blog_slug()),
-    ipfs_hash.unwrap_or(self::blog_ipfs_hash())
-  )
+    content.unwrap_or(self::blog_content())
+  ))
 }
 
 fn _update_blog(origin: Option<Origin>, blog_id: Option<u32>, update: Option<BlogUpdate<Test>>) -> dispatch::Result {
-  Blogs::update_blog(
+  checked(Blogs::update_blog(
     origin.unwrap_or(Origin::signed(ACCOUNT1)),
     blog_id.unwrap_or(1),
     update.unwrap_or(self::blog_update(None, None, None))
-  )
+  ))
 }
 
 fn _default_follow_blog() -> dispatch::Result {
@@ -133,10 +145,10 @@ fn _default_follow_blog() -> dispatch::Result {
 }
 
 fn _follow_blog(origin: Option<Origin>, blog_id: Option<BlogId>) -> dispatch::Result {
-  Blogs::follow_blog(
+  checked(Blogs::follow_blog(
     origin.unwrap_or(Origin::signed(ACCOUNT2)),
     blog_id.unwrap_or(1)
-  )
+  ))
 }
 
 fn _default_unfollow_blog() -> dispatch::Result {
@@ -144,52 +156,74 @@ fn _default_unfollow_blog() -> dispatch::Result {
 }
 
 fn _unfollow_blog(origin: Option<Origin>, blog_id: Option<BlogId>) -> dispatch::Result {
-  Blogs::unfollow_blog(
+  checked(Blogs::unfollow_blog(
     origin.unwrap_or(Origin::signed(ACCOUNT2)),
     blog_id.unwrap_or(1)
-  )
+  ))
 }
 
 fn _create_default_post() -> dispatch::Result {
   _create_post(None, None, None, None)
 }
 
-fn _create_post(origin: Option<Origin>, blog_id: Option<BlogId>, ipfs_hash: Option<Vec<u8>>, extension: Option<PostExtension<Test>>) -> dispatch::Result {
-  Blogs::create_post(
+fn _create_post(origin: Option<Origin>, blog_id: Option<BlogId>, content: Option<Content>, extension: Option<PostExtension<Test>>) -> dispatch::Result {
+  checked(Blogs::create_post(
     origin.unwrap_or(Origin::signed(ACCOUNT1)),
     blog_id.unwrap_or(1),
-    ipfs_hash.unwrap_or(self::post_ipfs_hash()),
-    extension.unwrap_or(self::extension_regular_post())
-  )
+    content.unwrap_or(self::post_content()),
+    extension.unwrap_or(self::extension_regular_post()),
+    vec![],
+    vec![],
+    PostVisibility::Public
+  ))
+}
+
+fn _create_post_with_visibility(visibility: PostVisibility) -> dispatch::Result {
+  checked(Blogs::create_post(Origin::signed(ACCOUNT1), 1, self::post_content(), self::extension_regular_post(), vec![], vec![], visibility))
+}
+
+fn _create_post_with_tags(tags: Vec<Vec<u8>>) -> dispatch::Result {
+  checked(Blogs::create_post(Origin::signed(ACCOUNT1), 1, self::post_content(), self::extension_regular_post(), tags, vec![], PostVisibility::Public))
+}
+
+fn _create_post_with_mentions(mentions: Vec<Mention<Test>>) -> dispatch::Result {
+  checked(Blogs::create_post(Origin::signed(ACCOUNT1), 1, self::post_content(), self::extension_regular_post(), vec![], mentions, PostVisibility::Public))
+}
+
+fn _update_post_tags(post_id: PostId, tags: Vec<Vec<u8>>) -> dispatch::Result {
+  _update_post(None, Some(post_id), Some(PostUpdate { blog_id: None, content: None, tags: Some(tags) }))
 }
 
 fn _update_post(origin: Option<Origin>, post_id: Option<PostId>, update: Option<PostUpdate<Test>>) -> dispatch::Result {
-  Blogs::update_post(
+  checked(Blogs::update_post(
     origin.unwrap_or(Origin::signed(ACCOUNT1)),
     post_id.unwrap_or(1),
-    update.unwrap_or(self::post_update(None, None))
-  )
+    update.unwrap_or(self::post_update(None, None)),
+    vec![]
+  ))
 }
 
 fn _create_default_comment() -> dispatch::Result {
   _create_comment(None, None, None, None)
 }
 
-fn _create_comment(origin: Option<Origin>, post_id: Option<PostId>, parent_id: Option<CommentId>, ipfs_hash: Option<Vec<u8>>) -> dispatch::Result {
-  Blogs::create_comment(
+fn _create_comment(origin: Option<Origin>, post_id: Option<PostId>, parent_id: Option<CommentId>, content: Option<Content>) -> dispatch::Result {
+  checked(Blogs::create_comment(
     origin.unwrap_or(Origin::signed(ACCOUNT1)),
     post_id.unwrap_or(1),
     parent_id,
-    ipfs_hash.unwrap_or(self::comment_ipfs_hash())
-  )
+    content.unwrap_or(self::comment_content()),
+    vec![]
+  ))
 }
 
 fn _update_comment(origin: Option<Origin>, comment_id: Option<CommentId>, update: Option<CommentUpdate>) -> dispatch::Result {
-  Blogs::update_comment(
+  checked(Blogs::update_comment(
     origin.unwrap_or(Origin::signed(ACCOUNT1)),
     comment_id.unwrap_or(1),
-    update.unwrap_or(self::comment_update(self::subcomment_ipfs_hash()))
-  )
+    update.unwrap_or(self::comment_update(self::subcomment_content())),
+    vec![]
+  ))
 }
 
 fn _create_default_post_reaction() -> dispatch::Result {
@@ -201,75 +235,75 @@ fn _create_default_comment_reaction() -> dispatch::Result {
 }
 
 fn _create_post_reaction(origin: Option<Origin>, post_id: Option<PostId>, kind: Option<ReactionKind>) -> dispatch::Result {
-  Blogs::create_post_reaction(
+  checked(Blogs::create_post_reaction(
     origin.unwrap_or(Origin::signed(ACCOUNT1)),
     post_id.unwrap_or(1),
     kind.unwrap_or(self::reaction_upvote())
-  )
+  ))
 }
 
 fn _create_comment_reaction(origin: Option<Origin>, comment_id: Option<CommentId>, kind: Option<ReactionKind>) -> dispatch::Result {
-  Blogs::create_comment_reaction(
+  checked(Blogs::create_comment_reaction(
     origin.unwrap_or(Origin::signed(ACCOUNT1)),
     comment_id.unwrap_or(1),
     kind.unwrap_or(self::reaction_upvote())
-  )
+  ))
 }
 
 fn _update_post_reaction(origin: Option<Origin>, post_id: Option<PostId>, reaction_id: ReactionId, kind: Option<ReactionKind>) -> dispatch::Result {
-  Blogs::update_post_reaction(
+  checked(Blogs::update_post_reaction(
     origin.unwrap_or(Origin::signed(ACCOUNT1)),
     post_id.unwrap_or(1),
     reaction_id,
     kind.unwrap_or(self::reaction_upvote())
-  )
+  ))
 }
 
 fn _update_comment_reaction(origin: Option<Origin>, comment_id: Option<CommentId>, reaction_id: ReactionId, kind: Option<ReactionKind>) -> dispatch::Result {
-  Blogs::update_comment_reaction(
+  checked(Blogs::update_comment_reaction(
     origin.unwrap_or(Origin::signed(ACCOUNT1)),
     comment_id.unwrap_or(1),
     reaction_id,
     kind.unwrap_or(self::reaction_upvote())
-  )
+  ))
 }
 
 fn _delete_post_reaction(origin: Option<Origin>, post_id: Option<PostId>, reaction_id: ReactionId) -> dispatch::Result {
-  Blogs::delete_post_reaction(
+  checked(Blogs::delete_post_reaction(
     origin.unwrap_or(Origin::signed(ACCOUNT1)),
     post_id.unwrap_or(1),
     reaction_id
-  )
+  ))
 }
 
 fn _delete_comment_reaction(origin: Option<Origin>, comment_id: Option<CommentId>, reaction_id: ReactionId) -> dispatch::Result {
-  Blogs::delete_comment_reaction(
+  checked(Blogs::delete_comment_reaction(
     origin.unwrap_or(Origin::signed(ACCOUNT1)),
     comment_id.unwrap_or(1),
     reaction_id
-  )
+  ))
 }
 
 fn _create_default_profile() -> dispatch::Result {
   _create_profile(None, None, None)
 }
 
-fn _create_profile(origin: Option<Origin>, username: Option<Vec<u8>>, ipfs_hash: Option<Vec<u8>>) -> dispatch::Result {
-  Blogs::create_profile(
+fn _create_profile(origin: Option<Origin>, username: Option<Vec<u8>>, content: Option<Content>) -> dispatch::Result {
+  checked(Blogs::create_profile(
     origin.unwrap_or(Origin::signed(ACCOUNT1)),
     username.unwrap_or(self::alice_username()),
-    ipfs_hash.unwrap_or(self::profile_ipfs_hash())
-  )
+    content.unwrap_or(self::profile_content())
+  ))
 }
 
-fn _update_profile(origin: Option<Origin>, username: Option<Vec<u8>>, ipfs_hash: Option<Vec<u8>>) -> dispatch::Result {
-  Blogs::update_profile(
+fn _update_profile(origin: Option<Origin>, username: Option<Vec<u8>>, content: Option<Content>) -> dispatch::Result {
+  checked(Blogs::update_profile(
     origin.unwrap_or(Origin::signed(ACCOUNT1)),
     ProfileUpdate {
       username,
-      ipfs_hash
+      content
     }
-  )
+  ))
 }
 
 fn _default_follow_account() -> dispatch::Result {
@@ -277,10 +311,10 @@ fn _default_follow_account() -> dispatch::Result {
 }
 
 fn _follow_account(origin: Option<Origin>, account: Option<AccountId>) -> dispatch::Result {
-  Blogs::follow_account(
+  checked(Blogs::follow_account(
     origin.unwrap_or(Origin::signed(ACCOUNT2)),
     account.unwrap_or(ACCOUNT1)
-  )
+  ))
 }
 
 fn _default_unfollow_account() -> dispatch::Result {
@@ -288,10 +322,10 @@ fn _default_unfollow_account() -> dispatch::Result {
 }
 
 fn _unfollow_account(origin: Option<Origin>, account: Option<AccountId>) -> dispatch::Result {
-  Blogs::unfollow_account(
+  checked(Blogs::unfollow_account(
     origin.unwrap_or(Origin::signed(ACCOUNT2)),
     account.unwrap_or(ACCOUNT1)
-  )
+  ))
 }
 
 fn _change_post_score_by_id(account: AccountId, post_id: PostId, action: ScoringAction) -> dispatch::Result {
@@ -326,11 +360,10 @@ fn create_blog_should_work() {
 
     assert_eq!(blog.created.account, ACCOUNT1);
     assert_eq!(blog.slug, self::blog_slug());
-    assert_eq!(blog.ipfs_hash, self::blog_ipfs_hash());
+    assert_eq!(blog.content, self::blog_content());
     assert!(blog.writers.is_empty());
-    assert_eq!(blog.posts_count, 0);
-    assert_eq!(blog.followers_count, 1);
-    assert!(blog.edit_history.is_empty());
+    assert_eq!(Blogs::blog_counters(1), BlogCounters { posts_count: 0, followers_count: 1, score: 0 });
+    assert_eq!(Blogs::blog_revisions_count(1), 0);
   });
 }
 
@@ -365,19 +398,83 @@ fn create_blog_should_fail_not_unique_slug() {
 }
 
 #[test]
-fn create_blog_should_fail_invalid_ipfs_hash() {
-  let ipfs_hash : Vec<u8> = b"QmV9tSDx9UiPeWExXEeH6aoDvmihvx6j".to_vec();
+fn create_blog_should_fail_invalid_content() {
+  let content = Content::IPFS(b"QmV9tSDx9UiPeWExXEeH6aoDvmihvx6j".to_vec());
+
+  with_externalities(&mut build_ext(), || {
+    // Try to catch an error creating a blog with invalid content
+    assert_noop!(_create_blog(None, None, Some(content)), MSG_IPFS_IS_INCORRECT);
+  });
+}
+
+#[test]
+fn create_blog_should_fail_not_base58_cid_v0() {
+  let content = Content::IPFS(b"QmRAQB6YaCyidP37UdDnjFY5vQuiBrcqdyoW1CuDgwxk00".to_vec());
+
+  with_externalities(&mut build_ext(), || {
+    assert_noop!(_create_blog(None, None, Some(content)), MSG_IPFS_IS_INCORRECT);
+  });
+}
+
+#[test]
+fn create_blog_should_fail_cid_v1_with_nonzero_padding() {
+  let content = Content::IPFS(b"bafybeigdyrzt5sfp7udm7hu76uh7y26nf3efuylqabf3oclgtqy55fbzdj".to_vec());
+
+  with_externalities(&mut build_ext(), || {
+    assert_noop!(_create_blog(None, None, Some(content)), MSG_IPFS_IS_INCORRECT);
+  });
+}
+
+#[test]
+fn create_blog_should_fail_empty_content() {
+  with_externalities(&mut build_ext(), || {
+    assert_noop!(_create_blog(None, None, Some(Content::None)), MSG_CONTENT_IS_EMPTY);
+    assert_noop!(_create_blog(None, None, Some(Content::Raw(vec![]))), MSG_CONTENT_IS_EMPTY);
+  });
+}
+
+#[test]
+fn create_blog_should_work_with_other_content_kinds() {
+  let cid_v1 = Content::IPFS(b"bafybeigdyrzt5sfp7udm7hu76uh7y26nf3efuylqabf3oclgtqy55fbzdi".to_vec());
+  let hyper_key = Content::Hyper(b"ab9c2d1f0e3b4a5968778695a4b3c2d1e0f1a2b3c4d5e6f708192a3b4c5d6e7f".to_vec());
+  let arweave_tx = Content::Arweave(b"bNbA3TEQVL60xlgCcqdz4ZPHFZ711cZ3hmkpGttDt_U".to_vec());
 
   with_externalities(&mut build_ext(), || {
-    // Try to catch an error creating a blog with invalid ipfs_hash
-    assert_noop!(_create_blog(None, None, Some(ipfs_hash)), MSG_IPFS_IS_INCORRECT);
+    assert_ok!(_create_blog(None, Some(b"blog_cid_v1".to_vec()), Some(cid_v1.clone()))); // BlogId 1
+    assert_ok!(_create_blog(None, Some(b"blog_hyper".to_vec()), Some(hyper_key.clone()))); // BlogId 2
+    assert_ok!(_create_blog(None, Some(b"blog_arweave".to_vec()), Some(arweave_tx.clone()))); // BlogId 3
+
+    assert_eq!(Blogs::blog_by_id(1).unwrap().content, cid_v1);
+    assert_eq!(Blogs::blog_by_id(2).unwrap().content, hyper_key);
+    assert_eq!(Blogs::blog_by_id(3).unwrap().content, arweave_tx);
+
+    assert_noop!(_create_blog(
+      None,
+      Some(b"blog_hyper_upper".to_vec()),
+      Some(Content::Hyper(b"AB9C2D1F0E3B4A5968778695A4B3C2D1E0F1A2B3C4D5E6F708192A3B4C5D6E7F".to_vec()))
+    ), MSG_HYPER_HASH_IS_INCORRECT);
+    assert_noop!(_create_blog(
+      None,
+      Some(b"blog_arweave_short".to_vec()),
+      Some(Content::Arweave(b"bNbA3TEQVL60xlgCcqdz4ZPHFZ711cZ3hmkpGttDt".to_vec()))
+    ), MSG_ARWEAVE_HASH_IS_INCORRECT);
   });
 }
 
+#[test]
+fn content_should_decode_legacy_ipfs_hash() {
+  let legacy_hash = b"QmRAQB6YaCyidP37UdDnjFY5vQuiBrcqdyoW1CuDgwxkD4".to_vec();
+  let legacy_empty_hash: Vec<u8> = vec![];
+
+  assert_eq!(Content::decode(&mut &legacy_hash.encode()[..]), Some(Content::IPFS(legacy_hash)));
+  assert_eq!(Content::decode(&mut &legacy_empty_hash.encode()[..]), Some(Content::None));
+  assert_eq!(Content::decode(&mut &self::blog_content().encode()[..]), Some(self::blog_content()));
+}
+
 #[test]
 fn update_blog_should_work() {
   let slug : Vec<u8> = b"new_slug".to_vec();
-  let ipfs_hash : Vec<u8> = b"QmRAQB6YaCyidP37UdDnjFY5vQuiBrcqdyoW2CuDgwxkD4".to_vec();
+  let content = Content::IPFS(b"QmRAQB6YaCyidP37UdDnjFY5vQuiBrcqdyoW2CuDgwxkD4".to_vec());
 
   with_externalities(&mut build_ext(), || {
     assert_ok!(_create_default_blog()); // BlogId 1
@@ -388,7 +485,7 @@ fn update_blog_should_work() {
         self::blog_update(
           None,
           Some(slug.clone()),
-          Some(ipfs_hash.clone())
+          Some(content.clone())
         )
       )
     ));
@@ -396,12 +493,79 @@ fn update_blog_should_work() {
     // Check whether blog updates correctly
     let blog = Blogs::blog_by_id(1).unwrap();
     assert_eq!(blog.slug, slug);
-    assert_eq!(blog.ipfs_hash, ipfs_hash);
+    assert_eq!(blog.content, content);
 
     // Check whether history recorded correctly
-    assert_eq!(blog.edit_history[0].old_data.writers, None);
-    assert_eq!(blog.edit_history[0].old_data.slug, Some(self::blog_slug()));
-    assert_eq!(blog.edit_history[0].old_data.ipfs_hash, Some(self::blog_ipfs_hash()));
+    let history = Blogs::blog_history_page(1, 0, 10);
+    assert_eq!(history.len(), 1);
+    assert_eq!(history[0].old_data.writers, None);
+    assert_eq!(history[0].old_data.slug, Some(self::blog_slug()));
+    assert_eq!(history[0].old_data.content, Some(self::blog_content()));
+  });
+}
+
+#[test]
+fn create_blog_should_normalize_slug() {
+  with_externalities(&mut build_ext(), || {
+    assert_ok!(_create_blog(None, Some(b"My_Blog-1".to_vec()), None)); // BlogId 1
+
+    assert_eq!(Blogs::blog_by_id(1).unwrap().slug, b"my_blog-1".to_vec());
+    assert_eq!(Blogs::blog_id_by_slug(b"my_blog-1".to_vec()), Some(1));
+    assert_noop!(_create_blog(None, Some(b"my_blog-1".to_vec()), None), MSG_BLOG_SLUG_IS_NOT_UNIQUE);
+  });
+}
+
+#[test]
+fn create_blog_should_fail_invalid_slug_chars() {
+  with_externalities(&mut build_ext(), || {
+    assert_noop!(_create_blog(None, Some(b"blog slug".to_vec()), None), MSG_BLOG_SLUG_HAS_INVALID_CHARS);
+    assert_noop!(_create_blog(None, Some("блог_slug".as_bytes().to_vec()), None), MSG_BLOG_SLUG_HAS_INVALID_CHARS);
+  });
+}
+
+#[test]
+fn create_blog_should_fail_reserved_slug() {
+  with_externalities(&mut build_ext(), || {
+    assert!(Blogs::reserve_slugs(Origin::signed(ACCOUNT1), vec![b"admin".to_vec()]).is_err());
+    assert_ok!(Blogs::reserve_slugs(system::RawOrigin::Root.into(), vec![b"Admin".to_vec()]));
+
+    assert_noop!(_create_blog(None, Some(b"ADMIN".to_vec()), None), MSG_BLOG_SLUG_IS_RESERVED);
+
+    assert_ok!(Blogs::unreserve_slugs(system::RawOrigin::Root.into(), vec![b"admin".to_vec()]));
+    assert_ok!(_create_blog(None, Some(b"admin".to_vec()), None)); // BlogId 1
+  });
+}
+
+#[test]
+fn update_blog_should_keep_old_slug_as_redirect() {
+  with_externalities(&mut build_ext(), || {
+    assert_ok!(_create_default_blog()); // BlogId 1
+    assert_ok!(_update_blog(None, None, Some(self::blog_update(None, Some(b"new_slug".to_vec()), None))));
+
+    // The old slug still leads to the renamed blog and can not be claimed:
+    assert_eq!(Blogs::resolve_slug(self::blog_slug()), Some(1));
+    assert_eq!(Blogs::resolve_slug(b"new_slug".to_vec()), Some(1));
+    assert_noop!(_create_blog(Some(Origin::signed(ACCOUNT2)), None, None), MSG_BLOG_SLUG_IS_NOT_UNIQUE);
+
+    System::set_block_number(DEFAULT_SLUG_REDIRECT_PERIOD);
+    assert_eq!(Blogs::resolve_slug(self::blog_slug()), None);
+
+    assert_ok!(_create_blog(Some(Origin::signed(ACCOUNT2)), None, None)); // BlogId 2
+    assert_eq!(Blogs::resolve_slug(self::blog_slug()), Some(2));
+    assert_eq!(Blogs::slug_redirect_expires_at(self::blog_slug()), None);
+  });
+}
+
+#[test]
+fn update_blog_should_work_return_to_redirected_slug() {
+  with_externalities(&mut build_ext(), || {
+    assert_ok!(_create_default_blog()); // BlogId 1
+    assert_ok!(_update_blog(None, None, Some(self::blog_update(None, Some(b"new_slug".to_vec()), None))));
+    assert_ok!(_update_blog(None, None, Some(self::blog_update(None, Some(self::blog_slug()), None))));
+
+    assert_eq!(Blogs::blog_by_id(1).unwrap().slug, self::blog_slug());
+    assert_eq!(Blogs::slug_redirect_expires_at(self::blog_slug()), None);
+    assert_eq!(Blogs::resolve_slug(b"new_slug".to_vec()), Some(1));
   });
 }
 
@@ -522,19 +686,19 @@ fn update_blog_should_fail_not_unique_slug() {
 }
 
 #[test]
-fn update_blog_should_fail_invalid_ipfs_hash() {
-  let ipfs_hash : Vec<u8> = b"QmV9tSDx9UiPeWExXEeH6aoDvmihvx6j".to_vec();
+fn update_blog_should_fail_invalid_content() {
+  let content = Content::IPFS(b"QmV9tSDx9UiPeWExXEeH6aoDvmihvx6j".to_vec());
 
   with_externalities(&mut build_ext(), || {
     assert_ok!(_create_default_blog()); // BlogId 1
   
-    // Try to catch an error updating a blog with invalid ipfs_hash
+    // Try to catch an error updating a blog with invalid content
     assert_noop!(_update_blog(None, None,
       Some(
         self::blog_update(
           None, 
           None,
-          Some(ipfs_hash)
+          Some(content)
         )
       )
     ), MSG_IPFS_IS_INCORRECT);
@@ -557,13 +721,10 @@ fn create_post_should_work() {
 
     assert_eq!(post.blog_id, 1);
     assert_eq!(post.created.account, ACCOUNT1);
-    assert_eq!(post.ipfs_hash, self::post_ipfs_hash());
-    assert_eq!(post.comments_count, 0);
-    assert_eq!(post.upvotes_count, 0);
-    assert_eq!(post.downvotes_count, 0);
-    assert_eq!(post.shares_count, 0);
+    assert_eq!(post.content, self::post_content());
+    assert_eq!(Blogs::post_counters(1), PostCounters::default());
     assert_eq!(post.extension, self::extension_regular_post());
-    assert!(post.edit_history.is_empty());
+    assert_eq!(Blogs::post_revisions_count(1), 0);
   });
 }
 
@@ -575,20 +736,45 @@ fn create_post_should_fail_blog_not_found() {
 }
 
 #[test]
-fn create_post_should_fail_invalid_ipfs_hash() {
-  let ipfs_hash : Vec<u8> = b"QmV9tSDx9UiPeWExXEeH6aoDvmihvx6j".to_vec();
+fn create_post_should_fail_invalid_content() {
+  let content = Content::IPFS(b"QmV9tSDx9UiPeWExXEeH6aoDvmihvx6j".to_vec());
+
+  with_externalities(&mut build_ext(), || {
+    assert_ok!(_create_default_blog()); // BlogId 1
+
+    // Try to catch an error creating a regular post with invalid content
+    assert_noop!(_create_post(None, None, Some(content), None), MSG_IPFS_IS_INCORRECT);
+  });
+}
+
+#[test]
+fn create_post_should_work_with_raw_content() {
+  let content = Content::Raw("Короткий пост".as_bytes().to_vec());
+
+  with_externalities(&mut build_ext(), || {
+    assert_ok!(_create_default_blog()); // BlogId 1
+    assert_ok!(_create_post(None, None, Some(content.clone()), None)); // PostId 1
+
+    assert_eq!(Blogs::post_by_id(1).unwrap().content, content);
+  });
+}
+
+#[test]
+fn create_post_should_fail_raw_content_too_long() {
+  let mut text = vec![b'a'; DEFAULT_POST_MAX_LEN as usize];
 
   with_externalities(&mut build_ext(), || {
     assert_ok!(_create_default_blog()); // BlogId 1
+    assert_ok!(_create_post(None, None, Some(Content::Raw(text.clone())), None)); // PostId 1
 
-    // Try to catch an error creating a regular post with invalid ipfs_hash
-    assert_noop!(_create_post(None, None, Some(ipfs_hash), None), MSG_IPFS_IS_INCORRECT);
+    text.push(b'a');
+    assert_noop!(_create_post(None, None, Some(Content::Raw(text)), None), MSG_RAW_CONTENT_IS_TOO_LONG);
   });
 }
 
 #[test]
 fn update_post_should_work() {
-  let ipfs_hash : Vec<u8> = b"QmRAQB6YaCyidP37UdDnjFY5vQuiBrcqdyoW1CuDgwxkD4".to_vec();
+  let content = Content::IPFS(b"QmRAQB6YaCyidP37UdDnjFY5vQuiBrcqdyoW1CuDgwxkD4".to_vec());
 
   with_externalities(&mut build_ext(), || {
     assert_ok!(_create_default_blog()); // BlogId 1
@@ -599,7 +785,7 @@ fn update_post_should_work() {
       Some(
         self::post_update(
           None,
-          Some(ipfs_hash.clone())
+          Some(content.clone())
         )
       )
     ));
@@ -608,11 +794,67 @@ fn update_post_should_work() {
     // Check whether post updates correctly
     let post = Blogs::post_by_id(1).unwrap();
     assert_eq!(post.blog_id, 1);
-    assert_eq!(post.ipfs_hash, ipfs_hash);
+    assert_eq!(post.content, content);
 
     // Check whether history recorded correctly
-    assert_eq!(post.edit_history[0].old_data.blog_id, None);
-    assert_eq!(post.edit_history[0].old_data.ipfs_hash, Some(self::post_ipfs_hash()));
+    let history = Blogs::post_history_page(1, 0, 10);
+    assert_eq!(history[0].old_data.blog_id, None);
+    assert_eq!(history[0].old_data.content, Some(self::post_content()));
+  });
+}
+
+#[test]
+fn update_post_should_count_posts_of_blog_it_is_moved_to() {
+  with_externalities(&mut build_ext(), || {
+    assert_ok!(_create_default_blog()); // BlogId 1
+    assert_ok!(_create_blog(None, Some(b"blog2_slug".to_vec()), None)); // BlogId 2
+    assert_ok!(_create_default_post()); // PostId 1
+    assert_ok!(_create_default_post()); // PostId 2
+    assert_ok!(_create_default_post()); // PostId 3
+    assert_ok!(_create_post(None, Some(2), None, None)); // PostId 4
+
+    // The counter of the new blog grows from its own value, not from the old blog's one:
+    assert_ok!(_update_post(None, None, Some(self::post_update(Some(2), None))));
+    assert_eq!(Blogs::blog_counters(1).posts_count, 2);
+    assert_eq!(Blogs::blog_counters(2).posts_count, 2);
+  });
+}
+
+#[test]
+fn update_post_should_move_score_of_post_to_new_blog() {
+  with_externalities(&mut build_ext(), || {
+    assert_ok!(_create_default_blog()); // BlogId 1
+    assert_ok!(_create_blog(None, Some(b"blog2_slug".to_vec()), None)); // BlogId 2
+    assert_ok!(_create_default_post()); // PostId 1
+    assert_ok!(_create_post_reaction(Some(Origin::signed(ACCOUNT2)), None, None));
+    let score = Blogs::post_counters(1).score;
+    assert_ne!(score, 0);
+
+    assert_ok!(_update_post(None, None, Some(self::post_update(Some(2), None))));
+    assert_eq!(Blogs::blog_counters(1).score, 0);
+    assert_eq!(Blogs::blog_counters(2).score, score);
+    assert_eq!(Blogs::post_score_in_blog((1, 1)), 0);
+    assert_eq!(Blogs::post_score_in_blog((2, 1)), score);
+  });
+}
+
+#[test]
+fn update_post_should_work_move_to_another_blog() {
+  with_externalities(&mut build_ext(), || {
+    assert_ok!(_create_default_blog()); // BlogId 1
+    assert_ok!(_create_blog(None, Some(b"blog2_slug".to_vec()), None)); // BlogId 2
+    assert_ok!(_create_default_post()); // PostId 1
+    assert_ok!(_create_default_post()); // PostId 2
+
+    // Move PostId 1 to BlogId 2
+    assert_ok!(_update_post(None, None, Some(self::post_update(Some(2), None))));
+
+    // Check whether counters and indexes of both blogs are updated
+    assert_eq!(Blogs::post_ids_by_blog_id(1), vec![2]);
+    assert_eq!(Blogs::post_ids_by_blog_id(2), vec![1]);
+    assert_eq!(Blogs::blog_counters(1).posts_count, 1);
+    assert_eq!(Blogs::blog_counters(2).posts_count, 1);
+    assert_eq!(Blogs::post_by_id(1).unwrap().blog_id, 2);
   });
 }
 
@@ -666,25 +908,179 @@ fn update_post_should_fail_not_an_owner() {
 }
 
 #[test]
-fn update_post_should_fail_invalid_ipfs_hash() {
-  let ipfs_hash : Vec<u8> = b"QmV9tSDx9UiPeWExXEeH6aoDvmihvx6j".to_vec();
+fn update_post_should_fail_invalid_content() {
+  let content = Content::IPFS(b"QmV9tSDx9UiPeWExXEeH6aoDvmihvx6j".to_vec());
 
   with_externalities(&mut build_ext(), || {
     assert_ok!(_create_default_blog()); // BlogId 1
     assert_ok!(_create_default_post()); // PostId 1
   
-    // Try to catch an error updating a post with invalid ipfs_hash
+    // Try to catch an error updating a post with invalid content
     assert_noop!(_update_post(None, None,
       Some(
         self::post_update(
           None,
-          Some(ipfs_hash)
+          Some(content)
         )
       )
     ), MSG_IPFS_IS_INCORRECT);
   });
 }
 
+#[test]
+fn create_post_should_work_with_tags() {
+  with_externalities(&mut build_ext(), || {
+    assert_ok!(_create_default_blog()); // BlogId 1
+    assert_ok!(_create_post_with_tags(vec![b"#Rust".to_vec(), b"substrate".to_vec(), b"rust".to_vec()])); // PostId 1
+
+    assert_eq!(Blogs::post_by_id(1).unwrap().tags, vec![b"rust".to_vec(), b"substrate".to_vec()]);
+    assert_eq!(Blogs::post_ids_by_tag(b"rust".to_vec()), vec![1]);
+    assert_eq!(Blogs::post_ids_by_tag(b"substrate".to_vec()), vec![1]);
+  });
+}
+
+#[test]
+fn create_post_should_fail_invalid_tags() {
+  with_externalities(&mut build_ext(), || {
+    assert_ok!(_create_default_blog()); // BlogId 1
+
+    let too_many_tags = (0..=DEFAULT_MAX_TAGS_PER_POST).map(|i| format!("tag{}", i).into_bytes()).collect();
+    assert_noop!(_create_post_with_tags(too_many_tags), MSG_TOO_MANY_TAGS_ON_POST);
+    assert_noop!(_create_post_with_tags(vec![b"#".to_vec()]), MSG_TAG_IS_EMPTY);
+    assert_noop!(_create_post_with_tags(vec![vec![b'a'; DEFAULT_TAG_MAX_LEN as usize + 1]]), MSG_TAG_IS_TOO_LONG);
+    assert_noop!(_create_post_with_tags(vec![b"web-3".to_vec()]), MSG_TAG_HAS_INVALID_CHARS);
+  });
+}
+
+#[test]
+fn update_post_should_work_with_tags() {
+  with_externalities(&mut build_ext(), || {
+    assert_ok!(_create_default_blog()); // BlogId 1
+    assert_ok!(_create_post_with_tags(vec![b"rust".to_vec(), b"wasm".to_vec()])); // PostId 1
+    assert_ok!(_update_post_tags(1, vec![b"wasm".to_vec(), b"Substrate".to_vec()]));
+
+    assert_eq!(Blogs::post_by_id(1).unwrap().tags, vec![b"wasm".to_vec(), b"substrate".to_vec()]);
+    assert!(Blogs::post_ids_by_tag(b"rust".to_vec()).is_empty());
+    assert_eq!(Blogs::post_ids_by_tag(b"wasm".to_vec()), vec![1]);
+    assert_eq!(Blogs::post_ids_by_tag(b"substrate".to_vec()), vec![1]);
+
+    // Unchanged tags are not counted as used again:
+    assert_eq!(Blogs::trending_tags(10)[2], (b"wasm".to_vec(), 1));
+    assert_eq!(
+      Blogs::post_history_page(1, 0, 1)[0].old_data.tags,
+      Some(vec![b"rust".to_vec(), b"wasm".to_vec()])
+    );
+  });
+}
+
+#[test]
+fn trending_tags_should_rank_tags_used_within_window() {
+  with_externalities(&mut build_ext(), || {
+    assert_ok!(_create_default_blog()); // BlogId 1
+    assert_ok!(_create_post_with_tags(vec![b"b".to_vec(), b"a".to_vec()]));
+    assert_ok!(_create_post_with_tags(vec![b"b".to_vec()]));
+    assert_ok!(_create_post_with_tags(vec![b"c".to_vec(), b"b".to_vec()]));
+
+    assert_eq!(Blogs::trending_tags(10), vec![(b"b".to_vec(), 3), (b"a".to_vec(), 1), (b"c".to_vec(), 1)]);
+    assert_eq!(Blogs::trending_tags(2), vec![(b"b".to_vec(), 3), (b"a".to_vec(), 1)]);
+
+    // Usage outside of the window is not counted and gets pruned:
+    System::set_block_number(DEFAULT_TAG_USAGE_BUCKET_LEN * DEFAULT_TAG_USAGE_BUCKETS_COUNT as u64);
+    assert!(Blogs::trending_tags(10).is_empty());
+
+    assert_ok!(_create_post_with_tags(vec![b"c".to_vec()]));
+    <Blogs as OnFinalize<u64>>::on_finalize(DEFAULT_TAG_USAGE_BUCKET_LEN * DEFAULT_TAG_USAGE_BUCKETS_COUNT as u64);
+    assert_eq!(Blogs::recent_tags(), vec![b"c".to_vec()]);
+    assert!(Blogs::tag_usage(b"a".to_vec()).is_empty());
+    assert_eq!(Blogs::trending_tags(10), vec![(b"c".to_vec(), 1)]);
+  });
+}
+
+#[test]
+fn recent_tags_should_be_pruned_in_chunks() {
+  with_externalities(&mut build_ext(), || {
+    assert_ok!(Blogs::set_tag_usage_buckets(system::RawOrigin::Root.into(), 10, 1, 2));
+    assert_ok!(_create_default_blog()); // BlogId 1
+    assert_ok!(_create_post_with_tags(vec![b"a".to_vec(), b"b".to_vec(), b"c".to_vec()]));
+
+    System::set_block_number(10);
+    assert_ok!(_create_post_with_tags(vec![b"b".to_vec()]));
+
+    // Only the last two tags are checked in the first block of a bucket:
+    finalize_block(10);
+    assert_eq!(Blogs::tag_pruning_cursor(), Some(1));
+    assert_eq!(Blogs::recent_tags(), vec![b"a".to_vec(), b"b".to_vec()]);
+    assert_eq!(Blogs::tag_usage(b"a".to_vec()), vec![(0, 1)]);
+
+    finalize_block(11);
+    assert_eq!(Blogs::tag_pruning_cursor(), None);
+    assert_eq!(Blogs::recent_tags(), vec![b"b".to_vec()]);
+    assert_eq!(Blogs::recent_tag_index(b"b".to_vec()), Some(0));
+    assert!(Blogs::tag_usage(b"a".to_vec()).is_empty());
+    assert_eq!(Blogs::trending_tags(10), vec![(b"b".to_vec(), 1)]);
+  });
+}
+
+#[test]
+fn mentions_should_be_indexed_once_per_post_and_comment() {
+  with_externalities(&mut build_ext(), || {
+    assert_ok!(_create_profile(Some(Origin::signed(ACCOUNT2)), Some(self::bob_username()), None));
+    assert_ok!(_create_default_blog()); // BlogId 1
+
+    // The author and duplicates are skipped:
+    assert_ok!(_create_post_with_mentions(vec![
+      Mention::Username(b"bob".to_vec()),
+      Mention::Account(ACCOUNT2),
+      Mention::Account(ACCOUNT1),
+    ])); // PostId 1
+    assert_eq!(Blogs::mentions_page(ACCOUNT2, 0, 10), vec![(1, None)]);
+    assert!(Blogs::mentions_page(ACCOUNT1, 0, 10).is_empty());
+
+    // Mentioning again in the same post only is not a revision:
+    assert_ok!(checked(Blogs::update_post(Origin::signed(ACCOUNT1), 1, self::post_update(None, None), vec![Mention::Account(ACCOUNT2)])));
+    assert_eq!(Blogs::mentions_page(ACCOUNT2, 0, 10), vec![(1, None)]);
+    assert_eq!(Blogs::post_revisions_count(1), 0);
+
+    assert_ok!(checked(Blogs::create_comment(Origin::signed(ACCOUNT1), 1, None, self::comment_content(), vec![Mention::Account(ACCOUNT2)])));
+    assert_eq!(Blogs::mentions_page(ACCOUNT2, 0, 10), vec![(1, None), (1, Some(1))]);
+  });
+}
+
+#[test]
+fn mentions_should_keep_only_max_per_account() {
+  with_externalities(&mut build_ext(), || {
+    assert_ok!(Blogs::set_max_mentions_per_account(system::RawOrigin::Root.into(), 2));
+    assert_ok!(_create_default_blog()); // BlogId 1
+    for _ in 0..3 {
+      assert_ok!(_create_post_with_mentions(vec![Mention::Account(ACCOUNT2)])); // PostIds 1-3
+    }
+
+    // The oldest mention is pruned along with its flag:
+    assert_eq!(Blogs::mentions_count(ACCOUNT2), 3);
+    assert_eq!(Blogs::mentions_page(ACCOUNT2, 0, 10), vec![(2, None), (3, None)]);
+    assert!(!Blogs::is_mentioned_in((ACCOUNT2, 1, None)));
+
+    // So it is indexed again, while kept mentions are not:
+    assert_ok!(checked(Blogs::update_post(Origin::signed(ACCOUNT1), 1, self::post_update(None, None), vec![Mention::Account(ACCOUNT2)])));
+    assert_ok!(checked(Blogs::update_post(Origin::signed(ACCOUNT1), 3, self::post_update(None, None), vec![Mention::Account(ACCOUNT2)])));
+    assert_eq!(Blogs::mentions_count(ACCOUNT2), 4);
+    assert_eq!(Blogs::mentions_page(ACCOUNT2, 0, 10), vec![(3, None), (1, None)]);
+    assert!(!Blogs::is_mentioned_in((ACCOUNT2, 2, None)));
+    assert!(Blogs::is_mentioned_in((ACCOUNT2, 1, None)));
+  });
+}
+
+#[test]
+fn create_post_should_fail_with_invalid_mentions() {
+  with_externalities(&mut build_ext(), || {
+    assert_ok!(_create_default_blog()); // BlogId 1
+
+    let too_many_mentions = (0..DEFAULT_MAX_MENTIONS as u64 + 1).map(|account| Mention::Account(account + 10)).collect();
+    assert_noop!(_create_post_with_mentions(too_many_mentions), MSG_TOO_MANY_MENTIONS);
+    assert_noop!(_create_post_with_mentions(vec![Mention::Username(self::bob_username())]), MSG_MENTIONED_USERNAME_NOT_FOUND);
+  });
+}
+
 // Comment tests
 #[test]
 fn create_comment_should_work() {
@@ -696,7 +1092,7 @@ fn create_comment_should_work() {
     // Check storages
     assert_eq!(Blogs::comment_ids_by_post_id(1), vec![1]);
     assert_eq!(Blogs::next_comment_id(), 2);
-    assert_eq!(Blogs::post_by_id(1).unwrap().comments_count, 1);
+    assert_eq!(Blogs::post_counters(1).comments_count, 1);
 
     // Check whether data stored correctly
     let comment = Blogs::comment_by_id(1).unwrap();
@@ -704,12 +1100,9 @@ fn create_comment_should_work() {
     assert_eq!(comment.parent_id, None);
     assert_eq!(comment.post_id, 1);
     assert_eq!(comment.created.account, ACCOUNT1);
-    assert_eq!(comment.ipfs_hash, self::comment_ipfs_hash());
-    assert_eq!(comment.upvotes_count, 0);
-    assert_eq!(comment.downvotes_count, 0);
-    assert_eq!(comment.shares_count, 0);
-    assert_eq!(comment.direct_replies_count, 0);
-    assert!(comment.edit_history.is_empty());
+    assert_eq!(comment.content, self::comment_content());
+    assert_eq!(Blogs::comment_counters(1), CommentCounters::default());
+    assert_eq!(Blogs::comment_revisions_count(1), 0);
   });
 }
 
@@ -724,11 +1117,11 @@ fn create_comment_should_work_with_parent() {
     // Check storages
     assert_eq!(Blogs::comment_ids_by_post_id(1), vec![1, 2]);
     assert_eq!(Blogs::next_comment_id(), 3);
-    assert_eq!(Blogs::post_by_id(1).unwrap().comments_count, 2);
+    assert_eq!(Blogs::post_counters(1).comments_count, 2);
 
     // Check whether data stored correctly
     assert_eq!(Blogs::comment_by_id(2).unwrap().parent_id, Some(1));
-    assert_eq!(Blogs::comment_by_id(1).unwrap().direct_replies_count, 1);
+    assert_eq!(Blogs::comment_counters(1).direct_replies_count, 1);
   });
 }
 
@@ -752,15 +1145,40 @@ fn create_comment_should_fail_parent_not_found() {
 }
 
 #[test]
-fn create_comment_should_fail_invalid_ipfs_hash() {
-  let ipfs_hash : Vec<u8> = b"QmV9tSDx9UiPeWExXEeH6aoDvmihvx6j".to_vec();
+fn create_comment_with_unknown_parent_should_not_score_post() {
+  with_externalities(&mut build_ext(), || {
+    assert_ok!(_create_default_blog()); // BlogId 1
+    assert_ok!(_create_default_post()); // PostId 1
+
+    // The parent is checked before the post is scored by another account:
+    assert_noop!(_create_comment(Some(Origin::signed(ACCOUNT2)), None, Some(1), None), MSG_UNKNOWN_PARENT_COMMENT);
+    assert_eq!(Blogs::post_score_by_account((ACCOUNT2, 1, ScoringAction::CreateComment)), None);
+    assert_eq!(Blogs::post_counters(1).comments_count, 0);
+  });
+}
+
+#[test]
+fn create_comment_should_fail_invalid_content() {
+  let content = Content::IPFS(b"QmV9tSDx9UiPeWExXEeH6aoDvmihvx6j".to_vec());
 
   with_externalities(&mut build_ext(), || {
     assert_ok!(_create_default_blog()); // BlogId 1
     assert_ok!(_create_default_post()); // PostId 1
 
     // Try to catch an error creating a comment with wrong parent
-    assert_noop!(_create_comment(None, None, None, Some(ipfs_hash)), MSG_IPFS_IS_INCORRECT);
+    assert_noop!(_create_comment(None, None, None, Some(content)), MSG_IPFS_IS_INCORRECT);
+  });
+}
+
+#[test]
+fn create_comment_should_fail_raw_content_not_utf8() {
+  let content = Content::Raw(vec![0xf0, 0x28, 0x8c, 0xbc]);
+
+  with_externalities(&mut build_ext(), || {
+    assert_ok!(_create_default_blog()); // BlogId 1
+    assert_ok!(_create_default_post()); // PostId 1
+
+    assert_noop!(_create_comment(None, None, None, Some(content)), MSG_RAW_CONTENT_IS_NOT_UTF8);
   });
 }
 
@@ -775,15 +1193,15 @@ fn update_comment_should_work() {
     assert_ok!(_update_comment(
       None,
       None,
-      Some(self::comment_update(self::subcomment_ipfs_hash()))
+      Some(self::comment_update(self::subcomment_content()))
     ));
 
     // Check whether post updates correctly
     let comment = Blogs::comment_by_id(1).unwrap();
-    assert_eq!(comment.ipfs_hash, self::subcomment_ipfs_hash());
+    assert_eq!(comment.content, self::subcomment_content());
 
     // Check whether history recorded correctly
-    assert_eq!(comment.edit_history[0].old_data.ipfs_hash, self::comment_ipfs_hash());
+    assert_eq!(Blogs::comment_history_page(1, 0, 10)[0].old_data.content, self::comment_content());
   });
 }
 
@@ -794,7 +1212,7 @@ fn update_comment_should_fail_comment_not_found() {
     assert_noop!(_update_comment(
       None,
       None,
-      Some(self::comment_update(self::subcomment_ipfs_hash()))
+      Some(self::comment_update(self::subcomment_content()))
     ),
     MSG_COMMENT_NOT_FOUND);
   });
@@ -811,43 +1229,60 @@ fn update_comment_should_fail_not_an_owner() {
     assert_noop!(_update_comment(
       Some(Origin::signed(2)),
       None,
-      Some(self::comment_update(self::subcomment_ipfs_hash()))
+      Some(self::comment_update(self::subcomment_content()))
     ),
     MSG_ONLY_COMMENT_AUTHOR_CAN_UPDATE_COMMENT);
   });
 }
 
 #[test]
-fn update_comment_should_fail_invalid_ipfs_hash() {
-  let ipfs_hash : Vec<u8> = b"QmV9tSDx9UiPeWExXEeH6aoDvmihvx6j".to_vec();
+fn update_comment_should_fail_invalid_content() {
+  let content = Content::IPFS(b"QmV9tSDx9UiPeWExXEeH6aoDvmihvx6j".to_vec());
 
   with_externalities(&mut build_ext(), || {
     assert_ok!(_create_default_blog()); // BlogId 1
     assert_ok!(_create_default_post()); // PostId 1
     assert_ok!(_create_default_comment()); // CommentId 1
 
-    // Try to catch an error updating a comment with invalid ipfs_hash
+    // Try to catch an error updating a comment with invalid content
     assert_noop!(_update_comment(
       None,
       None,
-      Some(self::comment_update(ipfs_hash))
+      Some(self::comment_update(content))
     ),
     MSG_IPFS_IS_INCORRECT);
   });
 }
 
 #[test]
-fn update_comment_should_fail_ipfs_hash_dont_differ() {
+fn update_comment_should_work_with_raw_content() {
+  let content = Content::Raw(b"Edited inline comment".to_vec());
+
+  with_externalities(&mut build_ext(), || {
+    assert_ok!(_create_default_blog()); // BlogId 1
+    assert_ok!(_create_default_post()); // PostId 1
+    assert_ok!(_create_default_comment()); // CommentId 1
+
+    assert_ok!(_update_comment(None, None, Some(self::comment_update(content.clone()))));
+    assert_eq!(Blogs::comment_by_id(1).unwrap().content, content);
+
+    let too_long = Content::Raw(vec![b'a'; DEFAULT_COMMENT_MAX_LEN as usize + 1]);
+    assert_noop!(_update_comment(None, None, Some(self::comment_update(too_long))), MSG_RAW_CONTENT_IS_TOO_LONG);
+  });
+}
+
+#[test]
+fn update_comment_should_fail_content_dont_differ() {
   with_externalities(&mut build_ext(), || {
     assert_ok!(_create_default_blog()); // BlogId 1
     assert_ok!(_create_default_post()); // PostId 1
     assert_ok!(_create_default_comment()); // CommentId 1
 
-    // Try to catch an error updating a comment with the same ipfs_hash
+    // Try to catch an error updating a comment with the same content
     assert_noop!(_update_comment(
       None,
       None,
-      Some(self::comment_update(self::comment_ipfs_hash()))
+      Some(self::comment_update(self::comment_content()))
     ),
     MSG_NEW_COMMENT_HASH_DO_NOT_DIFFER);
   });
@@ -867,9 +1302,9 @@ fn create_post_reaction_should_work_upvote() {
     assert_eq!(Blogs::next_reaction_id(), 2);
 
     // Check post reaction counters
-    let post = Blogs::post_by_id(1).unwrap();
-    assert_eq!(post.upvotes_count, 1);
-    assert_eq!(post.downvotes_count, 0);
+    let post_counters = Blogs::post_counters(1);
+    assert_eq!(post_counters.upvotes_count, 1);
+    assert_eq!(post_counters.downvotes_count, 0);
 
     // Check whether data stored correctly
     let reaction = Blogs::reaction_by_id(1).unwrap();
@@ -891,9 +1326,9 @@ fn create_post_reaction_should_work_downvote() {
     assert_eq!(Blogs::next_reaction_id(), 2);
 
     // Check post reaction counters
-    let post = Blogs::post_by_id(1).unwrap();
-    assert_eq!(post.upvotes_count, 0);
-    assert_eq!(post.downvotes_count, 1);
+    let post_counters = Blogs::post_counters(1);
+    assert_eq!(post_counters.upvotes_count, 0);
+    assert_eq!(post_counters.downvotes_count, 1);
 
     // Check whether data stored correctly
     let reaction = Blogs::reaction_by_id(1).unwrap();
@@ -935,9 +1370,9 @@ fn create_comment_reaction_should_work_upvote() {
     assert_eq!(Blogs::next_reaction_id(), 2);
 
     // Check comment reaction counters
-    let comment = Blogs::comment_by_id(1).unwrap();
-    assert_eq!(comment.upvotes_count, 1);
-    assert_eq!(comment.downvotes_count, 0);
+    let comment_counters = Blogs::comment_counters(1);
+    assert_eq!(comment_counters.upvotes_count, 1);
+    assert_eq!(comment_counters.downvotes_count, 0);
 
     // Check whether data stored correctly
     let reaction = Blogs::reaction_by_id(1).unwrap();
@@ -959,9 +1394,9 @@ fn create_comment_reaction_should_work_downvote() {
     assert_eq!(Blogs::next_reaction_id(), 2);
 
     // Check comment reaction counters
-    let comment = Blogs::comment_by_id(1).unwrap();
-    assert_eq!(comment.upvotes_count, 0);
-    assert_eq!(comment.downvotes_count, 1);
+    let comment_counters = Blogs::comment_counters(1);
+    assert_eq!(comment_counters.upvotes_count, 0);
+    assert_eq!(comment_counters.downvotes_count, 1);
 
     // Check whether data stored correctly
     let reaction = Blogs::reaction_by_id(1).unwrap();
@@ -1013,11 +1448,26 @@ fn random_score_diff_check_result() {
   with_externalities(&mut build_ext(), || {
     assert_eq!(Blogs::get_score_diff(32768, self::scoring_action_upvote_post()), 80); // 2^15
     assert_eq!(Blogs::get_score_diff(32769, self::scoring_action_upvote_post()), 80); // 2^15 + 1
-    assert_eq!(Blogs::get_score_diff(65535, self::scoring_action_upvote_post()), 80); // 2^16 - 1
+    assert_eq!(Blogs::get_score_diff(65535, self::scoring_action_upvote_post()), 84); // 2^16 - 1
     assert_eq!(Blogs::get_score_diff(65536, self::scoring_action_upvote_post()), 85); // 2^16
   });
 }
 
+#[test]
+fn score_diff_should_not_depend_on_whether_stake_counts() {
+  with_externalities(&mut build_ext(), || {
+    // Reputation 3 is two and a half levels, and the half level counts whether stake counts or not:
+    assert_ok!(Blogs::set_voting_stake_params(system::RawOrigin::Root.into(), 0, 1, 10));
+    let diff_without_stake = Blogs::get_account_score_diff(&ACCOUNT1, 3, self::scoring_action_upvote_post());
+    assert_ok!(Blogs::set_voting_stake_params(system::RawOrigin::Root.into(), 1, 1, 10));
+    let diff_with_stake = Blogs::get_account_score_diff(&ACCOUNT1, 3, self::scoring_action_upvote_post());
+
+    assert_eq!(diff_without_stake, Blogs::get_score_diff(3, self::scoring_action_upvote_post()));
+    assert_eq!(diff_without_stake, 250 * DEFAULT_UPVOTE_POST_ACTION_WEIGHT / 100);
+    assert_eq!(diff_with_stake, (250 * 99 * DEFAULT_UPVOTE_POST_ACTION_WEIGHT as i64 / 10_000) as i16);
+  });
+}
+
 //--------------------------------------------------------------------------------------------------
 
 #[test]
@@ -1027,7 +1477,7 @@ fn change_blog_score_should_work_follow_blog() {
 
     assert_ok!(Blogs::follow_blog(Origin::signed(ACCOUNT2), 1));
 
-    assert_eq!(Blogs::blog_by_id(1).unwrap().score, DEFAULT_FOLLOW_BLOG_ACTION_WEIGHT as i32);
+    assert_eq!(Blogs::blog_counters(1).score, DEFAULT_FOLLOW_BLOG_ACTION_WEIGHT as i32);
     assert_eq!(Blogs::social_account_by_id(ACCOUNT1).unwrap().reputation, 1 + DEFAULT_FOLLOW_BLOG_ACTION_WEIGHT as u32);
     assert_eq!(Blogs::social_account_by_id(ACCOUNT2).unwrap().reputation, 1);
   });
@@ -1041,7 +1491,7 @@ fn change_blog_score_should_work_revert_follow_blog() {
     assert_ok!(Blogs::follow_blog(Origin::signed(ACCOUNT2), 1));
     assert_ok!(Blogs::unfollow_blog(Origin::signed(ACCOUNT2), 1));
 
-    assert_eq!(Blogs::blog_by_id(1).unwrap().score, 0);
+    assert_eq!(Blogs::blog_counters(1).score, 0);
     assert_eq!(Blogs::social_account_by_id(ACCOUNT1).unwrap().reputation, 1);
     assert_eq!(Blogs::social_account_by_id(ACCOUNT2).unwrap().reputation, 1);
   });
@@ -1054,7 +1504,7 @@ fn change_blog_score_should_work_upvote_post() {
     assert_ok!(_create_default_post());
     assert_ok!(_create_post_reaction(Some(Origin::signed(ACCOUNT2)), None, None)); // ReactionId 1
 
-    assert_eq!(Blogs::blog_by_id(1).unwrap().score, DEFAULT_UPVOTE_POST_ACTION_WEIGHT as i32);
+    assert_eq!(Blogs::blog_counters(1).score, DEFAULT_UPVOTE_POST_ACTION_WEIGHT as i32);
     assert_eq!(Blogs::social_account_by_id(ACCOUNT1).unwrap().reputation, 1 + DEFAULT_UPVOTE_POST_ACTION_WEIGHT as u32);
   });
 }
@@ -1066,7 +1516,7 @@ fn change_blog_score_should_work_downvote_post() {
     assert_ok!(_create_default_post());
     assert_ok!(_create_post_reaction(Some(Origin::signed(ACCOUNT2)), None, Some(self::reaction_downvote()))); // ReactionId 1
     
-    assert_eq!(Blogs::blog_by_id(1).unwrap().score, DEFAULT_DOWNVOTE_POST_ACTION_WEIGHT as i32);
+    assert_eq!(Blogs::blog_counters(1).score, DEFAULT_DOWNVOTE_POST_ACTION_WEIGHT as i32);
     assert_eq!(Blogs::social_account_by_id(ACCOUNT1).unwrap().reputation, 1);
   });
 }
@@ -1080,8 +1530,8 @@ fn change_post_score_should_work_create_comment() {
     assert_ok!(_create_default_post()); // PostId 1
     assert_ok!(_create_comment(Some(Origin::signed(ACCOUNT2)), None, None, None)); // CommentId 1
 
-    assert_eq!(Blogs::post_by_id(1).unwrap().score, DEFAULT_CREATE_COMMENT_ACTION_WEIGHT as i32);
-    assert_eq!(Blogs::blog_by_id(1).unwrap().score, DEFAULT_CREATE_COMMENT_ACTION_WEIGHT as i32);
+    assert_eq!(Blogs::post_counters(1).score, DEFAULT_CREATE_COMMENT_ACTION_WEIGHT as i32);
+    assert_eq!(Blogs::blog_counters(1).score, DEFAULT_CREATE_COMMENT_ACTION_WEIGHT as i32);
     assert_eq!(Blogs::social_account_by_id(ACCOUNT1).unwrap().reputation, 1 + DEFAULT_CREATE_COMMENT_ACTION_WEIGHT as u32);
     assert_eq!(Blogs::post_score_by_account((ACCOUNT2, 1, self::scoring_action_create_comment())), Some(DEFAULT_CREATE_COMMENT_ACTION_WEIGHT));
   });
@@ -1095,7 +1545,7 @@ fn change_post_score_should_work_upvote() {
 
     assert_ok!(_create_post_reaction(Some(Origin::signed(ACCOUNT2)), None, None));
 
-    assert_eq!(Blogs::post_by_id(1).unwrap().score, DEFAULT_UPVOTE_POST_ACTION_WEIGHT as i32);
+    assert_eq!(Blogs::post_counters(1).score, DEFAULT_UPVOTE_POST_ACTION_WEIGHT as i32);
     assert_eq!(Blogs::social_account_by_id(ACCOUNT1).unwrap().reputation, 1 + DEFAULT_UPVOTE_POST_ACTION_WEIGHT as u32);
     assert_eq!(Blogs::post_score_by_account((ACCOUNT2, 1, self::scoring_action_upvote_post())), Some(DEFAULT_UPVOTE_POST_ACTION_WEIGHT));
   });
@@ -1109,7 +1559,7 @@ fn change_post_score_should_work_downvote() {
 
     assert_ok!(_create_post_reaction(Some(Origin::signed(ACCOUNT2)), None, Some(self::reaction_downvote())));
 
-    assert_eq!(Blogs::post_by_id(1).unwrap().score, DEFAULT_DOWNVOTE_POST_ACTION_WEIGHT as i32);
+    assert_eq!(Blogs::post_counters(1).score, DEFAULT_DOWNVOTE_POST_ACTION_WEIGHT as i32);
     assert_eq!(Blogs::social_account_by_id(ACCOUNT1).unwrap().reputation, 1);
     assert_eq!(Blogs::post_score_by_account((ACCOUNT2, 1, self::scoring_action_downvote_post())), Some(DEFAULT_DOWNVOTE_POST_ACTION_WEIGHT));
   });
@@ -1124,7 +1574,7 @@ fn change_post_score_should_revert_upvote() {
     assert_ok!(_create_post_reaction(Some(Origin::signed(ACCOUNT2)), None, None)); // ReactionId 1
     assert_ok!(_delete_post_reaction(Some(Origin::signed(ACCOUNT2)), None, 1));
 
-    assert_eq!(Blogs::post_by_id(1).unwrap().score, 0);
+    assert_eq!(Blogs::post_counters(1).score, 0);
     assert_eq!(Blogs::social_account_by_id(ACCOUNT1).unwrap().reputation, 1);
     assert_eq!(Blogs::post_score_by_account((ACCOUNT2, 1, self::scoring_action_upvote_post())), None);
   });
@@ -1139,7 +1589,7 @@ fn change_post_score_should_revert_downvote() {
     assert_ok!(_create_post_reaction(Some(Origin::signed(ACCOUNT2)), None, Some(self::reaction_downvote()))); // ReactionId 1
     assert_ok!(_delete_post_reaction(Some(Origin::signed(ACCOUNT2)), None, 1));
 
-    assert_eq!(Blogs::post_by_id(1).unwrap().score, 0);
+    assert_eq!(Blogs::post_counters(1).score, 0);
     assert_eq!(Blogs::social_account_by_id(ACCOUNT1).unwrap().reputation, 1);
     assert_eq!(Blogs::post_score_by_account((ACCOUNT2, 1, self::scoring_action_downvote_post())), None);
   });
@@ -1154,7 +1604,7 @@ fn change_post_score_cancel_upvote_with_downvote() {
     assert_ok!(_create_post_reaction(Some(Origin::signed(ACCOUNT2)), None, None)); // ReactionId 1
     assert_ok!(_update_post_reaction(Some(Origin::signed(ACCOUNT2)), None, 1, Some(self::reaction_downvote())));
 
-    assert_eq!(Blogs::post_by_id(1).unwrap().score, DEFAULT_DOWNVOTE_POST_ACTION_WEIGHT as i32);
+    assert_eq!(Blogs::post_counters(1).score, DEFAULT_DOWNVOTE_POST_ACTION_WEIGHT as i32);
     assert_eq!(Blogs::social_account_by_id(ACCOUNT1).unwrap().reputation, 1);
     assert_eq!(Blogs::post_score_by_account((ACCOUNT2, 1, self::scoring_action_upvote_post())), None);
     assert_eq!(Blogs::post_score_by_account((ACCOUNT2, 1, self::scoring_action_downvote_post())), Some(DEFAULT_DOWNVOTE_POST_ACTION_WEIGHT));
@@ -1170,7 +1620,7 @@ fn change_post_score_cancel_downvote_with_upvote() {
     assert_ok!(_create_post_reaction(Some(Origin::signed(ACCOUNT2)), None, Some(self::reaction_downvote()))); // ReactionId 1
     assert_ok!(_update_post_reaction(Some(Origin::signed(ACCOUNT2)), None, 1, None));
 
-    assert_eq!(Blogs::post_by_id(1).unwrap().score, DEFAULT_UPVOTE_POST_ACTION_WEIGHT as i32);
+    assert_eq!(Blogs::post_counters(1).score, DEFAULT_UPVOTE_POST_ACTION_WEIGHT as i32);
     assert_eq!(Blogs::social_account_by_id(ACCOUNT1).unwrap().reputation, 1 + DEFAULT_UPVOTE_POST_ACTION_WEIGHT as u32);
     assert_eq!(Blogs::post_score_by_account((ACCOUNT2, 1, self::scoring_action_downvote_post())), None);
     assert_eq!(Blogs::post_score_by_account((ACCOUNT2, 1, self::scoring_action_upvote_post())), Some(DEFAULT_UPVOTE_POST_ACTION_WEIGHT));
@@ -1252,7 +1702,7 @@ fn change_comment_score_should_work_upvote() {
     assert_ok!(_create_post(Some(Origin::signed(ACCOUNT2)), None, None, None));
     assert_ok!(_create_comment(Some(Origin::signed(ACCOUNT2)), None, None, None));
     assert_ok!(_change_comment_score_by_id(ACCOUNT1, 1, self::scoring_action_upvote_comment()));
-    assert_eq!(Blogs::comment_by_id(1).unwrap().score, DEFAULT_UPVOTE_COMMENT_ACTION_WEIGHT as i32);
+    assert_eq!(Blogs::comment_counters(1).score, DEFAULT_UPVOTE_COMMENT_ACTION_WEIGHT as i32);
     assert_eq!(Blogs::social_account_by_id(ACCOUNT1).unwrap().reputation, 1);
     assert_eq!(Blogs::social_account_by_id(ACCOUNT2).unwrap().reputation, 1 + DEFAULT_UPVOTE_COMMENT_ACTION_WEIGHT as u32);
     assert_eq!(Blogs::comment_score_by_account((ACCOUNT1, 1, self::scoring_action_upvote_comment())), Some(DEFAULT_UPVOTE_COMMENT_ACTION_WEIGHT));
@@ -1266,7 +1716,7 @@ fn change_comment_score_should_work_downvote() {
     assert_ok!(_create_post(Some(Origin::signed(ACCOUNT2)), None, None, None));
     assert_ok!(_create_comment(Some(Origin::signed(ACCOUNT2)), None, None, None));
     assert_ok!(_change_comment_score_by_id(ACCOUNT1, 1, self::scoring_action_downvote_comment()));
-    assert_eq!(Blogs::comment_by_id(1).unwrap().score, DEFAULT_DOWNVOTE_COMMENT_ACTION_WEIGHT as i32);
+    assert_eq!(Blogs::comment_counters(1).score, DEFAULT_DOWNVOTE_COMMENT_ACTION_WEIGHT as i32);
     assert_eq!(Blogs::social_account_by_id(ACCOUNT1).unwrap().reputation, 1);
     assert_eq!(Blogs::social_account_by_id(ACCOUNT2).unwrap().reputation, 1);
     assert_eq!(Blogs::comment_score_by_account((ACCOUNT1, 1, self::scoring_action_downvote_comment())), Some(DEFAULT_DOWNVOTE_COMMENT_ACTION_WEIGHT));
@@ -1281,7 +1731,7 @@ fn change_comment_score_should_revert_upvote() {
     assert_ok!(_create_comment(Some(Origin::signed(ACCOUNT2)), None, None, None));
     assert_ok!(_change_comment_score_by_id(ACCOUNT1, 1, self::scoring_action_upvote_comment()));
     assert_ok!(_change_comment_score_by_id(ACCOUNT1, 1, self::scoring_action_upvote_comment()));
-    assert_eq!(Blogs::comment_by_id(1).unwrap().score, 0);
+    assert_eq!(Blogs::comment_counters(1).score, 0);
     assert_eq!(Blogs::social_account_by_id(ACCOUNT1).unwrap().reputation, 1);
     assert_eq!(Blogs::social_account_by_id(ACCOUNT2).unwrap().reputation, 1);
     assert_eq!(Blogs::comment_score_by_account((ACCOUNT1, 1, self::scoring_action_upvote_comment())), None);
@@ -1296,7 +1746,7 @@ fn change_comment_score_should_revert_downvote() {
     assert_ok!(_create_comment(Some(Origin::signed(ACCOUNT2)), None, None, None));
     assert_ok!(_change_comment_score_by_id(ACCOUNT1, 1, self::scoring_action_downvote_comment()));
     assert_ok!(_change_comment_score_by_id(ACCOUNT1, 1, self::scoring_action_downvote_comment()));
-    assert_eq!(Blogs::comment_by_id(1).unwrap().score, 0);
+    assert_eq!(Blogs::comment_counters(1).score, 0);
     assert_eq!(Blogs::social_account_by_id(ACCOUNT1).unwrap().reputation, 1);
     assert_eq!(Blogs::social_account_by_id(ACCOUNT2).unwrap().reputation, 1);
     assert_eq!(Blogs::comment_score_by_account((ACCOUNT1, 1, self::scoring_action_downvote_comment())), None);
@@ -1313,7 +1763,7 @@ fn change_comment_score_check_cancel_upvote() {
     assert_ok!(_change_comment_score_by_id(ACCOUNT1, 1, self::scoring_action_upvote_comment()));
 
     assert_ok!(_change_comment_score_by_id(ACCOUNT1, 1, self::scoring_action_downvote_comment()));
-    assert_eq!(Blogs::comment_by_id(1).unwrap().score, DEFAULT_DOWNVOTE_COMMENT_ACTION_WEIGHT as i32);
+    assert_eq!(Blogs::comment_counters(1).score, DEFAULT_DOWNVOTE_COMMENT_ACTION_WEIGHT as i32);
     assert_eq!(Blogs::social_account_by_id(ACCOUNT1).unwrap().reputation, 1);
     assert_eq!(Blogs::social_account_by_id(ACCOUNT2).unwrap().reputation, 1);
     assert_eq!(Blogs::comment_score_by_account((ACCOUNT1, 1, self::scoring_action_upvote_comment())), None);
@@ -1331,7 +1781,7 @@ fn change_comment_score_check_cancel_downvote() {
     assert_ok!(_change_comment_score_by_id(ACCOUNT1, 1, self::scoring_action_downvote_comment()));
 
     assert_ok!(_change_comment_score_by_id(ACCOUNT1, 1, self::scoring_action_upvote_comment()));
-    assert_eq!(Blogs::comment_by_id(1).unwrap().score, DEFAULT_UPVOTE_COMMENT_ACTION_WEIGHT as i32);
+    assert_eq!(Blogs::comment_counters(1).score, DEFAULT_UPVOTE_COMMENT_ACTION_WEIGHT as i32);
     assert_eq!(Blogs::social_account_by_id(ACCOUNT1).unwrap().reputation, 1);
     assert_eq!(Blogs::social_account_by_id(ACCOUNT2).unwrap().reputation, 1 + DEFAULT_UPVOTE_COMMENT_ACTION_WEIGHT as u32);
     assert_eq!(Blogs::comment_score_by_account((ACCOUNT1, 1, self::scoring_action_downvote_comment())), None);
@@ -1360,7 +1810,7 @@ fn share_post_should_work() {
     assert_ok!(_create_post(
       Some(Origin::signed(ACCOUNT2)),
       Some(2),
-      Some(vec![]),
+      Some(Content::None),
       Some(self::extension_shared_post(1))
     )); // Share PostId 1 on BlogId 2 by ACCOUNT2
 
@@ -1373,13 +1823,13 @@ fn share_post_should_work() {
     assert_eq!(Blogs::shared_post_ids_by_original_post_id(1), vec![2]);
 
     // Check whether data stored correctly
-    assert_eq!(Blogs::post_by_id(1).unwrap().shares_count, 1);
+    assert_eq!(Blogs::post_counters(1).shares_count, 1);
 
     let shared_post = Blogs::post_by_id(2).unwrap();
 
     assert_eq!(shared_post.blog_id, 2);
     assert_eq!(shared_post.created.account, ACCOUNT2);
-    assert!(shared_post.ipfs_hash.is_empty());
+    assert!(shared_post.content.is_none());
     assert_eq!(shared_post.extension, self::extension_shared_post(1));
   });
 }
@@ -1392,7 +1842,7 @@ fn share_post_should_work_share_own_post() {
     assert_ok!(_create_post(
       Some(Origin::signed(ACCOUNT1)),
       Some(1),
-      Some(vec![]),
+      Some(Content::None),
       Some(self::extension_shared_post(1))
     )); // Share PostId 1
 
@@ -1404,12 +1854,12 @@ fn share_post_should_work_share_own_post() {
     assert_eq!(Blogs::shared_post_ids_by_original_post_id(1), vec![2]);
 
     // Check whether data stored correctly
-    assert_eq!(Blogs::post_by_id(1).unwrap().shares_count, 1);
+    assert_eq!(Blogs::post_counters(1).shares_count, 1);
 
     let shared_post = Blogs::post_by_id(2).unwrap();
     assert_eq!(shared_post.blog_id, 1);
     assert_eq!(shared_post.created.account, ACCOUNT1);
-    assert!(shared_post.ipfs_hash.is_empty());
+    assert!(shared_post.content.is_none());
     assert_eq!(shared_post.extension, self::extension_shared_post(1));
   });
 }
@@ -1423,11 +1873,11 @@ fn share_post_should_change_score() {
     assert_ok!(_create_post(
       Some(Origin::signed(ACCOUNT2)),
       Some(2),
-      Some(vec![]),
+      Some(Content::None),
       Some(self::extension_shared_post(1))
     )); // Share PostId 1 on BlogId 2 by ACCOUNT2
 
-    assert_eq!(Blogs::post_by_id(1).unwrap().score, DEFAULT_SHARE_POST_ACTION_WEIGHT as i32);
+    assert_eq!(Blogs::post_counters(1).score, DEFAULT_SHARE_POST_ACTION_WEIGHT as i32);
     assert_eq!(Blogs::social_account_by_id(ACCOUNT1).unwrap().reputation, 1 + DEFAULT_SHARE_POST_ACTION_WEIGHT as u32);
     assert_eq!(Blogs::post_score_by_account((ACCOUNT2, 1, self::scoring_action_share_post())), Some(DEFAULT_SHARE_POST_ACTION_WEIGHT));
   });
@@ -1441,11 +1891,11 @@ fn share_post_should_not_change_score() {
     assert_ok!(_create_post(
       Some(Origin::signed(ACCOUNT1)),
       Some(1),
-      Some(vec![]),
+      Some(Content::None),
       Some(self::extension_shared_post(1))
     )); // Share PostId
 
-    assert_eq!(Blogs::post_by_id(1).unwrap().score, 0);
+    assert_eq!(Blogs::post_counters(1).score, 0);
     assert_eq!(Blogs::social_account_by_id(ACCOUNT1).unwrap().reputation, 1);
     assert_eq!(Blogs::post_score_by_account((ACCOUNT1, 1, self::scoring_action_share_post())), None);
   });
@@ -1460,7 +1910,7 @@ fn share_post_should_fail_original_post_not_found() {
     assert_noop!(_create_post(
       Some(Origin::signed(ACCOUNT2)),
       Some(2),
-      Some(vec![]),
+      Some(Content::None),
       Some(self::extension_shared_post(1))),
       
     MSG_ORIGINAL_POST_NOT_FOUND);
@@ -1476,7 +1926,7 @@ fn share_post_should_fail_share_shared_post() {
     assert_ok!(_create_post(
       Some(Origin::signed(ACCOUNT2)),
       Some(2),
-      Some(vec![]),
+      Some(Content::None),
       Some(self::extension_shared_post(1)))
     );
 
@@ -1484,13 +1934,47 @@ fn share_post_should_fail_share_shared_post() {
     assert_noop!(_create_post(
       Some(Origin::signed(ACCOUNT1)),
       Some(1),
-      Some(vec![]),
+      Some(Content::None),
       Some(self::extension_shared_post(2))),
       
     MSG_CANNOT_SHARE_SHARED_POST);
   });
 }
 
+#[test]
+fn quote_shared_post_should_work() {
+  with_externalities(&mut build_ext(), || {
+    assert_ok!(_create_default_blog()); // BlogId 1
+    assert_ok!(_create_default_post()); // PostId 1
+    assert_ok!(_create_post(Some(Origin::signed(ACCOUNT2)), None, Some(Content::None), Some(self::extension_shared_post(1)))); // PostId 2
+    assert_ok!(_create_post(None, None, Some(self::blog_content()), Some(self::extension_shared_post(2)))); // PostId 3 quotes PostId 2
+
+    assert_eq!(Blogs::post_by_id(3).unwrap().content, self::blog_content());
+    assert_eq!(Blogs::attribution_chain(3), vec![2, 1]);
+    assert_eq!(Blogs::shared_post_ids_by_original_post_id(2), vec![3]);
+    assert_eq!(Blogs::post_counters(2).shares_count, 1);
+  });
+}
+
+#[test]
+fn quote_shared_post_should_fail() {
+  with_externalities(&mut build_ext(), || {
+    <MaxQuoteDepth<Test>>::put(1);
+    assert_ok!(_create_default_blog()); // BlogId 1
+    assert_ok!(_create_default_post()); // PostId 1
+
+    assert_noop!(
+      _create_post(None, None, Some(Content::Raw(vec![0xff])), Some(self::extension_shared_post(1))),
+      MSG_RAW_CONTENT_IS_NOT_UTF8
+    );
+    assert_ok!(_create_post(Some(Origin::signed(ACCOUNT2)), None, Some(self::blog_content()), Some(self::extension_shared_post(1)))); // PostId 2
+    assert_noop!(
+      _create_post(None, None, Some(self::blog_content()), Some(self::extension_shared_post(2))),
+      MSG_QUOTE_CHAIN_IS_TOO_LONG
+    );
+  });
+}
+
 #[test]
 fn share_comment_should_work() {
   with_externalities(&mut build_ext(), || {
@@ -1501,7 +1985,7 @@ fn share_comment_should_work() {
     assert_ok!(_create_post(
       Some(Origin::signed(ACCOUNT2)),
       Some(2),
-      Some(vec![]),
+      Some(Content::None),
       Some(self::extension_shared_comment(1))
     )); // Share CommentId 1 on BlogId 2 by ACCOUNT2
 
@@ -1514,13 +1998,13 @@ fn share_comment_should_work() {
     assert_eq!(Blogs::shared_post_ids_by_original_comment_id(1), vec![2]);
 
     // Check whether data stored correctly
-    assert_eq!(Blogs::comment_by_id(1).unwrap().shares_count, 1);
+    assert_eq!(Blogs::comment_counters(1).shares_count, 1);
 
     let shared_post = Blogs::post_by_id(2).unwrap();
 
     assert_eq!(shared_post.blog_id, 2);
     assert_eq!(shared_post.created.account, ACCOUNT2);
-    assert!(shared_post.ipfs_hash.is_empty());
+    assert!(shared_post.content.is_none());
     assert_eq!(shared_post.extension, self::extension_shared_comment(1));
   });
 }
@@ -1535,11 +2019,11 @@ fn share_comment_should_change_score() {
     assert_ok!(_create_post(
       Some(Origin::signed(ACCOUNT2)),
       Some(2),
-      Some(vec![]),
+      Some(Content::None),
       Some(self::extension_shared_comment(1))
     )); // Share CommentId 1 on BlogId 2 by ACCOUNT2
 
-    assert_eq!(Blogs::comment_by_id(1).unwrap().score, DEFAULT_SHARE_COMMENT_ACTION_WEIGHT as i32);
+    assert_eq!(Blogs::comment_counters(1).score, DEFAULT_SHARE_COMMENT_ACTION_WEIGHT as i32);
     assert_eq!(Blogs::social_account_by_id(ACCOUNT1).unwrap().reputation, 1 + DEFAULT_SHARE_COMMENT_ACTION_WEIGHT as u32);
     assert_eq!(Blogs::comment_score_by_account((ACCOUNT2, 1, self::scoring_action_share_comment())), Some(DEFAULT_SHARE_COMMENT_ACTION_WEIGHT));
   });
@@ -1555,62 +2039,159 @@ fn share_comment_should_fail_original_comment_not_found() {
     assert_noop!(_create_post(
       Some(Origin::signed(ACCOUNT2)),
       Some(2),
-      Some(vec![]),
+      Some(Content::None),
       Some(self::extension_shared_comment(1))),
       
     MSG_ORIGINAL_COMMENT_NOT_FOUND);
   });
 }
 
-// Profiles tests
+// Visibility tests
 
 #[test]
-fn create_profile_should_work() {
+fn create_draft_post_should_not_list_it() {
   with_externalities(&mut build_ext(), || {
-    assert_ok!(_create_default_profile()); // AccountId 1
+    assert_ok!(_create_default_blog()); // BlogId 1
+    assert_ok!(_create_post_with_visibility(PostVisibility::Draft)); // PostId 1
+    assert_ok!(_create_post_with_visibility(PostVisibility::Unlisted)); // PostId 2
 
-    let profile = Blogs::social_account_by_id(ACCOUNT1).unwrap().profile.unwrap();
-    assert_eq!(profile.created.account, ACCOUNT1);
-    assert_eq!(profile.updated, None);
-    assert_eq!(profile.username, self::alice_username());
-    assert_eq!(profile.ipfs_hash, self::profile_ipfs_hash());
-    assert!(profile.edit_history.is_empty());
-    assert_eq!(Blogs::account_by_profile_username(self::alice_username()), Some(ACCOUNT1));
+    assert_eq!(Blogs::post_by_id(1).unwrap().visibility, PostVisibility::Draft);
+    assert_eq!(Blogs::post_by_id(2).unwrap().visibility, PostVisibility::Unlisted);
+    assert!(Blogs::post_ids_by_blog_id(1).is_empty());
+    assert_eq!(Blogs::blog_counters(1).posts_count, 0);
   });
 }
 
 #[test]
-fn create_profile_should_fail_profile_exists() {
+fn draft_post_should_be_visible_to_author_and_blog_writers_only() {
   with_externalities(&mut build_ext(), || {
-    assert_ok!(_create_default_profile()); // AccountId 1
-    assert_noop!(_create_default_profile(), MSG_PROFILE_ALREADY_EXISTS);
+    assert_ok!(_create_default_blog()); // BlogId 1
+    assert_ok!(_update_blog(None, None, Some(self::blog_update(Some(vec![ACCOUNT2]), None, None))));
+    assert_ok!(_create_post_with_visibility(PostVisibility::Draft)); // PostId 1
+    assert_ok!(_create_post_with_visibility(PostVisibility::Unlisted)); // PostId 2
+
+    let draft = Blogs::post_by_id(1).unwrap();
+    assert!(Blogs::is_post_visible_to(&draft, &ACCOUNT1));
+    assert!(Blogs::is_post_visible_to(&draft, &ACCOUNT2));
+    assert!(!Blogs::is_post_visible_to(&draft, &VERIFIER));
+    assert!(Blogs::is_post_visible_to(&Blogs::post_by_id(2).unwrap(), &VERIFIER));
   });
 }
 
 #[test]
-fn create_profile_should_fail_invalid_ipfs_hash() {
-  let ipfs_hash : Vec<u8> = b"QmV9tSDx9UiPeWExXEeH6aoDvmihvx6j".to_vec();
-
+fn publish_post_should_work() {
   with_externalities(&mut build_ext(), || {
-    assert_noop!(_create_profile(None, None, Some(ipfs_hash)), MSG_IPFS_IS_INCORRECT);
+    assert_ok!(_create_default_blog()); // BlogId 1
+    assert_ok!(_create_post_with_visibility(PostVisibility::Draft)); // PostId 1
+    assert_ok!(_update_post_tags(1, vec![b"rust".to_vec()]));
+    assert!(Blogs::post_ids_by_tag(b"rust".to_vec()).is_empty());
+
+    assert_ok!(checked(Blogs::publish_post(Origin::signed(ACCOUNT1), 1)));
+    assert_eq!(Blogs::post_by_id(1).unwrap().visibility, PostVisibility::Public);
+    assert_eq!(Blogs::post_ids_by_blog_id(1), vec![1]);
+    assert_eq!(Blogs::blog_counters(1).posts_count, 1);
+    assert_eq!(Blogs::post_ids_by_tag(b"rust".to_vec()), vec![1]);
   });
 }
 
 #[test]
-fn create_profile_should_fail_username_is_busy() {
+fn publish_post_should_fail_not_owner_or_already_public() {
   with_externalities(&mut build_ext(), || {
-    assert_ok!(_create_default_profile()); // AccountId 1
-    assert_noop!(_create_profile(Some(Origin::signed(ACCOUNT2)), None, None), MSG_USERNAME_IS_BUSY);
+    assert_ok!(_create_default_blog()); // BlogId 1
+    assert_ok!(_create_default_post()); // PostId 1
+    assert_ok!(_create_post_with_visibility(PostVisibility::Unlisted)); // PostId 2
+
+    assert_noop!(Blogs::publish_post(Origin::signed(ACCOUNT1), 1), MSG_POST_IS_ALREADY_PUBLIC);
+    assert_noop!(Blogs::publish_post(Origin::signed(ACCOUNT2), 2), MSG_ONLY_POST_OWNER_CAN_PUBLISH_POST);
   });
 }
 
 #[test]
-fn create_profile_should_fail_too_short_username() {
-  let username : Vec<u8> = vec![97; (DEFAULT_USERNAME_MIN_LEN - 1) as usize];
-
+fn unpublished_post_should_reject_interactions() {
   with_externalities(&mut build_ext(), || {
-    assert_ok!(_create_default_profile()); // AccountId 1
-    assert_noop!(_create_profile(Some(Origin::signed(ACCOUNT2)), Some(username), None), MSG_USERNAME_TOO_SHORT);
+    assert_ok!(_create_default_blog()); // BlogId 1
+    assert_ok!(_create_post_with_visibility(PostVisibility::Unlisted)); // PostId 1
+
+    assert_noop!(_create_default_comment(), MSG_POST_IS_NOT_PUBLIC);
+    assert_noop!(_create_default_post_reaction(), MSG_POST_IS_NOT_PUBLIC);
+    assert_noop!(
+      _create_post(Some(Origin::signed(ACCOUNT2)), None, Some(Content::None), Some(self::extension_shared_post(1))),
+      MSG_POST_IS_NOT_PUBLIC
+    );
+  });
+}
+
+#[test]
+fn create_post_should_fail_unpublished_share_or_mention_in_draft() {
+  with_externalities(&mut build_ext(), || {
+    assert_ok!(_create_default_blog()); // BlogId 1
+    assert_ok!(_create_default_post()); // PostId 1
+
+    assert_noop!(Blogs::create_post(
+      Origin::signed(ACCOUNT2), 1, Content::None, self::extension_shared_post(1), vec![], vec![], PostVisibility::Draft
+    ), MSG_SHARE_SHOULD_BE_PUBLIC);
+    assert_noop!(Blogs::create_post(
+      Origin::signed(ACCOUNT1), 1, self::post_content(), self::extension_regular_post(), vec![], vec![Mention::Account(ACCOUNT2)], PostVisibility::Draft
+    ), MSG_CANNOT_MENTION_IN_DRAFT);
+  });
+}
+
+// Profiles tests
+
+#[test]
+fn create_profile_should_work() {
+  with_externalities(&mut build_ext(), || {
+    assert_ok!(_create_default_profile()); // AccountId 1
+
+    let profile = Blogs::social_account_by_id(ACCOUNT1).unwrap().profile.unwrap();
+    assert_eq!(profile.created.account, ACCOUNT1);
+    assert_eq!(profile.updated, None);
+    assert_eq!(profile.username, self::alice_username());
+    assert_eq!(profile.content, self::profile_content());
+    assert_eq!(Blogs::profile_revisions_count(ACCOUNT1), 0);
+    assert_eq!(Blogs::account_by_profile_username(b"alice".to_vec()), Some(ACCOUNT1));
+  });
+}
+
+#[test]
+fn create_profile_should_fail_profile_exists() {
+  with_externalities(&mut build_ext(), || {
+    assert_ok!(_create_default_profile()); // AccountId 1
+    assert_noop!(_create_default_profile(), MSG_PROFILE_ALREADY_EXISTS);
+  });
+}
+
+#[test]
+fn create_profile_should_fail_invalid_content() {
+  let content = Content::IPFS(b"QmV9tSDx9UiPeWExXEeH6aoDvmihvx6j".to_vec());
+
+  with_externalities(&mut build_ext(), || {
+    assert_noop!(_create_profile(None, None, Some(content)), MSG_IPFS_IS_INCORRECT);
+  });
+}
+
+#[test]
+fn create_profile_should_fail_raw_content() {
+  with_externalities(&mut build_ext(), || {
+    assert_noop!(_create_profile(None, None, Some(Content::Raw(b"About me".to_vec()))), MSG_RAW_CONTENT_IS_NOT_ALLOWED);
+  });
+}
+
+#[test]
+fn create_profile_should_fail_username_is_busy() {
+  with_externalities(&mut build_ext(), || {
+    assert_ok!(_create_default_profile()); // AccountId 1
+    assert_noop!(_create_profile(Some(Origin::signed(ACCOUNT2)), None, None), MSG_USERNAME_IS_BUSY);
+  });
+}
+
+#[test]
+fn create_profile_should_fail_too_short_username() {
+  let username : Vec<u8> = vec![97; (DEFAULT_USERNAME_MIN_LEN - 1) as usize];
+
+  with_externalities(&mut build_ext(), || {
+    assert_ok!(_create_default_profile()); // AccountId 1
+    assert_noop!(_create_profile(Some(Origin::signed(ACCOUNT2)), Some(username), None), MSG_USERNAME_TOO_SHORT);
   });
 }
 
@@ -1638,21 +2219,89 @@ fn create_profile_should_fail_invalid_username() {
 fn update_profile_should_work() {
   with_externalities(&mut build_ext(), || {
     assert_ok!(_create_default_profile()); // AccountId 1
-    assert_ok!(_update_profile(None, Some(self::bob_username()), Some(self::blog_ipfs_hash())));
+    assert_ok!(_update_profile(None, Some(self::bob_username()), Some(self::blog_content())));
 
     // Check whether profile updated correctly
     let profile = Blogs::social_account_by_id(ACCOUNT1).unwrap().profile.unwrap();
     assert!(profile.updated.is_some());
     assert_eq!(profile.username, self::bob_username());
-    assert_eq!(profile.ipfs_hash, self::blog_ipfs_hash());
+    assert_eq!(profile.content, self::blog_content());
 
     // Check storages
-    assert_eq!(Blogs::account_by_profile_username(self::alice_username()), None);
-    assert_eq!(Blogs::account_by_profile_username(self::bob_username()), Some(ACCOUNT1));
+    assert_eq!(Blogs::account_by_profile_username(b"alice".to_vec()), None);
+    assert_eq!(Blogs::account_by_profile_username(b"bob".to_vec()), Some(ACCOUNT1));
 
     // Check whether profile history is written correctly
-    assert_eq!(profile.edit_history[0].old_data.username, Some(self::alice_username()));
-    assert_eq!(profile.edit_history[0].old_data.ipfs_hash, Some(self::profile_ipfs_hash()));
+    let history = Blogs::profile_history_page(ACCOUNT1, 0, 10);
+    assert_eq!(history[0].old_data.username, Some(self::alice_username()));
+    assert_eq!(history[0].old_data.content, Some(self::profile_content()));
+  });
+}
+
+#[test]
+fn create_profile_should_fail_username_differs_only_in_case() {
+  with_externalities(&mut build_ext(), || {
+    assert_ok!(_create_default_profile()); // AccountId 1
+    assert_noop!(
+      _create_profile(Some(Origin::signed(ACCOUNT2)), Some(b"aLiCe".to_vec()), None),
+      MSG_USERNAME_IS_BUSY
+    );
+  });
+}
+
+#[test]
+fn create_profile_should_fail_reserved_username() {
+  with_externalities(&mut build_ext(), || {
+    assert!(Blogs::reserve_usernames(Origin::signed(ACCOUNT1), vec![b"admin".to_vec()]).is_err());
+    assert_ok!(Blogs::reserve_usernames(system::RawOrigin::Root.into(), vec![b"Admin".to_vec()]));
+    assert_noop!(_create_profile(None, Some(b"ADMIN".to_vec()), None), MSG_USERNAME_IS_RESERVED);
+
+    assert_ok!(Blogs::unreserve_usernames(system::RawOrigin::Root.into(), vec![b"admin".to_vec()]));
+    assert_ok!(_create_profile(None, Some(b"ADMIN".to_vec()), None));
+  });
+}
+
+#[test]
+fn update_profile_should_work_change_username_case() {
+  with_externalities(&mut build_ext(), || {
+    assert_ok!(_create_default_profile()); // AccountId 1
+    assert_ok!(_update_profile(None, Some(b"ALICE".to_vec()), None));
+
+    let profile = Blogs::social_account_by_id(ACCOUNT1).unwrap().profile.unwrap();
+    assert_eq!(profile.username, b"ALICE".to_vec());
+    assert_eq!(Blogs::account_by_profile_username(b"alice".to_vec()), Some(ACCOUNT1));
+    assert_eq!(Blogs::released_username(b"alice".to_vec()), None);
+  });
+}
+
+#[test]
+fn update_profile_should_release_username_after_cooldown() {
+  with_externalities(&mut build_ext(), || {
+    assert_ok!(_create_default_profile()); // AccountId 1
+    assert_ok!(_update_profile(None, Some(self::bob_username()), None));
+
+    // Nobody else can take a released username during the cooldown:
+    assert_noop!(
+      _create_profile(Some(Origin::signed(ACCOUNT2)), Some(self::alice_username()), None),
+      MSG_USERNAME_IS_IN_COOLDOWN
+    );
+
+    System::set_block_number(DEFAULT_USERNAME_RELEASE_COOLDOWN);
+    assert_ok!(_create_profile(Some(Origin::signed(ACCOUNT2)), Some(self::alice_username()), None));
+    assert_eq!(Blogs::account_by_profile_username(b"alice".to_vec()), Some(ACCOUNT2));
+    assert_eq!(Blogs::released_username(b"alice".to_vec()), None);
+  });
+}
+
+#[test]
+fn update_profile_should_work_reclaim_username_during_cooldown() {
+  with_externalities(&mut build_ext(), || {
+    assert_ok!(_create_default_profile()); // AccountId 1
+    assert_ok!(_update_profile(None, Some(self::bob_username()), None));
+    assert_ok!(_update_profile(None, Some(self::alice_username()), None));
+
+    assert_eq!(Blogs::account_by_profile_username(b"alice".to_vec()), Some(ACCOUNT1));
+    assert_eq!(Blogs::released_username(b"bob".to_vec()).map(|(account, _)| account), Some(ACCOUNT1));
   });
 }
 
@@ -1719,12 +2368,12 @@ fn update_profile_should_fail_invalid_username() {
 }
 
 #[test]
-fn update_profile_should_fail_invalid_ipfs_hash() {
-  let ipfs_hash : Vec<u8> = b"QmV9tSDx9UiPeWExXEeH6aoDvmihvx6j".to_vec();
+fn update_profile_should_fail_invalid_content() {
+  let content = Content::IPFS(b"QmV9tSDx9UiPeWExXEeH6aoDvmihvx6j".to_vec());
 
   with_externalities(&mut build_ext(), || {
     assert_ok!(_create_default_profile());
-    assert_noop!(_update_profile(None, None, Some(ipfs_hash)), MSG_IPFS_IS_INCORRECT);
+    assert_noop!(_update_profile(None, None, Some(content)), MSG_IPFS_IS_INCORRECT);
   });
 }
 
@@ -1737,7 +2386,7 @@ fn follow_blog_should_work() {
 
     assert_ok!(_default_follow_blog()); // Follow BlogId 1 by ACCOUNT2
 
-    assert_eq!(Blogs::blog_by_id(1).unwrap().followers_count, 2);
+    assert_eq!(Blogs::blog_counters(1).followers_count, 2);
     assert_eq!(Blogs::blogs_followed_by_account(ACCOUNT2), vec![1]);
     assert_eq!(Blogs::blog_followers(1), vec![ACCOUNT1, ACCOUNT2]);
     assert_eq!(Blogs::blog_followed_by_account((ACCOUNT2, 1)), true);
@@ -1769,7 +2418,7 @@ fn unfollow_blog_should_work() {
     assert_ok!(_default_follow_blog()); // Follow BlogId 1 by ACCOUNT2
     assert_ok!(_default_unfollow_blog());
 
-    assert_eq!(Blogs::blog_by_id(1).unwrap().followers_count, 1);
+    assert_eq!(Blogs::blog_counters(1).followers_count, 1);
     assert!(Blogs::blogs_followed_by_account(ACCOUNT2).is_empty());
     assert_eq!(Blogs::blog_followers(1), vec![ACCOUNT1]);
   });
@@ -1804,6 +2453,24 @@ fn follow_account_should_work() {
   });
 }
 
+#[test]
+fn follow_account_should_change_reputation_of_followed_account() {
+  with_externalities(&mut build_ext(), || {
+    assert_ok!(_default_follow_account()); // Follow ACCOUNT1 by ACCOUNT2
+
+    let score_diff = Blogs::get_account_score_diff(&ACCOUNT2, 1, self::scoring_action_follow_account());
+    let followed_account = Blogs::social_account_by_id(ACCOUNT1).unwrap();
+    assert_eq!(followed_account.followers_count, 1);
+    assert_eq!(followed_account.reputation, 1 + score_diff as u32);
+
+    assert_ok!(_default_unfollow_account());
+    let followed_account = Blogs::social_account_by_id(ACCOUNT1).unwrap();
+    assert_eq!(followed_account.followers_count, 0);
+    assert_eq!(followed_account.reputation, 1);
+    assert_eq!(Blogs::account_reputation_diff_by_account((ACCOUNT2, ACCOUNT1, self::scoring_action_follow_account())), None);
+  });
+}
+
 #[test]
 fn follow_account_should_fail_follow_itself() {
   with_externalities(&mut build_ext(), || {
@@ -1848,4 +2515,1665 @@ fn unfollow_account_should_fail_is_not_followed() {
     
     assert_noop!(_default_unfollow_account(), MSG_ACCOUNT_IS_NOT_FOLLOWED);
   });
-}
\ No newline at end of file
+}
+
+// Fee tests
+
+fn _set_create_blog_fees(base_fee: u64, fee_per_byte: u64) {
+  assert_ok!(Blogs::set_action_base_fee(system::RawOrigin::Root.into(), PaidAction::CreateBlog, base_fee));
+  assert_ok!(Blogs::set_fee_per_byte(system::RawOrigin::Root.into(), fee_per_byte));
+}
+
+fn _set_reputation(account: AccountId, reputation: u32) {
+  let mut social_account = Blogs::get_or_new_social_account(account);
+  social_account.reputation = reputation;
+  <SocialAccountById<Test>>::insert(account, social_account);
+}
+
+#[test]
+fn create_blog_should_charge_fee() {
+  with_externalities(&mut build_ext(), || {
+    let _ = Balances::deposit_creating(&ACCOUNT1, 1_000);
+    self::_set_create_blog_fees(10, 1);
+
+    assert_ok!(_create_default_blog()); // BlogId 1
+
+    let payload_len = (self::blog_slug().len() + self::blog_content().size()) as u64;
+    assert_eq!(Blogs::fee_for(&ACCOUNT1, PaidAction::CreateBlog, payload_len as usize), 10 + payload_len);
+    assert_eq!(Balances::free_balance(ACCOUNT1), 1_000 - 10 - payload_len);
+  });
+}
+
+#[test]
+fn create_blog_should_discount_fee_for_high_reputation() {
+  with_externalities(&mut build_ext(), || {
+    let _ = Balances::deposit_creating(&ACCOUNT1, 1_000);
+    self::_set_create_blog_fees(100, 0);
+    self::_set_reputation(ACCOUNT1, DEFAULT_FEE_DISCOUNT_REPUTATION);
+
+    assert_ok!(_create_default_blog()); // BlogId 1
+    assert_eq!(Balances::free_balance(ACCOUNT1), 1_000 - (100 - DEFAULT_FEE_DISCOUNT_PERCENT as u64));
+  });
+}
+
+#[test]
+fn create_blog_should_not_charge_exempt_account() {
+  with_externalities(&mut build_ext(), || {
+    self::_set_create_blog_fees(100, 1);
+    self::_set_reputation(ACCOUNT1, DEFAULT_FEE_EXEMPT_REPUTATION);
+
+    assert_ok!(_create_default_blog()); // BlogId 1
+    assert_eq!(Balances::free_balance(ACCOUNT1), 0);
+  });
+}
+
+#[test]
+fn create_blog_should_fail_not_enough_balance_for_fee() {
+  with_externalities(&mut build_ext(), || {
+    let _ = Balances::deposit_creating(&ACCOUNT1, 5);
+    self::_set_create_blog_fees(10, 0);
+
+    assert!(_create_default_blog().is_err());
+    assert_eq!(Blogs::blog_by_id(1), None);
+    assert_eq!(Balances::free_balance(ACCOUNT1), 5);
+  });
+}
+
+#[test]
+fn rejected_calls_should_not_charge_fee() {
+  let invalid_content = Content::IPFS(b"QmV9tSDx9UiPeWExXEeH6aoDvmihvx6j".to_vec());
+
+  with_externalities(&mut build_ext(), || {
+    assert_ok!(_create_default_blog()); // BlogId 1
+    assert_ok!(_create_default_post()); // PostId 1
+    let _ = Balances::deposit_creating(&ACCOUNT1, 1_000);
+    for action in vec![PaidAction::CreatePost, PaidAction::UpdatePost, PaidAction::UpdateBlog] {
+      assert_ok!(Blogs::set_action_base_fee(system::RawOrigin::Root.into(), action, 10));
+    }
+
+    assert_noop!(_create_post(None, None, Some(invalid_content.clone()), None), MSG_IPFS_IS_INCORRECT);
+    assert_noop!(_create_post(None, None, Some(Content::None), Some(self::extension_shared_post(2))),
+      MSG_ORIGINAL_POST_NOT_FOUND);
+    assert_noop!(_update_post(None, None, Some(self::post_update(None, Some(invalid_content.clone())))),
+      MSG_IPFS_IS_INCORRECT);
+    assert_noop!(_update_post(None, None, Some(self::post_update(Some(2), None))), MSG_BLOG_NOT_FOUND);
+    assert_noop!(_update_blog(None, None, Some(self::blog_update(None, Some(vec![97; (DEFAULT_SLUG_MIN_LEN - 1) as usize]), None))),
+      MSG_BLOG_SLUG_IS_TOO_SHORT);
+    assert_eq!(Balances::free_balance(ACCOUNT1), 1_000);
+  });
+}
+
+#[test]
+fn rejected_scoring_calls_should_not_charge_fee() {
+  with_externalities(&mut build_ext(), || {
+    assert_ok!(_create_default_blog()); // BlogId 1
+    assert_ok!(_create_default_post()); // PostId 1
+    let _ = Balances::deposit_creating(&ACCOUNT2, 1_000);
+    for action in vec![PaidAction::CreateComment, PaidAction::React, PaidAction::FollowAccount] {
+      assert_ok!(Blogs::set_action_base_fee(system::RawOrigin::Root.into(), action, 10));
+    }
+
+    // Scores whose reputation diffs are lost can not be reverted:
+    <PostScoreByAccount<Test>>::insert((ACCOUNT2, 1, ScoringAction::CreateComment), 1);
+    <PostScoreByAccount<Test>>::insert((ACCOUNT2, 1, ScoringAction::UpvotePost), 1);
+    assert_noop!(
+      Blogs::create_comment(Origin::signed(ACCOUNT2), 1, None, self::comment_content(), vec![]),
+      MSG_REPUTATION_DIFF_NOT_FOUND
+    );
+    assert_noop!(
+      Blogs::create_post_reaction(Origin::signed(ACCOUNT2), 1, self::reaction_downvote()),
+      MSG_REPUTATION_DIFF_NOT_FOUND
+    );
+
+    <SocialAccountById<Test>>::insert(ACCOUNT2, SocialAccount {
+      followers_count: 0,
+      following_accounts_count: u16::max_value(),
+      following_blogs_count: 0,
+      reputation: 1,
+      profile: None,
+    });
+    assert_noop!(Blogs::follow_account(Origin::signed(ACCOUNT2), ACCOUNT1), MSG_OVERFLOW_FOLLOWING_ACCOUNT);
+    assert_eq!(Balances::free_balance(ACCOUNT2), 1_000);
+  });
+}
+
+#[test]
+fn set_fee_reputation_thresholds_should_fail() {
+  with_externalities(&mut build_ext(), || {
+    assert!(Blogs::set_fee_reputation_thresholds(Origin::signed(ACCOUNT1), 10, 50, 100).is_err());
+    assert_noop!(
+      Blogs::set_fee_reputation_thresholds(system::RawOrigin::Root.into(), 10, 101, 100),
+      MSG_FEE_DISCOUNT_PERCENT_IS_TOO_BIG
+    );
+    assert_noop!(
+      Blogs::set_fee_reputation_thresholds(system::RawOrigin::Root.into(), 100, 50, 10),
+      MSG_FEE_EXEMPT_REPUTATION_BELOW_DISCOUNT
+    );
+  });
+}
+
+// Username deposit tests
+
+fn _set_username_deposit_base(deposit: u64) {
+  assert_ok!(Blogs::set_username_deposit_base(system::RawOrigin::Root.into(), deposit));
+}
+
+#[test]
+fn create_profile_should_lock_username_deposit() {
+  with_externalities(&mut build_ext(), || {
+    let _ = Balances::deposit_creating(&ACCOUNT1, 1_000);
+    self::_set_username_deposit_base(100);
+
+    assert_ok!(_create_default_profile()); // AccountId 1
+
+    let deposit = 100 / self::alice_username().len() as u64;
+    assert_eq!(Blogs::username_deposit(&self::alice_username()), deposit);
+    assert_eq!(Blogs::username_expires_at(b"alice".to_vec()), Some(DEFAULT_USERNAME_REGISTRATION_PERIOD));
+
+    let locks = Balances::locks(ACCOUNT1);
+    assert_eq!(locks.len(), 1);
+    assert_eq!(locks[0].id, USERNAME_LOCK_ID);
+    assert_eq!(locks[0].amount, deposit);
+    assert_eq!(locks[0].until, DEFAULT_USERNAME_REGISTRATION_PERIOD);
+  });
+}
+
+#[test]
+fn create_profile_should_fail_not_enough_balance_for_username_deposit() {
+  with_externalities(&mut build_ext(), || {
+    let _ = Balances::deposit_creating(&ACCOUNT1, 10);
+    self::_set_username_deposit_base(100);
+
+    // "Alice" requires a deposit of 20, while a longer username is cheaper:
+    assert_noop!(_create_default_profile(), MSG_NOT_ENOUGH_BALANCE_FOR_USERNAME_DEPOSIT);
+    assert_ok!(_create_profile(None, Some(b"AliceInWonderland".to_vec()), None));
+  });
+}
+
+#[test]
+fn renew_username_should_extend_expiry() {
+  with_externalities(&mut build_ext(), || {
+    let _ = Balances::deposit_creating(&ACCOUNT1, 1_000);
+    self::_set_username_deposit_base(100);
+    assert_ok!(_create_default_profile()); // AccountId 1
+
+    assert_ok!(Blogs::renew_username(Origin::signed(ACCOUNT1)));
+
+    assert_eq!(Blogs::username_expires_at(b"alice".to_vec()), Some(2 * DEFAULT_USERNAME_REGISTRATION_PERIOD));
+    assert_eq!(Balances::locks(ACCOUNT1)[0].until, 2 * DEFAULT_USERNAME_REGISTRATION_PERIOD);
+  });
+}
+
+#[test]
+fn renew_username_should_fail_no_profile() {
+  with_externalities(&mut build_ext(), || {
+    assert_noop!(Blogs::renew_username(Origin::signed(ACCOUNT1)), MSG_SOCIAL_ACCOUNT_NOT_FOUND);
+  });
+}
+
+#[test]
+fn reclaim_expired_username_should_work() {
+  with_externalities(&mut build_ext(), || {
+    let _ = Balances::deposit_creating(&ACCOUNT1, 1_000);
+    self::_set_username_deposit_base(100);
+    assert_ok!(_create_default_profile()); // AccountId 1
+
+    assert_noop!(
+      Blogs::reclaim_expired_username(Origin::signed(ACCOUNT2), self::alice_username()),
+      MSG_USERNAME_HAS_NOT_EXPIRED
+    );
+
+    System::set_block_number(DEFAULT_USERNAME_REGISTRATION_PERIOD);
+    assert_ok!(Blogs::reclaim_expired_username(Origin::signed(ACCOUNT2), b"ALICE".to_vec()));
+    check_invariants();
+
+    // The previous owner keeps their profile, but loses the username and the deposit lock:
+    let profile = Blogs::social_account_by_id(ACCOUNT1).unwrap().profile.unwrap();
+    assert!(profile.username.is_empty());
+    assert_eq!(Blogs::profile_history_page(ACCOUNT1, 0, 1)[0].old_data.username, Some(self::alice_username()));
+    assert!(Balances::locks(ACCOUNT1).is_empty());
+    assert_eq!(Blogs::account_by_profile_username(b"alice".to_vec()), None);
+    assert_eq!(Blogs::username_expires_at(b"alice".to_vec()), None);
+
+    let _ = Balances::deposit_creating(&ACCOUNT2, 1_000);
+    assert_ok!(_create_profile(Some(Origin::signed(ACCOUNT2)), Some(self::alice_username()), None));
+  });
+}
+
+// Attestation tests
+
+const VERIFIER : AccountId = 3;
+
+fn domain_claim() -> AttestationClaim {
+  AttestationClaim::Domain(b"example.com".to_vec())
+}
+
+fn _add_verifier_and_create_profile() {
+  assert_ok!(Blogs::add_verifier(system::RawOrigin::Root.into(), VERIFIER));
+  assert_ok!(_create_default_profile()); // AccountId 1
+}
+
+fn _attest_account(claim: AttestationClaim, expires_at: Option<u64>) -> dispatch::Result {
+  checked(Blogs::attest_account(Origin::signed(VERIFIER), ACCOUNT1, claim, expires_at))
+}
+
+#[test]
+fn attest_account_should_work() {
+  with_externalities(&mut build_ext(), || {
+    self::_add_verifier_and_create_profile();
+    assert_ok!(self::_attest_account(AttestationClaim::Identity, None));
+    assert_ok!(self::_attest_account(self::domain_claim(), Some(100)));
+
+    let attestations = Blogs::attestations_by_account(ACCOUNT1);
+    assert_eq!(attestations.len(), 2);
+    assert_eq!(attestations[1].verifier, VERIFIER);
+    assert_eq!(attestations[1].claim, self::domain_claim());
+    assert_eq!(attestations[1].expires_at, Some(100));
+    assert_eq!(Blogs::active_attestations(ACCOUNT1).len(), 2);
+
+    // Expired attestation is no longer active:
+    System::set_block_number(100);
+    assert_eq!(Blogs::active_attestations(ACCOUNT1).len(), 1);
+
+    // And the claim can be attested again:
+    assert_ok!(self::_attest_account(self::domain_claim(), None));
+    assert_eq!(Blogs::active_attestations(ACCOUNT1).len(), 2);
+  });
+}
+
+#[test]
+fn active_attestation_claims_should_skip_expired_and_revoked() {
+  with_externalities(&mut build_ext(), || {
+    self::_add_verifier_and_create_profile();
+    assert_ok!(self::_attest_account(AttestationClaim::Identity, None));
+    assert_ok!(self::_attest_account(self::domain_claim(), Some(100)));
+    assert_ok!(self::_attest_account(AttestationClaim::Organization(b"org".to_vec()), None));
+    assert_ok!(Blogs::revoke_attestation(Origin::signed(VERIFIER), ACCOUNT1, 2));
+
+    assert_eq!(Blogs::active_attestation_claims(ACCOUNT1), vec![
+      (VERIFIER, AttestationClaim::Identity, None),
+      (VERIFIER, self::domain_claim(), Some(100)),
+    ]);
+
+    System::set_block_number(100);
+    assert_eq!(Blogs::active_attestation_claims(ACCOUNT1), vec![(VERIFIER, AttestationClaim::Identity, None)]);
+  });
+}
+
+#[test]
+fn attest_account_should_fail_not_verifier() {
+  with_externalities(&mut build_ext(), || {
+    assert_ok!(_create_default_profile()); // AccountId 1
+    assert_noop!(self::_attest_account(AttestationClaim::Identity, None), MSG_ONLY_VERIFIER_CAN_ATTEST);
+
+    assert_ok!(Blogs::add_verifier(system::RawOrigin::Root.into(), VERIFIER));
+    assert_ok!(Blogs::remove_verifier(system::RawOrigin::Root.into(), VERIFIER));
+    assert_noop!(self::_attest_account(AttestationClaim::Identity, None), MSG_ONLY_VERIFIER_CAN_ATTEST);
+  });
+}
+
+#[test]
+fn attest_account_should_fail_no_profile() {
+  with_externalities(&mut build_ext(), || {
+    assert_ok!(Blogs::add_verifier(system::RawOrigin::Root.into(), VERIFIER));
+    assert_noop!(self::_attest_account(AttestationClaim::Identity, None), MSG_SOCIAL_ACCOUNT_NOT_FOUND);
+  });
+}
+
+#[test]
+fn attest_account_should_fail_already_attested() {
+  with_externalities(&mut build_ext(), || {
+    self::_add_verifier_and_create_profile();
+    assert_ok!(self::_attest_account(self::domain_claim(), None));
+
+    assert_noop!(self::_attest_account(self::domain_claim(), None), MSG_ATTESTATION_ALREADY_EXISTS);
+    assert_noop!(self::_attest_account(AttestationClaim::Identity, Some(0)), MSG_ATTESTATION_EXPIRES_IN_PAST);
+  });
+}
+
+#[test]
+fn revoke_attestation_should_work() {
+  with_externalities(&mut build_ext(), || {
+    self::_add_verifier_and_create_profile();
+    assert_ok!(self::_attest_account(AttestationClaim::Identity, None));
+    assert_ok!(self::_attest_account(self::domain_claim(), None));
+
+    assert_ok!(Blogs::revoke_attestation(Origin::signed(VERIFIER), ACCOUNT1, 0));
+    assert_ok!(Blogs::force_revoke_attestation(system::RawOrigin::Root.into(), ACCOUNT1, 1));
+
+    let attestations = Blogs::attestations_by_account(ACCOUNT1);
+    assert_eq!(attestations[0].revoked.as_ref().map(|change| change.account), Some(VERIFIER));
+    assert!(attestations[1].revoked.is_some());
+    assert!(Blogs::active_attestations(ACCOUNT1).is_empty());
+  });
+}
+
+#[test]
+fn revoke_attestation_should_fail() {
+  with_externalities(&mut build_ext(), || {
+    self::_add_verifier_and_create_profile();
+    assert_ok!(self::_attest_account(AttestationClaim::Identity, None));
+
+    assert_noop!(Blogs::revoke_attestation(Origin::signed(ACCOUNT2), ACCOUNT1, 0), MSG_ONLY_VERIFIER_CAN_REVOKE_ATTESTATION);
+    assert_noop!(Blogs::revoke_attestation(Origin::signed(VERIFIER), ACCOUNT1, 1), MSG_ATTESTATION_NOT_FOUND);
+
+    assert_ok!(Blogs::revoke_attestation(Origin::signed(VERIFIER), ACCOUNT1, 0));
+    assert_noop!(Blogs::revoke_attestation(Origin::signed(VERIFIER), ACCOUNT1, 0), MSG_ATTESTATION_IS_ALREADY_REVOKED);
+  });
+}
+
+#[test]
+fn edit_history_should_keep_only_max_len_revisions() {
+  with_externalities(&mut build_ext(), || {
+    assert_ok!(Blogs::set_max_edit_history_len(system::RawOrigin::Root.into(), 2));
+    assert_ok!(_create_default_blog()); // BlogId 1
+    assert_ok!(_create_default_post()); // PostId 1
+
+    for i in 0..3 {
+      let content = Content::Raw(vec![b'a' + i]);
+      assert_ok!(_update_post(None, None, Some(self::post_update(None, Some(content)))));
+    }
+
+    // The oldest revision is pruned, but indexes of the rest stay the same:
+    assert_eq!(Blogs::post_revisions_count(1), 3);
+    assert_eq!(Blogs::post_history_record((1, 0)), None);
+
+    let history = Blogs::post_history_page(1, 0, 10);
+    assert_eq!(history.len(), 2);
+    assert_eq!(history[0].old_data.content, Some(Content::Raw(b"a".to_vec())));
+    assert_eq!(history[1].old_data.content, Some(Content::Raw(b"b".to_vec())));
+
+    let page = Blogs::post_history_page(1, 2, 1);
+    assert_eq!(page.len(), 1);
+    assert_eq!(page[0].old_data.content, Some(Content::Raw(b"b".to_vec())));
+    assert!(Blogs::post_history_page(1, 3, 10).is_empty());
+  });
+}
+
+#[test]
+fn lowering_max_edit_history_len_should_prune_all_older_revisions() {
+  with_externalities(&mut build_ext(), || {
+    assert_ok!(_create_default_blog()); // BlogId 1
+    assert_ok!(_create_default_post()); // PostId 1
+
+    for i in 0..4 {
+      let content = Content::Raw(vec![b'a' + i]);
+      assert_ok!(_update_post(None, None, Some(self::post_update(None, Some(content)))));
+    }
+    assert_ok!(Blogs::set_max_edit_history_len(system::RawOrigin::Root.into(), 2));
+    assert_ok!(_update_post(None, None, Some(self::post_update(None, Some(Content::Raw(b"e".to_vec()))))));
+
+    // All revisions below the new window are pruned on the next edit:
+    assert_eq!(Blogs::post_revisions_count(1), 5);
+    for index in 0..3 {
+      assert_eq!(Blogs::post_history_record((1, index)), None);
+    }
+
+    let history = Blogs::post_history_page(1, 0, 10);
+    assert_eq!(history.len(), 2);
+    assert_eq!(history[0].old_data.content, Some(Content::Raw(b"c".to_vec())));
+    assert_eq!(history[1].old_data.content, Some(Content::Raw(b"d".to_vec())));
+  });
+}
+
+fn ipfs_hash(content: Content) -> Vec<u8> {
+  match content {
+    Content::IPFS(hash) => hash,
+    _ => vec![],
+  }
+}
+
+fn run_legacy_migration() {
+  while Blogs::pending_legacy_migration().is_some() {
+    Blogs::migrate_legacy_chunk();
+  }
+}
+
+#[test]
+fn migrate_from_legacy_layout_should_work() {
+  use super::legacy::{self, LegacyBlog, LegacyPost, LegacyComment, LegacySocialAccount};
+
+  with_externalities(&mut build_ext(), || {
+    let change = Change::<Test> { account: ACCOUNT1, block: 0, time: 0 };
+    let no_writers: Option<Vec<AccountId>> = None;
+    let no_hash: Option<Vec<u8>> = None;
+
+    // Values are encoded field by field, the way spec version 2 stored them:
+    let blog = [
+      1u32.encode(), change.encode(), Some(change.clone()).encode(), Vec::<AccountId>::new().encode(),
+      self::blog_slug().encode(), self::ipfs_hash(self::blog_content()).encode(), 1u16.encode(), 2u32.encode(),
+      vec![(change.clone(), (no_writers, Some(b"old_slug".to_vec()), no_hash.clone()))].encode(),
+      5i32.encode(),
+    ].concat();
+    let post = [
+      1u32.encode(), 1u32.encode(), change.encode(), Some(change.clone()).encode(),
+      PostExtension::<Test>::RegularPost.encode(), self::ipfs_hash(self::post_content()).encode(),
+      1u16.encode(), 2u16.encode(), 0u16.encode(), 1u16.encode(),
+      vec![(change.clone(), (Some(1u32), Some(self::ipfs_hash(self::blog_content()))))].encode(),
+      7i32.encode(),
+    ].concat();
+    let shared_post = [
+      2u32.encode(), 1u32.encode(), change.encode(), None::<Change<Test>>.encode(),
+      PostExtension::<Test>::SharedPost(1).encode(), Vec::<u8>::new().encode(),
+      0u16.encode(), 0u16.encode(), 0u16.encode(), 0u16.encode(),
+      Vec::<(Change<Test>, (Option<BlogId>, Option<Vec<u8>>))>::new().encode(),
+      0i32.encode(),
+    ].concat();
+    let comment = [
+      1u32.encode(), None::<CommentId>.encode(), 1u32.encode(), change.encode(), Some(change.clone()).encode(),
+      self::ipfs_hash(self::comment_content()).encode(),
+      1u16.encode(), 0u16.encode(), 0u16.encode(), 0u16.encode(),
+      vec![(change.clone(), self::ipfs_hash(self::post_content()))].encode(),
+      3i32.encode(),
+    ].concat();
+    let profile = |username: Vec<u8>| [
+      0u32.encode(), 0u16.encode(), 1u16.encode(), 1u32.encode(), 1u8.encode(),
+      change.encode(), Some(change.clone()).encode(), username.encode(), self::ipfs_hash(self::profile_content()).encode(),
+      vec![(change.clone(), (Some(self::alice_username()), no_hash.clone()))].encode(),
+    ].concat();
+
+    <legacy::BlogById<Test>>::insert(1, LegacyBlog::decode(&mut &blog[..]).unwrap());
+    <legacy::PostById<Test>>::insert(1, LegacyPost::decode(&mut &post[..]).unwrap());
+    <legacy::PostById<Test>>::insert(2, LegacyPost::decode(&mut &shared_post[..]).unwrap());
+    <legacy::CommentById<Test>>::insert(1, LegacyComment::decode(&mut &comment[..]).unwrap());
+    <legacy::SocialAccountById<Test>>::insert(ACCOUNT1, LegacySocialAccount::decode(&mut &profile(self::bob_username())[..]).unwrap());
+    // The second account is found only as a follower of the first one:
+    <legacy::SocialAccountById<Test>>::insert(ACCOUNT2, LegacySocialAccount::decode(&mut &profile(b"Carol".to_vec())[..]).unwrap());
+    <AccountFollowers<Test>>::insert(ACCOUNT1, vec![ACCOUNT2]);
+    <AccountByProfileUsername<Test>>::insert(self::bob_username(), ACCOUNT1);
+    <AccountByProfileUsername<Test>>::insert(b"Carol".to_vec(), ACCOUNT2);
+    // The second account upvoted the post, which was stored the same way as it is now,
+    // apart from the reputation diff kept per scorer and author:
+    <ReactionById<Test>>::insert(1, Reaction {
+      id: 1,
+      created: Change { account: ACCOUNT2, block: 0, time: 0 },
+      updated: None,
+      kind: self::reaction_upvote(),
+    });
+    <ReactionIdsByPostId<Test>>::insert(1, vec![1]);
+    <PostReactionIdByAccount<Test>>::insert((ACCOUNT2, 1), 1);
+    <PostScoreByAccount<Test>>::insert((ACCOUNT2, 1, ScoringAction::UpvotePost), 2);
+    <AccountReputationDiffByAccount<Test>>::insert((ACCOUNT2, ACCOUNT1, ScoringAction::UpvotePost), 1);
+    <NextBlogId<Test>>::put(2);
+    <NextPostId<Test>>::put(3);
+    <NextCommentId<Test>>::put(2);
+    <NextReactionId<Test>>::put(2);
+
+    // The legacy values were stored before the upgrade block:
+    System::set_block_number(1);
+    assert_ok!(Blogs::set_legacy_migration_chunk_len(system::RawOrigin::Root.into(), 3));
+    Blogs::start_legacy_migration();
+    Blogs::migrate_legacy_chunk();
+
+    // The first chunk migrates the blog and goes on with the slug keys:
+    let migration = Blogs::pending_legacy_migration().unwrap();
+    assert_eq!(migration.step, LegacyMigrationStep::LegacySlugKeys(2));
+    assert_eq!((migration.next_blog_id, migration.next_post_id, migration.next_comment_id), (2, 3, 2));
+    assert_eq!(Blogs::blog_counters(1).score, 5);
+    assert_eq!(Blogs::post_counters(1).score, 0);
+
+    self::run_legacy_migration();
+    assert_eq!(Blogs::legacy_accounts_count(), 0);
+    assert_eq!(Blogs::legacy_account(0), None);
+
+    assert_eq!(Blogs::blog_by_id(1), Some(Blog {
+      id: 1,
+      created: change.clone(),
+      updated: Some(change.clone()),
+      writers: vec![],
+      slug: self::blog_slug(),
+      content: self::blog_content(),
+    }));
+    assert_eq!(Blogs::blog_counters(1), BlogCounters { posts_count: 1, followers_count: 2, score: 5 });
+    assert_eq!(Blogs::blog_revisions_count(1), 1);
+    assert_eq!(Blogs::blog_history_record((1, 0)).unwrap().old_data.slug, Some(b"old_slug".to_vec()));
+
+    assert_eq!(Blogs::post_by_id(1), Some(Post {
+      id: 1,
+      blog_id: 1,
+      created: change.clone(),
+      updated: Some(change.clone()),
+      extension: PostExtension::RegularPost,
+      content: self::post_content(),
+      tags: vec![],
+      visibility: PostVisibility::Public,
+    }));
+    assert_eq!(Blogs::post_counters(1), PostCounters { comments_count: 1, upvotes_count: 2, downvotes_count: 0, shares_count: 1, score: 7 });
+    assert_eq!(Blogs::post_history_record((1, 0)).unwrap().old_data, PostUpdate {
+      blog_id: Some(1),
+      content: Some(self::blog_content()),
+      tags: None,
+    });
+    let shared_post = Blogs::post_by_id(2).unwrap();
+    assert_eq!(shared_post.extension, PostExtension::SharedPost(1));
+    assert_eq!(shared_post.content, Content::None);
+    assert_eq!(Blogs::post_revisions_count(2), 0);
+
+    assert_eq!(Blogs::comment_by_id(1).unwrap().content, self::comment_content());
+    assert_eq!(Blogs::comment_counters(1).score, 3);
+    assert_eq!(Blogs::comment_history_record((1, 0)).unwrap().old_data.content, self::post_content());
+
+    let profile = Blogs::social_account_by_id(ACCOUNT1).unwrap().profile.unwrap();
+    assert_eq!(profile.username, self::bob_username());
+    assert_eq!(profile.content, self::profile_content());
+    assert_eq!(Blogs::profile_revisions_count(ACCOUNT1), 1);
+    assert_eq!(Blogs::profile_history_record((ACCOUNT1, 0)).unwrap().old_data.username, Some(self::alice_username()));
+    assert_eq!(Blogs::profile_revisions_count(ACCOUNT2), 1);
+
+    // Usernames are indexed in lowercase:
+    assert_eq!(Blogs::account_by_profile_username(self::bob_username()), None);
+    assert_eq!(Blogs::account_by_profile_username(b"bob".to_vec()), Some(ACCOUNT1));
+    assert_eq!(Blogs::account_by_profile_username(b"carol".to_vec()), Some(ACCOUNT2));
+
+    // The reputation diff of the upvote is kept per post now:
+    assert_eq!(Blogs::account_reputation_diff_by_account((ACCOUNT2, ACCOUNT1, ScoringAction::UpvotePost)), None);
+    assert_eq!(Blogs::post_reputation_diff_by_account((ACCOUNT2, 1, ScoringAction::UpvotePost, ACCOUNT1)), Some(1));
+    assert_eq!(Blogs::post_score_in_blog((1, 1)), 7);
+
+    // So the upvote can be deleted after the upgrade:
+    assert_ok!(Blogs::delete_post_reaction(Origin::signed(ACCOUNT2), 1, 1));
+    assert_eq!(Blogs::post_counters(1).upvotes_count, 1);
+    assert_eq!(Blogs::post_counters(1).score, 5);
+    assert_eq!(Blogs::blog_counters(1).score, 3);
+    assert_eq!(Blogs::post_score_in_blog((1, 1)), 5);
+    assert_eq!(Blogs::post_score_by_account((ACCOUNT2, 1, ScoringAction::UpvotePost)), None);
+    assert_eq!(Blogs::post_reputation_diff_by_account((ACCOUNT2, 1, ScoringAction::UpvotePost, ACCOUNT1)), None);
+  });
+}
+
+#[test]
+fn migrate_slug_keys_should_keep_slug_of_earliest_blog() {
+  with_externalities(&mut build_ext(), || {
+    // Slugs that differ only in case were indexed as different ones:
+    for (blog_id, slug) in vec![(1, b"My_Blog".to_vec()), (2, b"my_blog".to_vec()), (3, b"Other".to_vec())] {
+      assert_ok!(_create_blog(None, Some(format!("blog{}_slug", blog_id).into_bytes()), None));
+      let mut blog = Blogs::blog_by_id(blog_id).unwrap();
+      <BlogIdBySlug<Test>>::remove(blog.slug);
+      <BlogIdBySlug<Test>>::insert(slug.clone(), blog_id);
+      blog.slug = slug;
+      <BlogById<Test>>::insert(blog_id, blog);
+    }
+
+    // The blogs are already stored in the current layout, so only their slug keys are migrated:
+    <PendingLegacyMigration<Test>>::put(LegacyMigration {
+      step: LegacyMigrationStep::LegacySlugKeys(1),
+      next_blog_id: 4,
+      next_post_id: 1,
+      next_comment_id: 1,
+      next_reaction_id: 1,
+    });
+    self::run_legacy_migration();
+
+    assert_eq!(Blogs::blog_id_by_slug(b"My_Blog".to_vec()), None);
+    assert_eq!(Blogs::blog_id_by_slug(b"my_blog".to_vec()), Some(1));
+    assert_eq!(Blogs::blog_by_id(1).unwrap().slug, b"my_blog".to_vec());
+    assert_eq!(Blogs::blog_id_by_slug(b"other".to_vec()), Some(3));
+    assert_eq!(Blogs::blog_revisions_count(3), 0);
+
+    // The later blog gets its id appended:
+    assert_eq!(Blogs::blog_id_by_slug(b"my_blog-2".to_vec()), Some(2));
+    assert_eq!(Blogs::blog_by_id(2).unwrap().slug, b"my_blog-2".to_vec());
+    assert_eq!(Blogs::blog_history_record((2, 0)).unwrap().old_data.slug, Some(b"my_blog".to_vec()));
+  });
+}
+
+#[test]
+fn migrate_username_keys_should_keep_username_of_earliest_profile() {
+  with_externalities(&mut build_ext(), || {
+    let profile = |block: u64, username: Vec<u8>| SocialAccount::<Test> {
+      followers_count: 0,
+      following_accounts_count: 0,
+      following_blogs_count: 0,
+      reputation: 1,
+      profile: Some(Profile {
+        created: Change { account: ACCOUNT1, block, time: 0 },
+        updated: None,
+        username,
+        content: self::profile_content(),
+      }),
+    };
+    // Usernames that differ only in case were indexed as different ones:
+    for (account, block, username) in vec![
+      (ACCOUNT1, 2, self::alice_username()),
+      (ACCOUNT2, 1, b"alice".to_vec()),
+      (VERIFIER, 1, self::bob_username()),
+    ] {
+      <SocialAccountById<Test>>::insert(account, profile(block, username.clone()));
+      <AccountByProfileUsername<Test>>::insert(username, account);
+    }
+
+    Blogs::migrate_username_keys(&[ACCOUNT1, ACCOUNT2, VERIFIER]);
+
+    assert_eq!(Blogs::account_by_profile_username(b"alice".to_vec()), Some(ACCOUNT2));
+    assert_eq!(Blogs::account_by_profile_username(self::alice_username()), None);
+    assert_eq!(Blogs::account_by_profile_username(b"bob".to_vec()), Some(VERIFIER));
+    assert_eq!(Blogs::account_by_profile_username(self::bob_username()), None);
+
+    // The later profile loses its username:
+    assert!(Blogs::social_account_by_id(ACCOUNT1).unwrap().profile.unwrap().username.is_empty());
+    assert_eq!(Blogs::profile_history_page(ACCOUNT1, 0, 1)[0].old_data.username, Some(self::alice_username()));
+    assert_eq!(Blogs::social_account_by_id(ACCOUNT2).unwrap().profile.unwrap().username, b"alice".to_vec());
+    assert_eq!(Blogs::profile_revisions_count(ACCOUNT2), 0);
+  });
+}
+
+#[test]
+fn migrate_profile_histories_should_work() {
+  use super::legacy::{self, LegacySocialAccount, LegacyProfile, LegacyProfileHistoryRecord, LegacyProfileUpdate};
+
+  with_externalities(&mut build_ext(), || {
+    let change = Change::<Test> { account: ACCOUNT1, block: 0, time: 0 };
+    <legacy::SocialAccountById<Test>>::insert(ACCOUNT1, LegacySocialAccount {
+      followers_count: 0,
+      following_accounts_count: 0,
+      following_blogs_count: 0,
+      reputation: 1,
+      profile: Some(LegacyProfile {
+        created: change.clone(),
+        updated: Some(change.clone()),
+        username: self::bob_username(),
+        ipfs_hash: self::ipfs_hash(self::profile_content()),
+        edit_history: vec![LegacyProfileHistoryRecord {
+          edited: change,
+          old_data: LegacyProfileUpdate { username: Some(self::alice_username()), ipfs_hash: None },
+        }],
+      }),
+    });
+
+    assert!(Blogs::migrate_profile_histories(Origin::signed(ACCOUNT1), vec![ACCOUNT1]).is_err());
+    assert_noop!(
+      Blogs::migrate_profile_histories(system::RawOrigin::Root.into(), vec![ACCOUNT1]),
+      MSG_LEGACY_LAYOUT_WAS_NOT_UPGRADED
+    );
+
+    <LegacyLayoutUpgradedAt<Test>>::put(1);
+    assert_ok!(Blogs::migrate_profile_histories(system::RawOrigin::Root.into(), vec![ACCOUNT1, ACCOUNT2]));
+
+    let profile = Blogs::social_account_by_id(ACCOUNT1).unwrap().profile.unwrap();
+    assert_eq!(profile.username, self::bob_username());
+    assert_eq!(profile.content, self::profile_content());
+    assert_eq!(Blogs::profile_revisions_count(ACCOUNT1), 1);
+    assert_eq!(Blogs::profile_history_page(ACCOUNT1, 0, 1)[0].old_data.username, Some(self::alice_username()));
+    assert!(Blogs::is_profile_migrated(ACCOUNT1));
+    assert!(Blogs::social_account_by_id(ACCOUNT2).is_none());
+
+    // Neither the migrated profile nor one created after the upgrade are read as legacy ones:
+    System::set_block_number(2);
+    assert_ok!(_create_profile(Some(Origin::signed(ACCOUNT2)), Some(b"carol".to_vec()), None));
+    let fresh_account = Blogs::social_account_by_id(ACCOUNT2);
+    assert_ok!(Blogs::migrate_profile_histories(system::RawOrigin::Root.into(), vec![ACCOUNT1, ACCOUNT2]));
+
+    assert_eq!(Blogs::social_account_by_id(ACCOUNT1).unwrap().profile, Some(profile));
+    assert_eq!(Blogs::profile_revisions_count(ACCOUNT1), 1);
+    assert_eq!(Blogs::social_account_by_id(ACCOUNT2), fresh_account);
+    assert_eq!(Blogs::profile_revisions_count(ACCOUNT2), 0);
+    assert!(!Blogs::is_profile_migrated(ACCOUNT2));
+    assert_eq!(Blogs::account_by_profile_username(b"carol".to_vec()), Some(ACCOUNT2));
+  });
+}
+
+#[test]
+fn revert_blog_should_work() {
+  with_externalities(&mut build_ext(), || {
+    assert_ok!(_create_default_blog()); // BlogId 1
+    assert_ok!(_update_blog(None, None, Some(self::blog_update(None, Some(b"new_slug".to_vec()), None))));
+    assert_ok!(_update_blog(None, None, Some(self::blog_update(None, None, Some(self::post_content())))));
+
+    assert_ok!(checked(Blogs::revert_blog(Origin::signed(ACCOUNT1), 1, 0)));
+
+    let blog = Blogs::blog_by_id(1).unwrap();
+    assert_eq!(blog.slug, self::blog_slug());
+    assert_eq!(blog.content, self::blog_content());
+    assert_eq!(Blogs::blog_id_by_slug(self::blog_slug()), Some(1));
+
+    // The revert is a new revision that keeps the reverted state:
+    assert_eq!(Blogs::blog_revisions_count(1), 3);
+    let revert = Blogs::blog_history_record((1, 2)).unwrap();
+    assert_eq!(revert.old_data.slug, Some(b"new_slug".to_vec()));
+    assert_eq!(revert.old_data.content, Some(self::post_content()));
+  });
+}
+
+#[test]
+fn revert_post_should_work() {
+  with_externalities(&mut build_ext(), || {
+    assert_ok!(_create_default_blog()); // BlogId 1
+    assert_ok!(_create_default_post()); // PostId 1
+    assert_ok!(_update_post(None, None, Some(self::post_update(None, Some(self::blog_content())))));
+    assert_ok!(_update_post(None, None, Some(self::post_update(None, Some(self::subcomment_content())))));
+
+    // Revert only the last edit:
+    assert_ok!(checked(Blogs::revert_post(Origin::signed(ACCOUNT1), 1, 1)));
+    assert_eq!(Blogs::post_by_id(1).unwrap().content, self::blog_content());
+
+    assert_ok!(checked(Blogs::revert_post(Origin::signed(ACCOUNT1), 1, 0)));
+    assert_eq!(Blogs::post_by_id(1).unwrap().content, self::post_content());
+    assert_eq!(Blogs::post_revisions_count(1), 4);
+  });
+}
+
+#[test]
+fn revert_comment_should_work() {
+  with_externalities(&mut build_ext(), || {
+    assert_ok!(_create_default_blog()); // BlogId 1
+    assert_ok!(_create_default_post()); // PostId 1
+    assert_ok!(_create_default_comment()); // CommentId 1
+    assert_ok!(_update_comment(None, None, None));
+
+    assert_ok!(checked(Blogs::revert_comment(Origin::signed(ACCOUNT1), 1, 0)));
+    assert_eq!(Blogs::comment_by_id(1).unwrap().content, self::comment_content());
+    assert_eq!(Blogs::comment_history_record((1, 1)).unwrap().old_data.content, self::subcomment_content());
+  });
+}
+
+#[test]
+fn revert_post_should_fail() {
+  with_externalities(&mut build_ext(), || {
+    assert_ok!(Blogs::set_max_edit_history_len(system::RawOrigin::Root.into(), 1));
+    assert_ok!(_create_default_blog()); // BlogId 1
+    assert_ok!(_create_default_post()); // PostId 1
+    assert_noop!(Blogs::revert_post(Origin::signed(ACCOUNT1), 1, 0), MSG_REVISION_NOT_FOUND);
+
+    assert_ok!(_update_post(None, None, Some(self::post_update(None, Some(self::blog_content())))));
+    assert_noop!(Blogs::revert_post(Origin::signed(ACCOUNT2), 1, 0), MSG_ONLY_POST_OWNER_CAN_UPDATE_POST);
+
+    assert_ok!(_update_post(None, None, Some(self::post_update(None, Some(self::subcomment_content())))));
+    assert_noop!(Blogs::revert_post(Origin::signed(ACCOUNT1), 1, 0), MSG_REVISION_IS_PRUNED);
+  });
+}
+
+// Notification tests
+
+fn notification_kinds(account: AccountId) -> Vec<NotificationKind<Test>> {
+  Blogs::notifications_page(account, 0, DEFAULT_MAX_NOTIFICATIONS_PER_ACCOUNT)
+    .into_iter()
+    .map(|notification| notification.kind)
+    .collect()
+}
+
+#[test]
+fn notifications_should_be_added_for_other_accounts_only() {
+  with_externalities(&mut build_ext(), || {
+    assert_ok!(_default_follow_account()); // Follow ACCOUNT1 by ACCOUNT2
+    assert_ok!(_create_default_blog()); // BlogId 1
+    assert_ok!(_create_default_post()); // PostId 1
+    assert_ok!(_create_default_comment()); // CommentId 1 by the post author
+    assert_ok!(_create_comment(Some(Origin::signed(ACCOUNT2)), None, Some(1), None)); // CommentId 2
+    assert_ok!(_create_post_reaction(Some(Origin::signed(ACCOUNT2)), None, None)); // ReactionId 1
+    assert_ok!(_create_post(Some(Origin::signed(ACCOUNT2)), None, Some(Content::None), Some(self::extension_shared_post(1)))); // PostId 2
+
+    assert_eq!(notification_kinds(ACCOUNT1), vec![
+      NotificationKind::AccountFollowed,
+      NotificationKind::CommentReplied(1, 2),
+      NotificationKind::PostReacted(1, 1),
+      NotificationKind::PostShared(1, 2),
+    ]);
+    assert_eq!(Blogs::notification_by_account((ACCOUNT1, 0)).unwrap().created.account, ACCOUNT2);
+    assert_eq!(Blogs::unread_notifications_count(ACCOUNT1), 4);
+    assert_eq!(Blogs::notifications_count(ACCOUNT2), 0);
+  });
+}
+
+#[test]
+fn mark_notifications_read_should_work() {
+  with_externalities(&mut build_ext(), || {
+    assert_ok!(Blogs::set_max_notifications_per_account(system::RawOrigin::Root.into(), 2));
+    assert_ok!(_create_default_blog()); // BlogId 1
+    assert_ok!(_create_default_post()); // PostId 1
+    for _ in 0..3 {
+      assert_ok!(_create_comment(Some(Origin::signed(ACCOUNT2)), None, None, None));
+    }
+
+    // The oldest notification is pruned and is not counted as unread:
+    assert_eq!(Blogs::notifications_count(ACCOUNT1), 3);
+    assert!(Blogs::notification_by_account((ACCOUNT1, 0)).is_none());
+    assert_eq!(Blogs::unread_notifications_count(ACCOUNT1), 2);
+
+    assert_ok!(Blogs::mark_notifications_read(Origin::signed(ACCOUNT1), 2));
+    assert_eq!(Blogs::notifications_read_up_to(ACCOUNT1), 2);
+    assert_eq!(Blogs::unread_notifications_count(ACCOUNT1), 1);
+
+    assert_noop!(Blogs::mark_notifications_read(Origin::signed(ACCOUNT1), 2), MSG_NOTIFICATIONS_ARE_ALREADY_READ);
+    assert_noop!(Blogs::mark_notifications_read(Origin::signed(ACCOUNT1), 4), MSG_NOTIFICATION_NOT_FOUND);
+  });
+}
+
+#[test]
+fn lowering_max_notifications_should_prune_all_older_notifications() {
+  with_externalities(&mut build_ext(), || {
+    assert_ok!(_create_default_blog()); // BlogId 1
+    assert_ok!(_create_default_post()); // PostId 1
+    for _ in 0..4 {
+      assert_ok!(_create_comment(Some(Origin::signed(ACCOUNT2)), None, None, None));
+    }
+    assert_ok!(Blogs::set_max_notifications_per_account(system::RawOrigin::Root.into(), 2));
+    assert_ok!(_create_comment(Some(Origin::signed(ACCOUNT2)), None, None, None));
+
+    // All notifications below the new limit are pruned on the next one:
+    assert_eq!(Blogs::notifications_count(ACCOUNT1), 5);
+    for index in 0..3 {
+      assert!(Blogs::notification_by_account((ACCOUNT1, index)).is_none());
+    }
+    assert_eq!(Blogs::notifications_page(ACCOUNT1, 0, 10).len(), 2);
+    assert_eq!(Blogs::unread_notifications_count(ACCOUNT1), 2);
+  });
+}
+
+// Blocking and messaging tests
+
+fn message_content() -> Content {
+  Content::IPFS(b"QmYA2fn8cMbVWo4v95RwcwJVyQsNtnEwHerfWR8UNtEwoE".to_vec())
+}
+
+fn _send_message(origin: Option<Origin>, to: Option<AccountId>) -> dispatch::Result {
+  checked(Blogs::send_message(
+    origin.unwrap_or(Origin::signed(ACCOUNT1)),
+    to.unwrap_or(ACCOUNT2),
+    self::message_content()
+  ))
+}
+
+#[test]
+fn send_message_should_work() {
+  with_externalities(&mut build_ext(), || {
+    assert_ok!(_send_message(None, None)); // MessageId 1
+    assert_ok!(_send_message(Some(Origin::signed(ACCOUNT2)), Some(ACCOUNT1))); // MessageId 2
+
+    // Both participants see the same conversation:
+    assert_eq!(Blogs::conversation_page(ACCOUNT1, ACCOUNT2, 0, 10), vec![1, 2]);
+    assert_eq!(Blogs::conversation_page(ACCOUNT2, ACCOUNT1, 1, 10), vec![2]);
+    assert_eq!(Blogs::unread_messages_count((ACCOUNT2, ACCOUNT1)), 1);
+
+    let message = Blogs::message_by_id(1).unwrap();
+    assert_eq!(message.created.account, ACCOUNT1);
+    assert_eq!(message.recipient, ACCOUNT2);
+    assert_eq!(message.content, self::message_content());
+
+    assert_ok!(checked(Blogs::mark_conversation_read(Origin::signed(ACCOUNT2), ACCOUNT1)));
+    assert_eq!(Blogs::unread_messages_count((ACCOUNT2, ACCOUNT1)), 0);
+    assert_noop!(Blogs::mark_conversation_read(Origin::signed(ACCOUNT2), ACCOUNT1), MSG_NO_UNREAD_MESSAGES_IN_CONVERSATION);
+  });
+}
+
+#[test]
+fn send_message_should_fail() {
+  with_externalities(&mut build_ext(), || {
+    assert_noop!(_send_message(None, Some(ACCOUNT1)), MSG_ACCOUNT_CANNOT_MESSAGE_ITSELF);
+    assert_noop!(
+      Blogs::send_message(Origin::signed(ACCOUNT1), ACCOUNT2, Content::Raw(b"plain text".to_vec())),
+      MSG_RAW_CONTENT_IS_NOT_ALLOWED
+    );
+
+    assert_ok!(checked(Blogs::block_account(Origin::signed(ACCOUNT2), ACCOUNT1)));
+    assert_noop!(_send_message(None, None), MSG_SENDER_IS_BLOCKED_BY_RECIPIENT);
+
+    // The blocker still can write to the blocked account:
+    assert_ok!(_send_message(Some(Origin::signed(ACCOUNT2)), Some(ACCOUNT1)));
+  });
+}
+
+#[test]
+fn delete_message_should_work() {
+  with_externalities(&mut build_ext(), || {
+    assert_ok!(_send_message(None, None)); // MessageId 1
+    assert_ok!(_send_message(None, None)); // MessageId 2
+    assert_noop!(Blogs::delete_message(Origin::signed(ACCOUNT2), 1), MSG_ONLY_MESSAGE_SENDER_CAN_DELETE_MESSAGE);
+
+    assert_ok!(checked(Blogs::delete_message(Origin::signed(ACCOUNT1), 1)));
+    assert!(Blogs::message_by_id(1).is_none());
+    assert_eq!(Blogs::conversation_page(ACCOUNT1, ACCOUNT2, 0, 10), vec![2]);
+    assert_eq!(Blogs::unread_messages_count((ACCOUNT2, ACCOUNT1)), 1);
+    assert_noop!(Blogs::delete_message(Origin::signed(ACCOUNT1), 1), MSG_MESSAGE_NOT_FOUND);
+  });
+}
+
+#[test]
+fn blocked_accounts_should_not_mention_or_notify() {
+  with_externalities(&mut build_ext(), || {
+    assert_ok!(checked(Blogs::block_account(Origin::signed(ACCOUNT2), ACCOUNT1)));
+    assert_noop!(Blogs::block_account(Origin::signed(ACCOUNT2), ACCOUNT1), MSG_ACCOUNT_IS_ALREADY_BLOCKED);
+    assert_eq!(Blogs::accounts_blocked_by_account(ACCOUNT2), vec![ACCOUNT1]);
+
+    assert_ok!(_create_default_blog()); // BlogId 1
+    assert_ok!(_create_post_with_mentions(vec![Mention::Account(ACCOUNT2)])); // PostId 1
+    assert_ok!(_follow_account(Some(Origin::signed(ACCOUNT1)), Some(ACCOUNT2)));
+    assert!(Blogs::mentions_page(ACCOUNT2, 0, 10).is_empty());
+    assert_eq!(Blogs::notifications_count(ACCOUNT2), 0);
+
+    assert_ok!(checked(Blogs::unblock_account(Origin::signed(ACCOUNT2), ACCOUNT1)));
+    assert_noop!(Blogs::unblock_account(Origin::signed(ACCOUNT2), ACCOUNT1), MSG_ACCOUNT_IS_NOT_BLOCKED);
+    assert!(Blogs::accounts_blocked_by_account(ACCOUNT2).is_empty());
+  });
+}
+
+// Poll tests
+
+fn poll(multiple_choice: bool, weighted_by_reputation: bool) -> Poll<Test> {
+  Poll {
+    options: vec![b"Yes".to_vec(), b"No".to_vec(), b"Abstain".to_vec()],
+    ends_at: 10,
+    multiple_choice,
+    weighted_by_reputation,
+  }
+}
+
+fn _create_poll(poll: Poll<Test>) -> dispatch::Result {
+  _create_post(None, None, None, Some(PostExtension::Poll(poll)))
+}
+
+fn _vote_in_poll(origin: Option<Origin>, options: Vec<u16>) -> dispatch::Result {
+  checked(Blogs::vote_in_poll(origin.unwrap_or(Origin::signed(ACCOUNT2)), 1, options))
+}
+
+#[test]
+fn vote_in_poll_should_work() {
+  with_externalities(&mut build_ext(), || {
+    assert_ok!(_create_default_blog()); // BlogId 1
+    assert_ok!(_create_poll(self::poll(true, false))); // PostId 1
+    assert_eq!(Blogs::poll_tallies(1), vec![0, 0, 0]);
+    assert_eq!(Blogs::polls_ending_at(10), vec![1]);
+
+    assert_ok!(_vote_in_poll(None, vec![0, 2]));
+    assert_ok!(_vote_in_poll(Some(Origin::signed(ACCOUNT1)), vec![2]));
+    assert_eq!(Blogs::poll_tallies(1), vec![1, 0, 2]);
+    assert_eq!(Blogs::poll_vote_by_account((1, ACCOUNT2)), Some(vec![0, 2]));
+    assert_noop!(_vote_in_poll(None, vec![1]), MSG_ACCOUNT_ALREADY_VOTED_IN_POLL);
+
+    // Votes are accepted up to the end block, then the poll is closed:
+    System::set_block_number(10);
+    <Blogs as OnFinalize<u64>>::on_finalize(10);
+    assert!(Blogs::polls_ending_at(10).is_empty());
+
+    System::set_block_number(11);
+    assert_noop!(_vote_in_poll(Some(Origin::signed(VERIFIER)), vec![1]), MSG_POLL_IS_CLOSED);
+  });
+}
+
+#[test]
+fn vote_in_poll_should_be_weighted_by_reputation() {
+  with_externalities(&mut build_ext(), || {
+    assert_ok!(_create_default_blog()); // BlogId 1
+    assert_ok!(_create_poll(self::poll(false, true))); // PostId 1
+    _set_reputation(ACCOUNT2, 7);
+
+    assert_noop!(_vote_in_poll(None, vec![0, 1]), MSG_POLL_IS_SINGLE_CHOICE);
+    assert_ok!(_vote_in_poll(None, vec![1]));
+    assert_eq!(Blogs::poll_tallies(1), vec![0, 7, 0]);
+  });
+}
+
+#[test]
+fn create_poll_and_vote_should_fail() {
+  with_externalities(&mut build_ext(), || {
+    assert_ok!(_create_default_blog()); // BlogId 1
+
+    let mut poll = self::poll(true, false);
+    poll.options.truncate(1);
+    assert_noop!(_create_poll(poll), MSG_POLL_HAS_TOO_FEW_OPTIONS);
+
+    let mut poll = self::poll(true, false);
+    poll.options[1] = vec![];
+    assert_noop!(_create_poll(poll), MSG_POLL_OPTION_IS_EMPTY);
+
+    let mut poll = self::poll(true, false);
+    poll.ends_at = System::block_number();
+    assert_noop!(_create_poll(poll), MSG_POLL_ENDS_IN_PAST);
+
+    assert_ok!(_create_default_post()); // PostId 1
+    assert_noop!(_vote_in_poll(None, vec![0]), MSG_POST_IS_NOT_POLL);
+
+    assert_ok!(_create_poll(self::poll(true, false))); // PostId 2
+    assert_noop!(Blogs::vote_in_poll(Origin::signed(ACCOUNT2), 2, vec![3]), MSG_POLL_OPTION_NOT_FOUND);
+    assert_noop!(Blogs::vote_in_poll(Origin::signed(ACCOUNT2), 2, vec![1, 1]), MSG_POLL_OPTION_IS_CHOSEN_TWICE);
+    assert_noop!(Blogs::vote_in_poll(Origin::signed(ACCOUNT2), 2, vec![]), MSG_NO_POLL_OPTIONS_CHOSEN);
+  });
+}
+
+// Cross-posting tests
+
+#[test]
+fn cross_post_should_work() {
+  with_externalities(&mut build_ext(), || {
+    assert_ok!(_create_default_blog()); // BlogId 1
+    assert_ok!(_create_blog(None, Some(b"blog2_slug".to_vec()), None)); // BlogId 2
+    assert_ok!(_create_default_post()); // PostId 1
+
+    assert_ok!(checked(Blogs::cross_post(Origin::signed(ACCOUNT1), 1, 2)));
+    assert_eq!(Blogs::cross_post_blog_ids(1), vec![2]);
+    assert_eq!(Blogs::post_ids_by_blog_id(2), vec![1]);
+    assert_eq!(Blogs::blog_counters(2).posts_count, 1);
+
+    // Both blogs get the score of the post:
+    assert_ok!(_create_post_reaction(Some(Origin::signed(ACCOUNT2)), None, None));
+    let score = Blogs::post_counters(1).score;
+    assert_eq!(Blogs::blog_counters(1).score, score);
+    assert_eq!(Blogs::blog_counters(2).score, score);
+    assert_eq!(Blogs::post_score_in_blog((2, 1)), score);
+
+    assert_ok!(checked(Blogs::remove_cross_post(Origin::signed(ACCOUNT1), 1, 2)));
+    assert!(Blogs::post_ids_by_blog_id(2).is_empty());
+    assert_eq!(Blogs::blog_counters(2), BlogCounters::default());
+    assert_eq!(Blogs::blog_counters(1).score, score);
+  });
+}
+
+#[test]
+fn cross_post_should_fail() {
+  with_externalities(&mut build_ext(), || {
+    assert_ok!(_create_default_blog()); // BlogId 1
+    assert_ok!(_create_blog(Some(Origin::signed(ACCOUNT2)), Some(b"blog2_slug".to_vec()), None)); // BlogId 2 by ACCOUNT2
+    assert_ok!(_create_default_post()); // PostId 1
+
+    assert_noop!(Blogs::cross_post(Origin::signed(ACCOUNT2), 1, 2), MSG_ONLY_POST_OWNER_CAN_CROSS_POST);
+    assert_noop!(Blogs::cross_post(Origin::signed(ACCOUNT1), 1, 2), MSG_NO_PERMISSION_TO_CROSS_POST);
+    assert_noop!(Blogs::cross_post(Origin::signed(ACCOUNT1), 1, 1), MSG_POST_IS_ALREADY_IN_BLOG);
+    assert_noop!(Blogs::remove_cross_post(Origin::signed(ACCOUNT2), 1, 2), MSG_POST_IS_NOT_CROSS_POSTED_TO_BLOG);
+
+    // Writers of a blog can cross-post to it:
+    assert_ok!(_update_blog(Some(Origin::signed(ACCOUNT2)), Some(2), Some(BlogUpdate { writers: Some(vec![ACCOUNT1]), slug: None, content: None })));
+    assert_ok!(checked(Blogs::cross_post(Origin::signed(ACCOUNT1), 1, 2)));
+    assert_noop!(_update_post(None, None, Some(self::post_update(Some(2), None))), MSG_POST_IS_ALREADY_IN_BLOG);
+  });
+}
+
+// Pin tests
+
+#[test]
+fn pin_post_should_work() {
+  with_externalities(&mut build_ext(), || {
+    assert_ok!(_create_default_blog()); // BlogId 1
+    assert_ok!(_create_default_post()); // PostId 1
+    assert_ok!(_create_default_post()); // PostId 2
+    assert_ok!(_create_default_post()); // PostId 3
+
+    assert_ok!(checked(Blogs::pin_post(Origin::signed(ACCOUNT1), 1, 3)));
+    assert_ok!(checked(Blogs::pin_post(Origin::signed(ACCOUNT1), 1, 2)));
+    assert_eq!(Blogs::pinned_post_ids(1), vec![3, 2]);
+    assert_eq!(Blogs::blog_post_ids(1), vec![3, 2, 1]);
+
+    assert_ok!(checked(Blogs::unpin_post(Origin::signed(ACCOUNT1), 1, 3)));
+    assert_eq!(Blogs::blog_post_ids(1), vec![2, 1, 3]);
+  });
+}
+
+#[test]
+fn pin_post_should_fail() {
+  with_externalities(&mut build_ext(), || {
+    assert_ok!(Blogs::set_max_pins(system::RawOrigin::Root.into(), 1, 1));
+    assert_ok!(_create_default_blog()); // BlogId 1
+    assert_ok!(_create_blog(None, Some(b"blog2_slug".to_vec()), None)); // BlogId 2
+    assert_ok!(_create_default_post()); // PostId 1
+    assert_ok!(_create_default_post()); // PostId 2
+
+    assert_noop!(Blogs::pin_post(Origin::signed(ACCOUNT2), 1, 1), MSG_ONLY_BLOG_OWNER_CAN_PIN_POST);
+    assert_noop!(Blogs::pin_post(Origin::signed(ACCOUNT1), 2, 1), MSG_POST_IS_NOT_IN_BLOG);
+    assert_noop!(Blogs::unpin_post(Origin::signed(ACCOUNT1), 1, 1), MSG_POST_IS_NOT_PINNED);
+
+    assert_ok!(checked(Blogs::pin_post(Origin::signed(ACCOUNT1), 1, 1)));
+    assert_noop!(Blogs::pin_post(Origin::signed(ACCOUNT1), 1, 1), MSG_POST_IS_ALREADY_PINNED);
+    assert_noop!(Blogs::pin_post(Origin::signed(ACCOUNT1), 1, 2), MSG_TOO_MANY_PINNED_POSTS);
+
+    // Moving a post out of the blog unpins it:
+    assert_ok!(_update_post(None, None, Some(self::post_update(Some(2), None))));
+    assert!(Blogs::pinned_post_ids(1).is_empty());
+  });
+}
+
+#[test]
+fn pin_comment_should_work() {
+  with_externalities(&mut build_ext(), || {
+    assert_ok!(_create_default_blog()); // BlogId 1
+    assert_ok!(_create_default_post()); // PostId 1
+    assert_ok!(_create_default_comment()); // CommentId 1
+    assert_ok!(_create_comment(Some(Origin::signed(ACCOUNT2)), None, None, None)); // CommentId 2
+
+    assert_noop!(Blogs::pin_comment(Origin::signed(ACCOUNT2), 1, 2), MSG_ONLY_POST_OWNER_CAN_PIN_COMMENT);
+    assert_ok!(checked(Blogs::pin_comment(Origin::signed(ACCOUNT1), 1, 2)));
+    assert_eq!(Blogs::post_comment_ids(1), vec![2, 1]);
+    assert_noop!(Blogs::pin_comment(Origin::signed(ACCOUNT1), 1, 2), MSG_COMMENT_IS_ALREADY_PINNED);
+
+    assert_ok!(checked(Blogs::unpin_comment(Origin::signed(ACCOUNT1), 1, 2)));
+    assert_eq!(Blogs::post_comment_ids(1), vec![1, 2]);
+    assert_noop!(Blogs::unpin_comment(Origin::signed(ACCOUNT1), 1, 2), MSG_COMMENT_IS_NOT_PINNED);
+  });
+}
+
+// Bookmark tests
+
+fn collection_name() -> Vec<u8> {
+  b"Reading list".to_vec()
+}
+
+#[test]
+fn bookmark_should_work() {
+  with_externalities(&mut build_ext(), || {
+    assert_ok!(_create_default_blog()); // BlogId 1
+    assert_ok!(_create_default_post()); // PostId 1
+    assert_ok!(_create_default_comment()); // CommentId 1
+
+    assert_ok!(checked(Blogs::bookmark(Origin::signed(ACCOUNT2), Bookmark::Post(1))));
+    assert_ok!(checked(Blogs::bookmark(Origin::signed(ACCOUNT2), Bookmark::Comment(1))));
+    assert_eq!(Blogs::bookmarks_by_account(ACCOUNT2), vec![Bookmark::Post(1), Bookmark::Comment(1)]);
+    assert_noop!(Blogs::bookmark(Origin::signed(ACCOUNT2), Bookmark::Post(1)), MSG_ITEM_IS_ALREADY_BOOKMARKED);
+    assert_noop!(Blogs::bookmark(Origin::signed(ACCOUNT2), Bookmark::Post(2)), MSG_POST_NOT_FOUND);
+
+    assert_ok!(checked(Blogs::unbookmark(Origin::signed(ACCOUNT2), Bookmark::Post(1))));
+    assert_eq!(Blogs::bookmarks_by_account(ACCOUNT2), vec![Bookmark::Comment(1)]);
+    assert_noop!(Blogs::unbookmark(Origin::signed(ACCOUNT2), Bookmark::Post(1)), MSG_ITEM_IS_NOT_BOOKMARKED);
+  });
+}
+
+#[test]
+fn bookmark_should_fail_for_others_post_that_is_not_public() {
+  with_externalities(&mut build_ext(), || {
+    assert_ok!(_create_default_blog()); // BlogId 1
+    assert_ok!(_create_post_with_visibility(PostVisibility::Draft)); // PostId 1
+    assert_ok!(_create_post_with_visibility(PostVisibility::Unlisted)); // PostId 2
+    assert_ok!(checked(Blogs::create_collection(Origin::signed(ACCOUNT2), self::collection_name())));
+
+    assert_noop!(Blogs::bookmark(Origin::signed(ACCOUNT2), Bookmark::Post(1)), MSG_POST_IS_NOT_PUBLIC);
+    assert_noop!(Blogs::bookmark(Origin::signed(ACCOUNT2), Bookmark::Post(2)), MSG_POST_IS_NOT_PUBLIC);
+    assert_noop!(
+      Blogs::add_to_collection(Origin::signed(ACCOUNT2), self::collection_name(), Bookmark::Post(1)),
+      MSG_POST_IS_NOT_PUBLIC
+    );
+
+    // Authors can bookmark their own posts:
+    assert_ok!(checked(Blogs::bookmark(Origin::signed(ACCOUNT1), Bookmark::Post(1))));
+    assert_eq!(Blogs::bookmarks_by_account(ACCOUNT1), vec![Bookmark::Post(1)]);
+  });
+}
+
+#[test]
+fn collections_should_work() {
+  with_externalities(&mut build_ext(), || {
+    assert_ok!(_create_default_blog()); // BlogId 1
+    assert_ok!(_create_default_post()); // PostId 1
+
+    assert_ok!(checked(Blogs::create_collection(Origin::signed(ACCOUNT2), self::collection_name())));
+    assert_noop!(Blogs::create_collection(Origin::signed(ACCOUNT2), self::collection_name()), MSG_COLLECTION_NAME_IS_TAKEN);
+    assert_ok!(checked(Blogs::add_to_collection(Origin::signed(ACCOUNT2), self::collection_name(), Bookmark::Post(1))));
+    assert_noop!(
+      Blogs::add_to_collection(Origin::signed(ACCOUNT2), self::collection_name(), Bookmark::Post(1)),
+      MSG_ITEM_IS_ALREADY_IN_COLLECTION
+    );
+
+    assert_ok!(checked(Blogs::rename_collection(Origin::signed(ACCOUNT2), self::collection_name(), b"Best".to_vec())));
+    let collection = Blogs::collections_by_account(ACCOUNT2).remove(0);
+    assert_eq!(collection.name, b"Best".to_vec());
+    assert_eq!(collection.items, vec![Bookmark::Post(1)]);
+    assert_eq!(collection.created.account, ACCOUNT2);
+
+    assert_ok!(checked(Blogs::remove_from_collection(Origin::signed(ACCOUNT2), b"Best".to_vec(), Bookmark::Post(1))));
+    assert!(Blogs::collections_by_account(ACCOUNT2)[0].items.is_empty());
+
+    assert_noop!(Blogs::delete_collection(Origin::signed(ACCOUNT2), self::collection_name()), MSG_COLLECTION_NOT_FOUND);
+    assert_ok!(checked(Blogs::delete_collection(Origin::signed(ACCOUNT2), b"Best".to_vec())));
+    assert!(Blogs::collections_by_account(ACCOUNT2).is_empty());
+  });
+}
+
+#[test]
+fn create_collection_should_fail_with_invalid_name() {
+  with_externalities(&mut build_ext(), || {
+    assert_noop!(Blogs::create_collection(Origin::signed(ACCOUNT1), vec![]), MSG_COLLECTION_NAME_IS_EMPTY);
+    assert_noop!(
+      Blogs::create_collection(Origin::signed(ACCOUNT1), vec![b'a'; DEFAULT_COLLECTION_NAME_MAX_LEN as usize + 1]),
+      MSG_COLLECTION_NAME_IS_TOO_LONG
+    );
+    assert_noop!(Blogs::create_collection(Origin::signed(ACCOUNT1), vec![0xff]), MSG_COLLECTION_NAME_IS_NOT_UTF8);
+  });
+}
+
+// Co-author tests
+
+#[test]
+fn accept_co_authorship_should_work() {
+  with_externalities(&mut build_ext(), || {
+    assert_ok!(_create_default_blog()); // BlogId 1
+    assert_ok!(_create_default_post()); // PostId 1
+
+    assert_ok!(checked(Blogs::invite_co_author(Origin::signed(ACCOUNT1), 1, ACCOUNT2, 30)));
+    assert_ok!(checked(Blogs::invite_co_author(Origin::signed(ACCOUNT1), 1, VERIFIER, 20)));
+    assert_eq!(Blogs::co_author_invites(1), vec![(ACCOUNT2, 30), (VERIFIER, 20)]);
+    assert_eq!(Blogs::unread_notifications_count(ACCOUNT2), 1);
+
+    assert_ok!(checked(Blogs::accept_co_authorship(Origin::signed(ACCOUNT2), 1)));
+    assert_ok!(checked(Blogs::remove_co_author_invite(Origin::signed(VERIFIER), 1, VERIFIER)));
+    assert!(Blogs::co_author_invites(1).is_empty());
+    assert_eq!(Blogs::co_authors(1), vec![(ACCOUNT2, 30)]);
+  });
+}
+
+#[test]
+fn invite_co_author_should_fail() {
+  with_externalities(&mut build_ext(), || {
+    assert_ok!(_create_default_blog()); // BlogId 1
+    assert_ok!(_create_default_post()); // PostId 1
+
+    assert_noop!(Blogs::invite_co_author(Origin::signed(ACCOUNT2), 1, VERIFIER, 10), MSG_ONLY_POST_OWNER_CAN_INVITE_CO_AUTHOR);
+    assert_noop!(Blogs::invite_co_author(Origin::signed(ACCOUNT1), 1, ACCOUNT1, 10), MSG_POST_OWNER_CANNOT_BE_CO_AUTHOR);
+    assert_noop!(Blogs::invite_co_author(Origin::signed(ACCOUNT1), 1, ACCOUNT2, 0), MSG_CO_AUTHOR_SHARE_IS_ZERO);
+    assert_noop!(Blogs::invite_co_author(Origin::signed(ACCOUNT1), 1, ACCOUNT2, 100), MSG_CO_AUTHOR_SHARES_ARE_TOO_BIG);
+
+    assert_ok!(checked(Blogs::invite_co_author(Origin::signed(ACCOUNT1), 1, ACCOUNT2, 60)));
+    assert_noop!(Blogs::invite_co_author(Origin::signed(ACCOUNT1), 1, ACCOUNT2, 10), MSG_ACCOUNT_IS_ALREADY_CO_AUTHOR);
+    assert_noop!(Blogs::invite_co_author(Origin::signed(ACCOUNT1), 1, VERIFIER, 40), MSG_CO_AUTHOR_SHARES_ARE_TOO_BIG);
+    assert_noop!(Blogs::accept_co_authorship(Origin::signed(VERIFIER), 1), MSG_CO_AUTHOR_INVITE_NOT_FOUND);
+    assert_noop!(Blogs::remove_co_author_invite(Origin::signed(VERIFIER), 1, ACCOUNT2), MSG_NO_PERMISSION_TO_REMOVE_CO_AUTHOR_INVITE);
+  });
+}
+
+#[test]
+fn post_reputation_should_be_split_among_co_authors() {
+  with_externalities(&mut build_ext(), || {
+    assert_ok!(_create_default_blog()); // BlogId 1
+    assert_ok!(_create_default_post()); // PostId 1
+    assert_ok!(checked(Blogs::invite_co_author(Origin::signed(ACCOUNT1), 1, VERIFIER, 40)));
+    assert_ok!(checked(Blogs::accept_co_authorship(Origin::signed(VERIFIER), 1)));
+
+    // Upvote weight is 5: the co-author gets 2 and the creator gets the rest:
+    assert_ok!(_create_post_reaction(Some(Origin::signed(ACCOUNT2)), None, None)); // ReactionId 1
+    assert_eq!(Blogs::social_account_by_id(ACCOUNT1).unwrap().reputation, 4);
+    assert_eq!(Blogs::social_account_by_id(VERIFIER).unwrap().reputation, 3);
+
+    assert_ok!(_delete_post_reaction(Some(Origin::signed(ACCOUNT2)), None, 1));
+    assert_eq!(Blogs::social_account_by_id(ACCOUNT1).unwrap().reputation, 1);
+    assert_eq!(Blogs::social_account_by_id(VERIFIER).unwrap().reputation, 1);
+  });
+}
+
+#[test]
+fn post_reputation_should_be_reverted_per_post_with_shared_co_author() {
+  with_externalities(&mut build_ext(), || {
+    assert_ok!(_create_default_blog()); // BlogId 1
+    assert_ok!(_create_default_post()); // PostId 1
+    assert_ok!(_create_default_post()); // PostId 2
+    for post_id in 1..3 {
+      assert_ok!(checked(Blogs::invite_co_author(Origin::signed(ACCOUNT1), post_id, VERIFIER, 40)));
+      assert_ok!(checked(Blogs::accept_co_authorship(Origin::signed(VERIFIER), post_id)));
+    }
+
+    assert_ok!(_create_post_reaction(Some(Origin::signed(ACCOUNT2)), Some(1), None)); // ReactionId 1
+    assert_ok!(_create_post_reaction(Some(Origin::signed(ACCOUNT2)), Some(2), None)); // ReactionId 2
+    assert_eq!(Blogs::social_account_by_id(ACCOUNT1).unwrap().reputation, 7);
+    assert_eq!(Blogs::social_account_by_id(VERIFIER).unwrap().reputation, 5);
+    assert_eq!(Blogs::post_reputation_diff_by_account((ACCOUNT2, 1, ScoringAction::UpvotePost, VERIFIER)), Some(2));
+    assert_eq!(Blogs::post_reputation_diff_by_account((ACCOUNT2, 2, ScoringAction::UpvotePost, VERIFIER)), Some(2));
+
+    // Only the split of the first post is reverted:
+    assert_ok!(_delete_post_reaction(Some(Origin::signed(ACCOUNT2)), Some(1), 1));
+    assert_eq!(Blogs::social_account_by_id(ACCOUNT1).unwrap().reputation, 4);
+    assert_eq!(Blogs::social_account_by_id(VERIFIER).unwrap().reputation, 3);
+    assert!(Blogs::post_reputation_diff_by_account((ACCOUNT2, 1, ScoringAction::UpvotePost, VERIFIER)).is_none());
+
+    assert_ok!(_delete_post_reaction(Some(Origin::signed(ACCOUNT2)), Some(2), 2));
+    assert_eq!(Blogs::social_account_by_id(ACCOUNT1).unwrap().reputation, 1);
+    assert_eq!(Blogs::social_account_by_id(VERIFIER).unwrap().reputation, 1);
+  });
+}
+
+#[test]
+fn tip_post_should_be_split_among_co_authors() {
+  with_externalities(&mut build_ext(), || {
+    let _ = Balances::deposit_creating(&ACCOUNT2, 1_000);
+    assert_ok!(_create_default_blog()); // BlogId 1
+    assert_ok!(_create_default_post()); // PostId 1
+    assert_ok!(checked(Blogs::invite_co_author(Origin::signed(ACCOUNT1), 1, VERIFIER, 33)));
+    assert_ok!(checked(Blogs::accept_co_authorship(Origin::signed(VERIFIER), 1)));
+
+    assert_noop!(Blogs::tip_post(Origin::signed(ACCOUNT2), 1, 0), MSG_TIP_IS_ZERO);
+    assert!(Blogs::tip_post(Origin::signed(ACCOUNT2), 1, 2_000).is_err());
+
+    assert_ok!(checked(Blogs::tip_post(Origin::signed(ACCOUNT2), 1, 100)));
+    assert_eq!(Balances::free_balance(ACCOUNT2), 900);
+    assert_eq!(Balances::free_balance(VERIFIER), 33);
+    assert_eq!(Balances::free_balance(ACCOUNT1), 67);
+  });
+}
+
+#[test]
+fn tip_post_should_split_big_tips_exactly() {
+  with_externalities(&mut build_ext(), || {
+    let amount: u64 = 1 << 62;
+    let _ = Balances::deposit_creating(&ACCOUNT2, amount + 1);
+    assert_ok!(_create_default_blog()); // BlogId 1
+    assert_ok!(_create_default_post()); // PostId 1
+    assert_ok!(checked(Blogs::invite_co_author(Origin::signed(ACCOUNT1), 1, VERIFIER, 33)));
+    assert_ok!(checked(Blogs::accept_co_authorship(Origin::signed(VERIFIER), 1)));
+
+    assert_ok!(checked(Blogs::tip_post(Origin::signed(ACCOUNT2), 1, amount)));
+    let co_author_part = (amount as u128 * 33 / 100) as u64;
+    assert_eq!(Balances::free_balance(VERIFIER), co_author_part);
+    assert_eq!(Balances::free_balance(ACCOUNT1), amount - co_author_part);
+  });
+}
+
+#[test]
+fn tip_post_should_not_burn_parts_below_existential_deposit() {
+  with_externalities(&mut build_ext_with_existential_deposit(10), || {
+    let _ = Balances::deposit_creating(&ACCOUNT2, 1_000);
+    assert_ok!(_create_default_blog()); // BlogId 1
+    assert_ok!(_create_default_post()); // PostId 1
+    assert_ok!(checked(Blogs::invite_co_author(Origin::signed(ACCOUNT1), 1, VERIFIER, 5)));
+    assert_ok!(checked(Blogs::accept_co_authorship(Origin::signed(VERIFIER), 1)));
+
+    // Neither author can open an account with such a tip, so it goes back to the tipper:
+    assert_ok!(checked(Blogs::tip_post(Origin::signed(ACCOUNT2), 1, 5)));
+    assert_eq!(Balances::free_balance(ACCOUNT2), 1_000);
+    assert_eq!(Balances::free_balance(ACCOUNT1), 0);
+
+    // The co-author's part is too small, so the creator gets it:
+    assert_ok!(checked(Blogs::tip_post(Origin::signed(ACCOUNT2), 1, 100)));
+    assert_eq!(Balances::free_balance(ACCOUNT2), 900);
+    assert_eq!(Balances::free_balance(VERIFIER), 0);
+    assert_eq!(Balances::free_balance(ACCOUNT1), 100);
+  });
+}
+
+// Reward pool tests
+
+fn finalize_block(now: u64) {
+  System::set_block_number(now);
+  Blogs::on_finalize(now);
+  check_invariants();
+}
+
+#[test]
+fn deposit_to_reward_pool_should_work() {
+  with_externalities(&mut build_ext(), || {
+    let _ = Balances::deposit_creating(&ACCOUNT1, 1_000);
+
+    assert_noop!(Blogs::deposit_to_reward_pool(Origin::signed(ACCOUNT1), 0), MSG_REWARD_DEPOSIT_IS_ZERO);
+    assert_ok!(checked(Blogs::deposit_to_reward_pool(Origin::signed(ACCOUNT1), 100)));
+    assert!(Blogs::mint_to_reward_pool(Origin::signed(ACCOUNT1), 100).is_err());
+    assert_ok!(Blogs::mint_to_reward_pool(system::RawOrigin::Root.into(), 50));
+
+    assert_eq!(Balances::free_balance(ACCOUNT1), 900);
+    assert_eq!(Blogs::reward_pool(), 150);
+  });
+}
+
+#[test]
+fn add_epoch_score_should_not_underflow_positive_score() {
+  with_externalities(&mut build_ext(), || {
+    <EpochScoreByAccount<Test>>::insert((0, ACCOUNT1), 10);
+    <EpochPositiveScore<Test>>::insert(0, 5);
+
+    Blogs::add_epoch_score(ACCOUNT1, -10);
+    assert_eq!(Blogs::epoch_score_by_account((0, ACCOUNT1)), 0);
+    assert_eq!(Blogs::epoch_positive_score(0), 0);
+
+    <EpochPositiveScore<Test>>::insert(0, u64::max_value());
+    Blogs::add_epoch_score(ACCOUNT2, 10);
+    assert_eq!(Blogs::epoch_positive_score(0), u64::max_value());
+  });
+}
+
+#[test]
+fn rewards_should_be_distributed_by_epoch_score_in_chunks() {
+  with_externalities(&mut build_ext(), || {
+    assert_ok!(Blogs::set_reward_epoch(system::RawOrigin::Root.into(), 10, 1));
+    assert_ok!(Blogs::mint_to_reward_pool(system::RawOrigin::Root.into(), 300));
+    assert_ok!(_create_default_blog()); // BlogId 1
+    assert_ok!(_create_blog(Some(Origin::signed(ACCOUNT2)), Some(b"blog2_slug".to_vec()), None)); // BlogId 2
+    assert_ok!(_create_default_post()); // PostId 1
+    assert_ok!(_create_post(Some(Origin::signed(ACCOUNT2)), Some(2), None, None)); // PostId 2
+
+    // ACCOUNT1 gains 5 by a comment and 5 by an upvote, ACCOUNT2 gains 5 by an upvote:
+    assert_ok!(_create_comment(Some(Origin::signed(ACCOUNT2)), None, None, None));
+    assert_ok!(_create_post_reaction(Some(Origin::signed(VERIFIER)), Some(1), None));
+    assert_ok!(_create_post_reaction(Some(Origin::signed(VERIFIER)), Some(2), None));
+    assert_eq!(Blogs::epoch_score_by_account((0, ACCOUNT1)), 10);
+    assert_eq!(Blogs::epoch_score_by_account((0, ACCOUNT2)), 5);
+
+    finalize_block(9);
+    assert_eq!(Blogs::reward_epoch(), 0);
+
+    finalize_block(10);
+    assert_eq!(Blogs::reward_epoch(), 1);
+    assert_eq!(Blogs::reward_pool(), 0);
+
+    // One author per block:
+    finalize_block(11);
+    assert_eq!(Balances::free_balance(ACCOUNT1), 200);
+    assert_eq!(Balances::free_balance(ACCOUNT2), 0);
+    assert!(Blogs::pending_reward_distribution().is_some());
+
+    finalize_block(12);
+    assert_eq!(Balances::free_balance(ACCOUNT2), 100);
+    assert!(Blogs::pending_reward_distribution().is_none());
+    assert_eq!(Blogs::epoch_scored_accounts_count(0), 0);
+    assert_eq!(Blogs::epoch_score_by_account((0, ACCOUNT1)), 0);
+  });
+}
+
+#[test]
+fn rewards_below_existential_deposit_should_go_back_to_pool() {
+  with_externalities(&mut build_ext_with_existential_deposit(150), || {
+    assert_ok!(Blogs::set_reward_epoch(system::RawOrigin::Root.into(), 10, 2));
+    assert_ok!(Blogs::mint_to_reward_pool(system::RawOrigin::Root.into(), 300));
+    assert_ok!(_create_default_blog()); // BlogId 1
+    assert_ok!(_create_blog(Some(Origin::signed(ACCOUNT2)), Some(b"blog2_slug".to_vec()), None)); // BlogId 2
+    assert_ok!(_create_default_post()); // PostId 1
+    assert_ok!(_create_post(Some(Origin::signed(ACCOUNT2)), Some(2), None, None)); // PostId 2
+
+    // ACCOUNT1 gains 10 and ACCOUNT2 gains 5:
+    assert_ok!(_create_comment(Some(Origin::signed(ACCOUNT2)), None, None, None));
+    assert_ok!(_create_post_reaction(Some(Origin::signed(VERIFIER)), Some(1), None));
+    assert_ok!(_create_post_reaction(Some(Origin::signed(VERIFIER)), Some(2), None));
+
+    finalize_block(10);
+    finalize_block(11);
+    assert!(Blogs::pending_reward_distribution().is_none());
+    assert_eq!(Balances::free_balance(ACCOUNT1), 200);
+
+    // The reward of 100 could not create the account of ACCOUNT2:
+    assert_eq!(Balances::free_balance(ACCOUNT2), 0);
+    assert_eq!(Blogs::reward_pool(), 100);
+  });
+}
+
+#[test]
+fn reward_pool_should_be_kept_if_no_score_was_gained() {
+  with_externalities(&mut build_ext(), || {
+    assert_ok!(Blogs::set_reward_epoch(system::RawOrigin::Root.into(), 10, 1));
+    assert_ok!(Blogs::mint_to_reward_pool(system::RawOrigin::Root.into(), 300));
+
+    finalize_block(10);
+    finalize_block(11);
+    assert_eq!(Blogs::reward_epoch(), 1);
+    assert!(Blogs::pending_reward_distribution().is_none());
+    assert_eq!(Blogs::reward_pool(), 300);
+  });
+}
+
+// Curation tests
+
+#[test]
+fn earlier_upvoters_should_get_curation_score() {
+  with_externalities(&mut build_ext(), || {
+    assert_ok!(_create_default_blog()); // BlogId 1
+    assert_ok!(_create_default_post()); // PostId 1
+
+    // The first upvote has no one to curate:
+    assert_ok!(_create_post_reaction(Some(Origin::signed(ACCOUNT2)), None, None)); // ReactionId 1
+    assert_eq!(Blogs::epoch_score_by_account((0, ACCOUNT1)), 5);
+
+    // A quarter of the next upvote goes to the earlier upvoter:
+    assert_ok!(_create_post_reaction(Some(Origin::signed(VERIFIER)), None, None)); // ReactionId 2
+    assert_eq!(Blogs::post_upvoters(1), vec![(ACCOUNT2, 1), (VERIFIER, 1)]);
+    assert_eq!(Blogs::epoch_score_by_account((0, ACCOUNT1)), 9);
+    assert_eq!(Blogs::epoch_score_by_account((0, ACCOUNT2)), 1);
+    assert_eq!(Blogs::epoch_score_by_account((0, VERIFIER)), 0);
+  });
+}
+
+#[test]
+fn reversed_upvotes_should_not_count_for_curation() {
+  with_externalities(&mut build_ext(), || {
+    assert_ok!(_create_default_blog()); // BlogId 1
+    assert_ok!(_create_default_post()); // PostId 1
+    assert_ok!(_create_post_reaction(Some(Origin::signed(ACCOUNT2)), None, None)); // ReactionId 1
+    assert_ok!(_create_post_reaction(Some(Origin::signed(VERIFIER)), None, None)); // ReactionId 2
+
+    assert_ok!(_delete_post_reaction(Some(Origin::signed(VERIFIER)), None, 2));
+    assert_eq!(Blogs::post_upvoters(1), vec![(ACCOUNT2, 1)]);
+    assert_eq!(Blogs::epoch_score_by_account((0, ACCOUNT1)), 5);
+    assert_eq!(Blogs::epoch_score_by_account((0, ACCOUNT2)), 0);
+
+    assert_ok!(checked(Blogs::update_post_reaction(Origin::signed(ACCOUNT2), 1, 1, ReactionKind::Downvote)));
+    assert!(Blogs::post_upvoters(1).is_empty());
+  });
+}
+
+#[test]
+fn reversed_upvote_should_take_back_only_its_own_curation() {
+  with_externalities(&mut build_ext(), || {
+    assert_ok!(_create_default_blog()); // BlogId 1
+    assert_ok!(_create_default_post()); // PostId 1
+    assert_ok!(_create_post_reaction(Some(Origin::signed(ACCOUNT2)), None, None)); // ReactionId 1
+    assert_ok!(_create_post_reaction(Some(Origin::signed(VERIFIER)), None, None)); // ReactionId 2
+    assert_eq!(Blogs::post_curation_by_account((VERIFIER, 1, ScoringAction::UpvotePost)), vec![(ACCOUNT2, 1)]);
+
+    // The first upvote has paid no curation, so the later upvoter keeps its score:
+    assert_ok!(_delete_post_reaction(Some(Origin::signed(ACCOUNT2)), None, 1));
+    assert_eq!(Blogs::post_upvoters(1), vec![(VERIFIER, 1)]);
+    assert_eq!(Blogs::epoch_score_by_account((0, ACCOUNT1)), 4);
+    assert_eq!(Blogs::epoch_score_by_account((0, ACCOUNT2)), 1);
+    assert_eq!(Blogs::epoch_score_by_account((0, VERIFIER)), 0);
+
+    // The second upvote takes back the curation it has paid to the first upvoter:
+    assert_ok!(_delete_post_reaction(Some(Origin::signed(VERIFIER)), None, 2));
+    assert!(Blogs::post_curation_by_account((VERIFIER, 1, ScoringAction::UpvotePost)).is_empty());
+    assert_eq!(Blogs::epoch_score_by_account((0, ACCOUNT1)), 0);
+    assert_eq!(Blogs::epoch_score_by_account((0, ACCOUNT2)), 0);
+    assert_eq!(Blogs::epoch_score_by_account((0, VERIFIER)), 0);
+  });
+}
+
+#[test]
+fn only_first_upvoters_should_be_curated() {
+  with_externalities(&mut build_ext(), || {
+    <MaxCuratorsPerPost<Test>>::put(1);
+    assert_ok!(_create_default_blog()); // BlogId 1
+    assert_ok!(_create_default_post()); // PostId 1
+    assert_ok!(_create_post_reaction(Some(Origin::signed(ACCOUNT2)), None, None)); // ReactionId 1
+    assert_ok!(_create_post_reaction(Some(Origin::signed(VERIFIER)), None, None)); // ReactionId 2
+
+    assert_eq!(Blogs::post_upvoters(1), vec![(ACCOUNT2, 1)]);
+    assert_eq!(Blogs::epoch_score_by_account((0, ACCOUNT2)), 1);
+  });
+}
+
+#[test]
+fn set_curation_reward_percent_should_fail_over_100() {
+  with_externalities(&mut build_ext(), || {
+    assert_noop!(
+      Blogs::set_curation_reward_percent(system::RawOrigin::Root.into(), 101),
+      MSG_CURATION_REWARD_PERCENT_IS_TOO_BIG
+    );
+  });
+}
+
+// Voting stake tests
+
+fn voting_stake_lock(account: AccountId) -> Option<u64> {
+  Balances::locks(account).into_iter().find(|lock| lock.id == VOTING_STAKE_LOCK_ID).map(|lock| lock.amount)
+}
+
+#[test]
+fn lock_and_unbond_voting_stake_should_work() {
+  with_externalities(&mut build_ext(), || {
+    let _ = Balances::deposit_creating(&ACCOUNT1, 1_000);
+
+    assert_noop!(Blogs::lock_voting_stake(Origin::signed(ACCOUNT1), 0), MSG_VOTING_STAKE_IS_ZERO);
+    assert_noop!(Blogs::lock_voting_stake(Origin::signed(ACCOUNT1), 2_000), MSG_NOT_ENOUGH_BALANCE_FOR_VOTING_STAKE);
+    assert_ok!(checked(Blogs::lock_voting_stake(Origin::signed(ACCOUNT1), 600)));
+    assert_eq!(Blogs::voting_stake(ACCOUNT1), 600);
+    assert_eq!(voting_stake_lock(ACCOUNT1), Some(600));
+
+    assert_noop!(Blogs::unbond_voting_stake(Origin::signed(ACCOUNT1), 700), MSG_NOT_ENOUGH_VOTING_STAKE_TO_UNBOND);
+    assert_ok!(checked(Blogs::unbond_voting_stake(Origin::signed(ACCOUNT1), 200)));
+    assert_eq!(Blogs::voting_stake(ACCOUNT1), 400);
+    assert_eq!(Blogs::unbonding_stake(ACCOUNT1), Some((200, DEFAULT_STAKE_UNBONDING_PERIOD)));
+    assert_eq!(voting_stake_lock(ACCOUNT1), Some(600));
+    assert_noop!(Blogs::lock_voting_stake(Origin::signed(ACCOUNT1), 500), MSG_NOT_ENOUGH_BALANCE_FOR_VOTING_STAKE);
+
+    assert_noop!(Blogs::withdraw_unbonded_stake(Origin::signed(ACCOUNT1)), MSG_STAKE_IS_STILL_UNBONDING);
+    System::set_block_number(DEFAULT_STAKE_UNBONDING_PERIOD);
+    assert_ok!(checked(Blogs::withdraw_unbonded_stake(Origin::signed(ACCOUNT1))));
+    assert_eq!(Blogs::unbonding_stake(ACCOUNT1), None);
+    assert_eq!(voting_stake_lock(ACCOUNT1), Some(400));
+    assert_noop!(Blogs::withdraw_unbonded_stake(Origin::signed(ACCOUNT1)), MSG_NO_UNBONDING_STAKE);
+  });
+}
+
+#[test]
+fn unbond_voting_stake_should_restart_period_for_whole_unbonding_amount() {
+  with_externalities(&mut build_ext(), || {
+    let _ = Balances::deposit_creating(&ACCOUNT1, 1_000);
+    assert_ok!(checked(Blogs::lock_voting_stake(Origin::signed(ACCOUNT1), 600)));
+    assert_ok!(checked(Blogs::unbond_voting_stake(Origin::signed(ACCOUNT1), 200)));
+
+    System::set_block_number(5);
+    assert_ok!(checked(Blogs::unbond_voting_stake(Origin::signed(ACCOUNT1), 100)));
+    assert_eq!(Blogs::unbonding_stake(ACCOUNT1), Some((300, 5 + DEFAULT_STAKE_UNBONDING_PERIOD)));
+
+    System::set_block_number(DEFAULT_STAKE_UNBONDING_PERIOD);
+    assert_noop!(Blogs::withdraw_unbonded_stake(Origin::signed(ACCOUNT1)), MSG_STAKE_IS_STILL_UNBONDING);
+  });
+}
+
+#[test]
+fn unbond_voting_stake_should_fail_on_overflow() {
+  with_externalities(&mut build_ext(), || {
+    <VotingStake<Test>>::insert(ACCOUNT1, 100);
+    <UnbondingStake<Test>>::insert(ACCOUNT1, (u64::max_value() - 10, 1));
+
+    assert_noop!(Blogs::unbond_voting_stake(Origin::signed(ACCOUNT1), 100), MSG_OUT_OF_BOUNDS_UNBONDING_VOTING_STAKE);
+  });
+}
+
+#[test]
+fn lock_voting_stake_should_fail_on_overflow() {
+  with_externalities(&mut build_ext(), || {
+    let _ = Balances::deposit_creating(&ACCOUNT1, u64::max_value());
+    assert_ok!(checked(Blogs::lock_voting_stake(Origin::signed(ACCOUNT1), u64::max_value() - 10)));
+
+    assert_noop!(Blogs::lock_voting_stake(Origin::signed(ACCOUNT1), 100), MSG_OUT_OF_BOUNDS_LOCKING_VOTING_STAKE);
+    assert_eq!(Blogs::voting_stake(ACCOUNT1), u64::max_value() - 10);
+    assert_eq!(voting_stake_lock(ACCOUNT1), Some(u64::max_value() - 10));
+  });
+}
+
+#[test]
+fn score_level_percent_should_not_overflow() {
+  assert_eq!(Blogs::score_level_percent(1), 100);
+  assert_eq!(Blogs::score_level_percent(50_000_000), 2_649);
+  assert_eq!(Blogs::score_level_percent(u32::max_value()), 3_299);
+}
+
+#[test]
+fn voting_stake_should_weigh_scoring_actions() {
+  with_externalities(&mut build_ext(), || {
+    let _ = Balances::deposit_creating(&ACCOUNT2, 1_000);
+    assert_ok!(Blogs::set_voting_stake_params(system::RawOrigin::Root.into(), 50, 100, 10));
+    assert_ok!(checked(Blogs::lock_voting_stake(Origin::signed(ACCOUNT2), 800))); // 8 points
+    assert_ok!(_create_default_blog()); // BlogId 1
+    assert_ok!(_create_default_post()); // PostId 1
+
+    // Half of the reputation level (1) and half of the stake level (4) times the upvote weight (5):
+    assert_eq!(Blogs::get_account_score_diff(&ACCOUNT2, 1, ScoringAction::UpvotePost), 12);
+    // Without stake only half of the reputation level counts:
+    assert_eq!(Blogs::get_account_score_diff(&VERIFIER, 1, ScoringAction::UpvotePost), 2);
+
+    assert_ok!(_create_post_reaction(Some(Origin::signed(ACCOUNT2)), None, None));
+    assert_eq!(Blogs::post_counters(1).score, 12);
+
+    assert_noop!(
+      Blogs::set_voting_stake_params(system::RawOrigin::Root.into(), 101, 100, 10),
+      MSG_STAKE_WEIGHT_PERCENT_IS_TOO_BIG
+    );
+  });
+}